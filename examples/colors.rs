@@ -54,7 +54,7 @@ impl<'a> Application for Demo<'a> {
                 height as f32 * 0.5,
                 "hello, world",
                 font,
-                width / 15,
+                width as f32 / 15.0,
             ),
             None => {}
         }
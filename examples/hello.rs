@@ -95,7 +95,7 @@ impl<'a> Application for Demo<'a> {
                 height as f32 * 0.7,
                 str_0,
                 font,
-                width / 15,
+                width as f32 / 15.0,
             ),
             None => {}
         }
@@ -106,7 +106,7 @@ impl<'a> Application for Demo<'a> {
                 height as f32 * 0.5,
                 &str_1,
                 font,
-                width / 15,
+                width as f32 / 15.0,
             ),
             None => {}
         }
@@ -117,7 +117,7 @@ impl<'a> Application for Demo<'a> {
                 height as f32 * 0.3,
                 str_2,
                 font,
-                width / 15,
+                width as f32 / 15.0,
             ),
             None => {}
         }
@@ -128,7 +128,7 @@ impl<'a> Application for Demo<'a> {
                 height as f32 * 0.1,
                 str_3,
                 font,
-                width / 15,
+                width as f32 / 15.0,
             ),
             None => {}
         }
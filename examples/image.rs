@@ -16,25 +16,20 @@ extern crate reqwest;
 extern crate weekit;
 
 use std::io::Read;
-use weekit::openvg::{vgSetPixels, VGImage, VGubyte};
 use weekit::*;
 
 const IMAGE_PATH: &str = "https://picsum.photos/800/480";
 
-extern "C" {
-    fn createImageFromJpegData(data: *const VGubyte, length: usize) -> VGImage;
-}
-
 struct Demo<'a> {
     serif_typeface: Option<font::Font<'a>>,
-    image: VGImage,
+    image: Option<image::Image>,
 }
 
 impl<'a> Demo<'a> {
     fn new() -> Demo<'a> {
         Demo {
             serif_typeface: None,
-            image: 0,
+            image: None,
         }
     }
     fn load_fonts(&mut self) -> () {
@@ -46,8 +41,9 @@ impl<'a> Demo<'a> {
         assert!(resp.status().is_success());
         let mut buffer = Vec::new();
         resp.read_to_end(&mut buffer).unwrap();
-        unsafe {
-            self.image = createImageFromJpegData(buffer.as_ptr(), buffer.len());
+        match image::Image::from_bytes(&buffer) {
+            Ok(image) => self.image = Some(image),
+            Err(e) => println!("{}", e),
         }
     }
 }
@@ -62,18 +58,32 @@ impl<'a> Application for Demo<'a> {
         let canvas = draw::Canvas::new(width, height);
         canvas.background(192, 0, 0);
 
-        unsafe {
-            vgSetPixels(0, 0, self.image, 0, 0, 800, 480);
+        match self.image {
+            Some(ref image) => draw::image_fit(
+                image,
+                0.0,
+                0.0,
+                width as f32,
+                height as f32,
+                draw::Fit::Cover,
+            ),
+            None => {}
         }
 
         draw::fill(255, 255, 255, 1.0); // White text
+        draw::set_text_shadow(Some(draw::TextShadow::new(
+            2.0,
+            -2.0,
+            3.0,
+            draw::rgba(0, 0, 0, 0.8),
+        )));
         match self.serif_typeface {
             Some(ref font) => draw::text_mid(
                 width as f32 / 2.0,
                 height as f32 * 0.1,
                 IMAGE_PATH,
                 font,
-                width / 30,
+                width as f32 / 30.0,
             ),
             None => {}
         }
@@ -205,7 +205,7 @@ impl<'a> Application for Life<'a> {
                 height as f32 * 0.4,
                 &str_0,
                 font,
-                width / 5,
+                width as f32 / 5.0,
             ),
             None => {}
         }
@@ -215,7 +215,7 @@ impl<'a> Application for Life<'a> {
                 height as f32 * 0.05,
                 &str_1,
                 font,
-                width / 20,
+                width as f32 / 20.0,
             ),
             None => {}
         }
@@ -226,6 +226,7 @@ impl<'a> Application for Life<'a> {
             &event::Event::Touch(t, _) => self.handle_touch(t),
             &event::Event::Key(k, _) => self.handle_key(k),
             &event::Event::Tick(_) => self.handle_tick(),
+            _ => {}
         }
     }
 }
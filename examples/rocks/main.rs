@@ -531,7 +531,18 @@ impl Application for Rocks {
                 2.0 * rock.body.radius,
             );
         }
-        // draw the shots
+        // draw the shots with an additive glow
+        draw::set_blend_mode(draw::BlendMode::Additive);
+        draw::fill(255, 64, 0, 0.3);
+        draw::stroke(255, 0, 0, 0.0);
+        for shot in &self.shots {
+            draw::circle(
+                shot.body.position.x,
+                shot.body.position.y,
+                4.0 * shot.body.radius,
+            );
+        }
+        draw::set_blend_mode(draw::BlendMode::SrcOver);
         draw::fill(255, 0, 0, 0.7);
         draw::stroke(255, 0, 0, 1.0);
         for shot in &self.shots {
@@ -557,6 +568,7 @@ impl Application for Rocks {
             &event::Event::Touch(t, _) => self.handle_touch(t),
             &event::Event::Key(k, _) => self.handle_key(k),
             &event::Event::Tick(_) => self.handle_tick(),
+            _ => {}
         }
         match self.rx.try_recv() {
             Err(std::sync::mpsc::TryRecvError::Empty) => {}
@@ -0,0 +1,45 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate svgkit;
+extern crate weekit;
+
+use weekit::*;
+
+struct Demo {
+    svg: svgkit::Element,
+}
+
+impl Application for Demo {
+    fn draw(&mut self, width: u32, height: u32) -> () {
+        let canvas = draw::Canvas::new(width, height);
+        canvas.background(255, 255, 255);
+
+        // Draws the same document at several sizes along the bottom of the screen.
+        let mut x = 10.0;
+        let mut size = height as f32 / 8.0;
+        while x + size < width as f32 {
+            self.svg.draw(x, 10.0, size, size);
+            x += size + 10.0;
+            size *= 1.5;
+        }
+    }
+}
+
+fn main() {
+    let svg = svgkit::Reader::new()
+        .read_str(include_str!("../08_01.svg"))
+        .expect("unable to read svg");
+    weekit::main(Demo { svg: svg });
+}
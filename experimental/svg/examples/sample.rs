@@ -1,7 +1,7 @@
 // Copyright 2018 The WeeKit Authors. All Rights Reserved.
 //
 // Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License. 
+// you may not use this file except in compliance with the License.
 // You may obtain a copy of the License at
 //
 //    http://www.apache.org/licenses/LICENSE-2.0
@@ -20,6 +20,8 @@ fn main() {
     let filename = "08_01.svg";
     let mut f = File::open(filename).expect("file not found");
     let mut reader = svgkit::Reader::new();
-    let svg = reader.read(&mut f);
-    println!("{:?}", svg);
+    match reader.read(&mut f) {
+        Ok(svg) => println!("{:?}", svg),
+        Err(e) => println!("{}", e),
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright 2018 The WeeKit Authors. All Rights Reserved.
 //
 // Licensed under the Apache License, Version 2.0 (the "License");
-// you may not use this file except in compliance with the License. 
+// you may not use this file except in compliance with the License.
 // You may obtain a copy of the License at
 //
 //    http://www.apache.org/licenses/LICENSE-2.0
@@ -12,18 +12,83 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Reading and drawing of SVG Tiny 1.2 documents.
+//!
+//! Shapes, paths, groups, transforms, solid colors and the stroke and fill properties
+//! are supported. Text, images, `use` and animation are not, and those elements are
+//! skipped. Fills and strokes that refer to gradients or patterns are drawn with
+//! their fallback color, or not drawn if they have none.
+//!
+//! ```ignore
+//! let svg = svgkit::Reader::new().read_str(include_str!("icon.svg")).unwrap();
+//! svg.draw(x, y, w, h);
+//! ```
+
 extern crate quick_xml;
+extern crate weekit;
+
+pub mod path;
+mod render;
+pub mod style;
 
+use std::error;
+use std::fmt;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
 use std::str;
 
-pub use self::quick_xml::Reader as XMLReader;
 pub use self::quick_xml::events::Event;
+pub use self::quick_xml::Reader as XMLReader;
+
+pub use path::PathData;
+pub use style::{FillRule, LineCap, LineJoin, Paint, Style};
 
 pub type Length = f32;
 pub type Coordinate = f32;
-pub type Color = String;
+
+/// How a viewBox is fitted into the rectangle a document is drawn in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AspectRatio {
+    /// Where the viewBox is placed, from (0, 0) for the top left to (1, 1) for the
+    /// bottom right, or None to stretch it to fill the rectangle.
+    pub align: Option<(f32, f32)>,
+    /// True if the viewBox is scaled to cover the rectangle rather than fit inside it.
+    pub slice: bool,
+}
+
+impl AspectRatio {
+    /// Parses a preserveAspectRatio attribute.
+    pub fn parse(s: &str) -> AspectRatio {
+        let mut words = s.split_whitespace();
+        let position = |name: &str| match name {
+            "Min" => 0.0,
+            "Max" => 1.0,
+            _ => 0.5,
+        };
+        let align = match words.next() {
+            Some("none") => None,
+            Some(a) => match (a.get(1..4), a.get(5..8)) {
+                (Some(x), Some(y)) if a.len() == 8 => Some((position(x), position(y))),
+                _ => Some((0.5, 0.5)),
+            },
+            None => Some((0.5, 0.5)),
+        };
+        AspectRatio {
+            align: align,
+            slice: words.next() == Some("slice"),
+        }
+    }
+}
+
+impl Default for AspectRatio {
+    fn default() -> AspectRatio {
+        AspectRatio {
+            align: Some((0.5, 0.5)),
+            slice: false,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub enum Element {
@@ -31,54 +96,107 @@ pub enum Element {
     SVG {
         width: Option<Length>,
         height: Option<Length>,
-        view_box: String,
+        /// The x, y, width and height of the area drawn.
+        view_box: Option<[Coordinate; 4]>,
+        preserve_aspect_ratio: AspectRatio,
         xmlns: Option<String>,
         version: String,
         base_profile: String,
-        _children: Vec<Element>,
+        style: Style,
+        children: Vec<Element>,
     },
     Title {
-        _text: Option<String>,
+        text: String,
     },
     Desc {
-        _text: Option<String>,
+        text: String,
     },
     Rect {
-        x: Option<Coordinate>,
-        y: Option<Coordinate>,
-        width: Option<Length>,
-        height: Option<Length>,
-        rx: Option<Coordinate>,
-        ry: Option<Coordinate>,
-        fill: Option<Color>,
-        stroke: Option<Color>,
-        stroke_width: Option<String>,
+        x: Coordinate,
+        y: Coordinate,
+        width: Length,
+        height: Length,
+        rx: Option<Length>,
+        ry: Option<Length>,
+        style: Style,
     },
     Circle {
-        cx: Option<Coordinate>,
-        cy: Option<Coordinate>,
-        r: Option<Length>,
+        cx: Coordinate,
+        cy: Coordinate,
+        r: Length,
+        style: Style,
+    },
+    Ellipse {
+        cx: Coordinate,
+        cy: Coordinate,
+        rx: Length,
+        ry: Length,
+        style: Style,
     },
-    Ellipse {},
     Line {
         x1: Coordinate,
         y1: Coordinate,
         x2: Coordinate,
         y2: Coordinate,
+        style: Style,
+    },
+    Polyline {
+        /// Pairs of x and y coordinates.
+        points: Vec<Coordinate>,
+        style: Style,
+    },
+    Polygon {
+        /// Pairs of x and y coordinates.
+        points: Vec<Coordinate>,
+        style: Style,
     },
-    Polyline {},
-    Polygon {},
     Path {
-        d: Option<String>,
-        fill: Option<String>,
-        stroke: Option<String>,
-        stroke_width: Option<String>,
+        d: PathData,
+        style: Style,
     },
     Group {
-        _children: Vec<Element>,
+        style: Style,
+        children: Vec<Element>,
     },
 }
 
+/// Describes problems encountered when reading SVG documents.
+#[derive(Debug)]
+pub enum Error {
+    /// The document couldn't be read.
+    Io(io::Error),
+    /// The document isn't well-formed XML.
+    Xml(String),
+    /// The document has no svg element.
+    NoSvg,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "unable to read svg: {}", e),
+            Error::Xml(ref s) => write!(f, "unable to parse svg: {}", s),
+            Error::NoSvg => write!(f, "no svg element"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Io(_) => "unable to read svg",
+            Error::Xml(_) => "unable to parse svg",
+            Error::NoSvg => "no svg element",
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
 #[derive(Debug)]
 struct Partial {
     name: String,
@@ -106,52 +224,126 @@ impl Partial {
         }
         None
     }
-    fn attribute_coordinate(&self, name: &str) -> Option<Coordinate> {
-        for i in 0..self.keys.len() {
-            if self.keys[i] == name {
-                return Some(self.values[i].parse::<Coordinate>().unwrap());
-            }
-        }
-        None
+    fn attribute_coordinate(&self, name: &str) -> Coordinate {
+        self.attribute_length(name).unwrap_or(0.0)
     }
     fn attribute_length(&self, name: &str) -> Option<Length> {
+        self.attribute_string(name)
+            .and_then(|value| style::parse_length(&value))
+    }
+    fn attribute_points(&self) -> Vec<Coordinate> {
+        match self.attribute_string("points") {
+            Some(points) => path::parse_numbers(&points),
+            None => Vec::new(),
+        }
+    }
+    fn style(&self) -> Style {
+        let mut style = Style::default();
         for i in 0..self.keys.len() {
-            if self.keys[i] == name {
-                return Some(self.values[i].parse::<Length>().unwrap());
+            if self.keys[i] != "style" {
+                style.set(&self.keys[i], &self.values[i]);
             }
         }
-        None
+        if let Some(declarations) = self.attribute_string("style") {
+            style.set("style", &declarations);
+        }
+        style
     }
     fn make_svg(self) -> Element {
+        let view_box = self
+            .attribute_string("viewBox")
+            .map(|v| path::parse_numbers(&v))
+            .and_then(|v| {
+                if v.len() == 4 && v[2] > 0.0 && v[3] > 0.0 {
+                    Some([v[0], v[1], v[2], v[3]])
+                } else {
+                    None
+                }
+            });
         Element::SVG {
-            width: None,
-            height: None,
-            view_box: "".to_string(),
-            xmlns: None,
-            version: "".to_string(),
-            base_profile: "".to_string(),
-            _children: self.children,
+            width: self.attribute_length("width"),
+            height: self.attribute_length("height"),
+            view_box: view_box,
+            preserve_aspect_ratio: self
+                .attribute_string("preserveAspectRatio")
+                .map(|s| AspectRatio::parse(&s))
+                .unwrap_or_default(),
+            xmlns: self.attribute_string("xmlns"),
+            version: self.attribute_string("version").unwrap_or_default(),
+            base_profile: self.attribute_string("baseProfile").unwrap_or_default(),
+            style: self.style(),
+            children: self.children,
+        }
+    }
+    fn make_group(self) -> Element {
+        Element::Group {
+            style: self.style(),
+            children: self.children,
         }
     }
+    /// Makes a group of the first child that can be drawn, which is the only one a
+    /// switch shows.
+    fn make_switch(mut self) -> Element {
+        let first = self.children.drain(..).find(|child| match *child {
+            Element::Title { .. } | Element::Desc { .. } | Element::Unknown => false,
+            _ => true,
+        });
+        self.children = first.into_iter().collect();
+        self.make_group()
+    }
     fn make_path(self) -> Element {
         Element::Path {
-            d: self.attribute_string("d"),
-            fill: self.attribute_string("fill"),
-            stroke: self.attribute_string("stroke"),
-            stroke_width: self.attribute_string("stroke_width"),
+            d: PathData::parse(&self.attribute_string("d").unwrap_or_default()),
+            style: self.style(),
         }
     }
     fn make_rect(self) -> Element {
         Element::Rect {
             x: self.attribute_coordinate("x"),
             y: self.attribute_coordinate("y"),
-            width: self.attribute_length("width"),
-            height: self.attribute_length("height"),
+            width: self.attribute_coordinate("width"),
+            height: self.attribute_coordinate("height"),
             rx: self.attribute_length("rx"),
             ry: self.attribute_length("ry"),
-            fill: self.attribute_string("fill"),
-            stroke: self.attribute_string("stroke"),
-            stroke_width: self.attribute_string("stroke_width"),
+            style: self.style(),
+        }
+    }
+    fn make_circle(self) -> Element {
+        Element::Circle {
+            cx: self.attribute_coordinate("cx"),
+            cy: self.attribute_coordinate("cy"),
+            r: self.attribute_coordinate("r"),
+            style: self.style(),
+        }
+    }
+    fn make_ellipse(self) -> Element {
+        Element::Ellipse {
+            cx: self.attribute_coordinate("cx"),
+            cy: self.attribute_coordinate("cy"),
+            rx: self.attribute_coordinate("rx"),
+            ry: self.attribute_coordinate("ry"),
+            style: self.style(),
+        }
+    }
+    fn make_line(self) -> Element {
+        Element::Line {
+            x1: self.attribute_coordinate("x1"),
+            y1: self.attribute_coordinate("y1"),
+            x2: self.attribute_coordinate("x2"),
+            y2: self.attribute_coordinate("y2"),
+            style: self.style(),
+        }
+    }
+    fn make_polyline(self) -> Element {
+        Element::Polyline {
+            points: self.attribute_points(),
+            style: self.style(),
+        }
+    }
+    fn make_polygon(self) -> Element {
+        Element::Polygon {
+            points: self.attribute_points(),
+            style: self.style(),
         }
     }
 }
@@ -162,18 +354,22 @@ pub struct Reader {
 
 impl Reader {
     pub fn new() -> Reader {
-        let mut s: Vec<Partial> = Vec::new();
-        s.push(Partial::new(""));
-        return Reader { stack: s };
+        Reader { stack: Vec::new() }
     }
 
-    pub fn read(&mut self, f: &mut File) -> Element {
+    /// Reads an SVG document from a file.
+    pub fn read(&mut self, f: &mut File) -> Result<Element, Error> {
         let mut contents = String::new();
-        f.read_to_string(&mut contents)
-            .expect("something went wrong reading the file");
-        println!("{}", contents);
+        f.read_to_string(&mut contents)?;
+        self.read_str(&contents)
+    }
 
-        let mut reader = XMLReader::from_str(&contents);
+    /// Reads an SVG document from a string.
+    pub fn read_str(&mut self, contents: &str) -> Result<Element, Error> {
+        self.stack.clear();
+        self.stack.push(Partial::new(""));
+
+        let mut reader = XMLReader::from_str(contents);
         reader.trim_text(true);
 
         let mut buf = Vec::new();
@@ -182,60 +378,83 @@ impl Reader {
         loop {
             match reader.read_event(&mut buf) {
                 Ok(Event::Start(ref e)) => {
-                    self.open_element(e);
+                    self.open_element(e)?;
                 }
                 Ok(Event::Empty(ref e)) => {
-                    self.open_element(e);
+                    self.open_element(e)?;
                     self.close_element();
                 }
                 Ok(Event::End(_e)) => {
                     self.close_element();
                 }
                 Ok(Event::Text(e)) => {
-                    let text = e.unescape_and_decode(&reader).unwrap();
+                    let text = e
+                        .unescape_and_decode(&reader)
+                        .map_err(|e| Error::Xml(format!("{:?}", e)))?;
                     self.add_text(&text);
                 }
                 Ok(Event::Eof) => {
                     break; // exits the loop when reaching end of file
                 }
-                Err(e) => panic!("Error at position {}: {:?}", reader.buffer_position(), e),
+                Err(e) => {
+                    return Err(Error::Xml(format!(
+                        "error at position {}: {:?}",
+                        reader.buffer_position(),
+                        e
+                    )))
+                }
                 _ => (), // There are several other `Event`s we do not consider here
             }
             // if we don't keep a borrow elsewhere, we can clear the buffer to keep memory usage low
             buf.clear();
         }
 
-        println!("{:?}", self.stack.last().unwrap().children.last().unwrap());
-
-        let mut partial = self.stack.pop().unwrap();
-        let child = partial.children.pop().unwrap();
-        child
+        let mut partial = self.stack.drain(..).next().ok_or(Error::NoSvg)?;
+        match partial.children.pop() {
+            Some(svg @ Element::SVG { .. }) => Ok(svg),
+            _ => Err(Error::NoSvg),
+        }
     }
 
-    fn open_element(&mut self, e: &quick_xml::events::BytesStart) {
-        let name = str::from_utf8(e.name()).unwrap();
+    fn open_element(&mut self, e: &quick_xml::events::BytesStart) -> Result<(), Error> {
+        let name = str::from_utf8(e.name()).map_err(|e| Error::Xml(e.to_string()))?;
         let mut partial = Partial::new(name);
-        e.attributes().for_each(|a| {
-            let item = a.unwrap();
-            let key = str::from_utf8(item.key).unwrap();
-            let value = str::from_utf8(&item.value).unwrap();
+        for a in e.attributes() {
+            let item = a.map_err(|e| Error::Xml(format!("{:?}", e)))?;
+            let key = str::from_utf8(item.key).map_err(|e| Error::Xml(e.to_string()))?;
+            let value = str::from_utf8(&item.value).map_err(|e| Error::Xml(e.to_string()))?;
             partial.keys.push(key.to_string());
             partial.values.push(value.to_string());
-        });
+        }
         self.stack.push(partial);
+        Ok(())
     }
 
     fn close_element(&mut self) {
+        // The root is never closed, even by unbalanced end tags.
+        if self.stack.len() < 2 {
+            return;
+        }
         let partial = self.stack.pop().unwrap();
+        // No extensions are supported, so alternatives that need one are never chosen.
+        let in_switch = self.stack.last().map_or(false, |p| p.name == "switch");
+        if in_switch && partial.attribute_string("requiredExtensions").is_some() {
+            self.add_child(Element::Unknown);
+            return;
+        }
         let child = match partial.name.as_ref() {
             "svg" => partial.make_svg(),
-            "title" => Element::Title {
-                _text: Some(partial.text),
-            },
-            "desc" => Element::Desc {
-                _text: Some(partial.text),
-            },
+            "title" => Element::Title { text: partial.text },
+            "desc" => Element::Desc { text: partial.text },
+            // Links are drawn as groups of their children.
+            "g" | "a" => partial.make_group(),
+            "switch" => partial.make_switch(),
             "rect" => partial.make_rect(),
+            "circle" => partial.make_circle(),
+            "ellipse" => partial.make_ellipse(),
+            "line" => partial.make_line(),
+            "polyline" => partial.make_polyline(),
+            "polygon" => partial.make_polygon(),
             "path" => partial.make_path(),
             _ => Element::Unknown,
         };
@@ -245,7 +464,7 @@ impl Reader {
     fn add_child(&mut self, child: Element) {
         match self.stack.last_mut() {
             Some(ref mut node) => {
-                let mut c = &mut node.children;
+                let c = &mut node.children;
                 c.push(child);
             }
             None => {}
@@ -253,7 +472,8 @@ impl Reader {
     }
 
     fn add_text(&mut self, text: &str) {
-        let partial = self.stack.last_mut();
-        partial.unwrap().text = text.to_string();
+        if let Some(partial) = self.stack.last_mut() {
+            partial.text.push_str(text);
+        }
     }
 }
@@ -0,0 +1,336 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Path data and the OpenVG segments of basic shapes.
+
+use weekit::openvg::{VGPathAbsRel, VGPathSegment, VGfloat, VGubyte};
+
+/// OpenVG path segments and their coordinates, as drawn by `draw::make_curve()`.
+#[derive(Clone, Debug, Default)]
+pub struct PathData {
+    pub segments: Vec<VGubyte>,
+    pub coords: Vec<VGfloat>,
+}
+
+impl PathData {
+    /// Creates an empty PathData.
+    pub fn new() -> PathData {
+        PathData {
+            segments: Vec::new(),
+            coords: Vec::new(),
+        }
+    }
+
+    /// Returns true if there is nothing to draw.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    fn push(&mut self, segment: VGPathSegment, relative: bool, coords: &[VGfloat]) {
+        let mode = if relative {
+            VGPathAbsRel::VG_RELATIVE
+        } else {
+            VGPathAbsRel::VG_ABSOLUTE
+        };
+        self.segments.push(segment as VGubyte | mode as VGubyte);
+        self.coords.extend_from_slice(coords);
+    }
+
+    fn move_to(&mut self, x: VGfloat, y: VGfloat) {
+        self.push(VGPathSegment::VG_MOVE_TO, false, &[x, y]);
+    }
+
+    fn line_to(&mut self, x: VGfloat, y: VGfloat) {
+        self.push(VGPathSegment::VG_LINE_TO, false, &[x, y]);
+    }
+
+    /// Appends a small arc drawn in the direction of increasing angles.
+    fn arc_to(&mut self, rx: VGfloat, ry: VGfloat, x: VGfloat, y: VGfloat) {
+        self.push(VGPathSegment::VG_SCCWARC_TO, false, &[rx, ry, 0.0, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.push(VGPathSegment::VG_CLOSE_PATH, false, &[]);
+    }
+
+    /// Parses the `d` attribute of a path.
+    ///
+    /// Parsing stops at the first error, keeping the segments before it.
+    pub fn parse(d: &str) -> PathData {
+        let mut data = PathData::new();
+        let mut scanner = Scanner::new(d);
+        let mut command = None;
+        loop {
+            scanner.skip_separators();
+            if scanner.at_end() {
+                break;
+            }
+            command = match scanner.command() {
+                Some(c) => Some(c),
+                // Coordinates after a move are implicit lines.
+                None => match command {
+                    Some(b'M') => Some(b'L'),
+                    Some(b'm') => Some(b'l'),
+                    Some(b'Z') | Some(b'z') | None => break,
+                    other => other,
+                },
+            };
+            let c = command.unwrap();
+            let relative = c.is_ascii_lowercase();
+            let (segment, count) = match c.to_ascii_uppercase() {
+                b'M' => (VGPathSegment::VG_MOVE_TO, 2),
+                b'L' => (VGPathSegment::VG_LINE_TO, 2),
+                b'H' => (VGPathSegment::VG_HLINE_TO, 1),
+                b'V' => (VGPathSegment::VG_VLINE_TO, 1),
+                b'C' => (VGPathSegment::VG_CUBIC_TO, 6),
+                b'S' => (VGPathSegment::VG_SCUBIC_TO, 4),
+                b'Q' => (VGPathSegment::VG_QUAD_TO, 4),
+                b'T' => (VGPathSegment::VG_SQUAD_TO, 2),
+                b'A' => {
+                    match scanner.arc() {
+                        Some((coords, large, sweep)) => {
+                            // A positive-angle sweep in SVG is counterclockwise in OpenVG,
+                            // since both measure angles in user space.
+                            let segment = match (large, sweep) {
+                                (false, true) => VGPathSegment::VG_SCCWARC_TO,
+                                (false, false) => VGPathSegment::VG_SCWARC_TO,
+                                (true, true) => VGPathSegment::VG_LCCWARC_TO,
+                                (true, false) => VGPathSegment::VG_LCWARC_TO,
+                            };
+                            data.push(segment, relative, &coords);
+                        }
+                        None => break,
+                    }
+                    continue;
+                }
+                b'Z' => {
+                    data.close();
+                    continue;
+                }
+                _ => break,
+            };
+            let mut coords = [0.0; 6];
+            for i in 0..count {
+                match scanner.number() {
+                    Some(n) => coords[i] = n,
+                    None => return data,
+                }
+            }
+            data.push(segment, relative, &coords[..count]);
+        }
+        data
+    }
+
+    /// Returns the outline of a rectangle, with corners rounded by rx, ry, or an empty
+    /// path if the rectangle has no area.
+    pub fn rect(
+        x: VGfloat,
+        y: VGfloat,
+        w: VGfloat,
+        h: VGfloat,
+        rx: VGfloat,
+        ry: VGfloat,
+    ) -> PathData {
+        let mut data = PathData::new();
+        if w <= 0.0 || h <= 0.0 {
+            return data;
+        }
+        let rx = rx.min(w / 2.0);
+        let ry = ry.min(h / 2.0);
+        if rx <= 0.0 || ry <= 0.0 {
+            data.move_to(x, y);
+            data.line_to(x + w, y);
+            data.line_to(x + w, y + h);
+            data.line_to(x, y + h);
+            data.close();
+            return data;
+        }
+        data.move_to(x + rx, y);
+        data.line_to(x + w - rx, y);
+        data.arc_to(rx, ry, x + w, y + ry);
+        data.line_to(x + w, y + h - ry);
+        data.arc_to(rx, ry, x + w - rx, y + h);
+        data.line_to(x + rx, y + h);
+        data.arc_to(rx, ry, x, y + h - ry);
+        data.line_to(x, y + ry);
+        data.arc_to(rx, ry, x + rx, y);
+        data.close();
+        data
+    }
+
+    /// Returns the outline of an ellipse centered at cx, cy.
+    pub fn ellipse(cx: VGfloat, cy: VGfloat, rx: VGfloat, ry: VGfloat) -> PathData {
+        let mut data = PathData::new();
+        if rx <= 0.0 || ry <= 0.0 {
+            return data;
+        }
+        data.move_to(cx + rx, cy);
+        data.arc_to(rx, ry, cx - rx, cy);
+        data.arc_to(rx, ry, cx + rx, cy);
+        data.close();
+        data
+    }
+
+    /// Returns a line from x1, y1 to x2, y2.
+    pub fn line(x1: VGfloat, y1: VGfloat, x2: VGfloat, y2: VGfloat) -> PathData {
+        let mut data = PathData::new();
+        data.move_to(x1, y1);
+        data.line_to(x2, y2);
+        data
+    }
+
+    /// Returns lines joining points given as x, y pairs, closed back to the first
+    /// point if close is true.
+    pub fn poly(points: &[VGfloat], close: bool) -> PathData {
+        let mut data = PathData::new();
+        for (i, point) in points.chunks(2).filter(|p| p.len() == 2).enumerate() {
+            if i == 0 {
+                data.move_to(point[0], point[1]);
+            } else {
+                data.line_to(point[0], point[1]);
+            }
+        }
+        if close && !data.is_empty() {
+            data.close();
+        }
+        data
+    }
+}
+
+/// Parses a list of numbers separated by whitespace or commas, such as the points of
+/// a polygon, stopping at the first thing that isn't a number.
+pub fn parse_numbers(s: &str) -> Vec<VGfloat> {
+    let mut scanner = Scanner::new(s);
+    let mut numbers = Vec::new();
+    while let Some(n) = scanner.number() {
+        numbers.push(n);
+    }
+    numbers
+}
+
+/// Reads the numbers, flags and commands of path data.
+///
+/// Numbers needn't be separated when the next one starts with a sign or a second
+/// decimal point, as in "10-5" or "0.5.5".
+pub(crate) struct Scanner<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(s: &'a str) -> Scanner<'a> {
+        Scanner { s: s, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.as_bytes().get(self.pos).cloned()
+    }
+
+    /// Returns the offset of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.pos >= self.s.len()
+    }
+
+    pub fn skip_separators(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                b' ' | b'\t' | b'\r' | b'\n' | b',' => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+
+    /// Reads a path command letter.
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() && c != b'e' && c != b'E' => {
+                self.pos += 1;
+                Some(c)
+            }
+            _ => None,
+        }
+    }
+
+    /// Reads a number.
+    pub fn number(&mut self) -> Option<VGfloat> {
+        self.skip_separators();
+        let bytes = self.s.as_bytes();
+        let start = self.pos;
+        let mut end = start;
+        if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+            end += 1;
+        }
+        let digits = |mut i: usize| {
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            i
+        };
+        let mantissa = end;
+        end = digits(end);
+        if end < bytes.len() && bytes[end] == b'.' {
+            end = digits(end + 1);
+        }
+        if end == mantissa || &self.s[mantissa..end] == "." {
+            return None;
+        }
+        if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+            let mut i = end + 1;
+            if i < bytes.len() && (bytes[i] == b'+' || bytes[i] == b'-') {
+                i += 1;
+            }
+            let exponent = digits(i);
+            if exponent > i {
+                end = exponent;
+            }
+        }
+        match self.s[start..end].parse::<VGfloat>() {
+            Ok(n) => {
+                self.pos = end;
+                Some(n)
+            }
+            Err(_) => None,
+        }
+    }
+
+    /// Reads an arc flag, which needn't be separated from what follows it.
+    fn flag(&mut self) -> Option<bool> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    /// Reads the arguments of an arc: the OpenVG coordinates rx, ry, rotation, x, y and
+    /// the large-arc and sweep flags.
+    fn arc(&mut self) -> Option<([VGfloat; 5], bool, bool)> {
+        let rx = self.number()?;
+        let ry = self.number()?;
+        let rotation = self.number()?;
+        let large = self.flag()?;
+        let sweep = self.flag()?;
+        let x = self.number()?;
+        let y = self.number()?;
+        Some(([rx.abs(), ry.abs(), rotation, x, y], large, sweep))
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Drawing of elements as OpenVG paths.
+
+use path::PathData;
+use style::{ComputedStyle, FillRule, LineCap, LineJoin, Style};
+use weekit::draw;
+use weekit::openvg::{VGCapStyle, VGFillRule, VGJoinStyle, VGPaintMode, VGfloat};
+use weekit::transform::Affine;
+use {AspectRatio, Element};
+
+impl Element {
+    /// Draws the element in the rectangle at x, y with size w, h.
+    ///
+    /// An svg element's viewBox, or its width and height if it has no viewBox, is
+    /// scaled into the rectangle as its preserveAspectRatio asks. Other elements are
+    /// drawn one user unit to a pixel from the top left of the rectangle. Drawing is
+    /// only clipped to the rectangle when a viewBox is sliced to cover it.
+    pub fn draw(&self, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+        let _state = draw::SavedState::new();
+        // SVG measures y down from the top.
+        draw::translate(x, y + h);
+        draw::scale(1.0, -1.0);
+        if let Element::SVG {
+            width,
+            height,
+            view_box,
+            preserve_aspect_ratio,
+            ..
+        } = *self
+        {
+            let view_box = match (view_box, width, height) {
+                (Some(v), _, _) => Some(v),
+                (None, Some(width), Some(height)) if width > 0.0 && height > 0.0 => {
+                    Some([0.0, 0.0, width, height])
+                }
+                _ => None,
+            };
+            if let Some(v) = view_box {
+                if preserve_aspect_ratio.slice {
+                    draw::clip_path_rect(0.0, 0.0, w, h);
+                }
+                draw::transform(&view_box_transform(v, preserve_aspect_ratio, w, h));
+            }
+        }
+        render(self, &ComputedStyle::initial());
+    }
+}
+
+/// Returns the transform that fits a viewBox into a w by h viewport.
+fn view_box_transform(v: [VGfloat; 4], aspect: AspectRatio, w: VGfloat, h: VGfloat) -> Affine {
+    let sx = w / v[2];
+    let sy = h / v[3];
+    let (sx, sy, tx, ty) = match aspect.align {
+        None => (sx, sy, 0.0, 0.0),
+        Some((ax, ay)) => {
+            let s = if aspect.slice { sx.max(sy) } else { sx.min(sy) };
+            (s, s, (w - v[2] * s) * ax, (h - v[3] * s) * ay)
+        }
+    };
+    Affine::new(sx, 0.0, 0.0, sy, tx - v[0] * sx, ty - v[1] * sy)
+}
+
+fn render(element: &Element, parent: &ComputedStyle) {
+    let shape;
+    let (d, style) = match *element {
+        Element::SVG {
+            ref style,
+            ref children,
+            ..
+        }
+        | Element::Group {
+            ref style,
+            ref children,
+        } => {
+            render_group(children, style, parent);
+            return;
+        }
+        Element::Rect {
+            x,
+            y,
+            width,
+            height,
+            rx,
+            ry,
+            ref style,
+        } => {
+            // A missing radius takes the value of the other one.
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0),
+            };
+            shape = PathData::rect(x, y, width, height, rx, ry);
+            (&shape, style)
+        }
+        Element::Circle {
+            cx,
+            cy,
+            r,
+            ref style,
+        } => {
+            shape = PathData::ellipse(cx, cy, r, r);
+            (&shape, style)
+        }
+        Element::Ellipse {
+            cx,
+            cy,
+            rx,
+            ry,
+            ref style,
+        } => {
+            shape = PathData::ellipse(cx, cy, rx, ry);
+            (&shape, style)
+        }
+        Element::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            ref style,
+        } => {
+            shape = PathData::line(x1, y1, x2, y2);
+            (&shape, style)
+        }
+        Element::Polyline {
+            ref points,
+            ref style,
+        } => {
+            shape = PathData::poly(points, false);
+            (&shape, style)
+        }
+        Element::Polygon {
+            ref points,
+            ref style,
+        } => {
+            shape = PathData::poly(points, true);
+            (&shape, style)
+        }
+        Element::Path { ref d, ref style } => (d, style),
+        Element::Title { .. } | Element::Desc { .. } | Element::Unknown => return,
+    };
+    render_shape(d, style, parent);
+}
+
+fn render_group(children: &[Element], style: &Style, parent: &ComputedStyle) {
+    if style.display == Some(false) {
+        return;
+    }
+    let computed = style.compute(parent);
+    let saved = draw::current_transform();
+    if let Some(ref t) = style.transform {
+        draw::transform(t);
+    }
+    for child in children {
+        render(child, &computed);
+    }
+    draw::set_transform(&saved);
+}
+
+fn render_shape(d: &PathData, style: &Style, parent: &ComputedStyle) {
+    if d.is_empty() || style.display == Some(false) {
+        return;
+    }
+    let computed = style.compute(parent);
+    if !computed.visible {
+        return;
+    }
+    let mut modes = 0;
+    if let Some((r, g, b)) = computed.resolve(&computed.fill) {
+        draw::fill(r, g, b, computed.fill_opacity * computed.opacity);
+        draw::fill_rule(match computed.fill_rule {
+            FillRule::NonZero => VGFillRule::VG_NON_ZERO,
+            FillRule::EvenOdd => VGFillRule::VG_EVEN_ODD,
+        });
+        modes |= VGPaintMode::VG_FILL_PATH as u32;
+    }
+    if let Some((r, g, b)) = computed.resolve(&computed.stroke) {
+        if computed.stroke_width > 0.0 {
+            draw::stroke(r, g, b, computed.stroke_opacity * computed.opacity);
+            draw::stroke_width(computed.stroke_width);
+            draw::stroke_cap(match computed.stroke_linecap {
+                LineCap::Butt => VGCapStyle::VG_CAP_BUTT,
+                LineCap::Round => VGCapStyle::VG_CAP_ROUND,
+                LineCap::Square => VGCapStyle::VG_CAP_SQUARE,
+            });
+            let join = match computed.stroke_linejoin {
+                LineJoin::Miter => VGJoinStyle::VG_JOIN_MITER,
+                LineJoin::Round => VGJoinStyle::VG_JOIN_ROUND,
+                LineJoin::Bevel => VGJoinStyle::VG_JOIN_BEVEL,
+            };
+            draw::stroke_join(join, computed.stroke_miterlimit);
+            draw::stroke_dash(
+                &dash_pattern(&computed.stroke_dasharray),
+                computed.stroke_dashoffset,
+            );
+            modes |= VGPaintMode::VG_STROKE_PATH as u32;
+        }
+    }
+    if modes == 0 {
+        return;
+    }
+    let saved = draw::current_transform();
+    if let Some(ref t) = style.transform {
+        draw::transform(t);
+    }
+    draw::make_curve(&d.segments, &d.coords, modes);
+    draw::set_transform(&saved);
+}
+
+/// Returns the OpenVG dash pattern for a stroke-dasharray.
+///
+/// SVG repeats a list with an odd number of lengths to make it even, where OpenVG
+/// would ignore the last one, and draws solid strokes for invalid lists.
+fn dash_pattern(dashes: &[VGfloat]) -> Vec<VGfloat> {
+    if dashes.iter().any(|&d| d < 0.0) || dashes.iter().all(|&d| d == 0.0) {
+        return Vec::new();
+    }
+    let mut pattern = dashes.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dashes);
+    }
+    pattern
+}
@@ -0,0 +1,348 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Colors, lengths, transforms and the presentation attributes of elements.
+
+use path::{parse_numbers, Scanner};
+use weekit::transform::Affine;
+use Length;
+
+/// How the inside or outline of a shape is painted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Paint {
+    None,
+    /// The value of the `color` property.
+    CurrentColor,
+    Color(u32, u32, u32),
+    /// A reference to a paint server such as a gradient, which isn't supported, and
+    /// the paint used in its place.
+    Url(String, Box<Paint>),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// The transform and presentation attributes given to an element.
+///
+/// Attributes that weren't given are None, and are inherited from the element's
+/// parent when it is drawn.
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    pub transform: Option<Affine>,
+    pub fill: Option<Paint>,
+    pub fill_opacity: Option<f32>,
+    pub fill_rule: Option<FillRule>,
+    pub stroke: Option<Paint>,
+    pub stroke_width: Option<Length>,
+    pub stroke_opacity: Option<f32>,
+    pub stroke_linecap: Option<LineCap>,
+    pub stroke_linejoin: Option<LineJoin>,
+    pub stroke_miterlimit: Option<f32>,
+    /// Dash lengths, or an empty list for solid strokes.
+    pub stroke_dasharray: Option<Vec<Length>>,
+    pub stroke_dashoffset: Option<Length>,
+    pub color: Option<(u32, u32, u32)>,
+    /// Multiplies the opacity of fills and strokes.
+    pub opacity: Option<f32>,
+    /// False for `display="none"`, which hides an element and its children.
+    pub display: Option<bool>,
+    /// False for `visibility="hidden"`, which children may override.
+    pub visibility: Option<bool>,
+}
+
+impl Style {
+    /// Sets an attribute by name, ignoring names that aren't presentation attributes
+    /// and values that can't be parsed.
+    pub fn set(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        if value == "inherit" {
+            return;
+        }
+        match name {
+            "transform" => self.transform = parse_transform(value),
+            "fill" => self.fill = parse_paint(value),
+            "fill-opacity" => self.fill_opacity = parse_opacity(value),
+            "fill-rule" => {
+                self.fill_rule = match value {
+                    "nonzero" => Some(FillRule::NonZero),
+                    "evenodd" => Some(FillRule::EvenOdd),
+                    _ => None,
+                }
+            }
+            "stroke" => self.stroke = parse_paint(value),
+            "stroke-width" => self.stroke_width = parse_length(value),
+            "stroke-opacity" => self.stroke_opacity = parse_opacity(value),
+            "stroke-linecap" => {
+                self.stroke_linecap = match value {
+                    "butt" => Some(LineCap::Butt),
+                    "round" => Some(LineCap::Round),
+                    "square" => Some(LineCap::Square),
+                    _ => None,
+                }
+            }
+            "stroke-linejoin" => {
+                self.stroke_linejoin = match value {
+                    "miter" => Some(LineJoin::Miter),
+                    "round" => Some(LineJoin::Round),
+                    "bevel" => Some(LineJoin::Bevel),
+                    _ => None,
+                }
+            }
+            "stroke-miterlimit" => self.stroke_miterlimit = value.parse().ok(),
+            "stroke-dasharray" => {
+                self.stroke_dasharray = if value == "none" {
+                    Some(Vec::new())
+                } else {
+                    Some(parse_numbers(value))
+                }
+            }
+            "stroke-dashoffset" => self.stroke_dashoffset = parse_length(value),
+            "color" => self.color = parse_color(value),
+            "opacity" => self.opacity = parse_opacity(value),
+            "display" => self.display = Some(value != "none"),
+            "visibility" => self.visibility = Some(value == "visible"),
+            // Declarations in a style attribute take the place of attributes.
+            "style" => {
+                for declaration in value.split(';') {
+                    let mut parts = declaration.splitn(2, ':');
+                    if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+                        self.set(name.trim(), value);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the style used to draw an element with this style inside a parent with
+    /// a computed style.
+    pub(crate) fn compute(&self, parent: &ComputedStyle) -> ComputedStyle {
+        ComputedStyle {
+            fill: self.fill.clone().unwrap_or_else(|| parent.fill.clone()),
+            fill_opacity: self.fill_opacity.unwrap_or(parent.fill_opacity),
+            fill_rule: self.fill_rule.unwrap_or(parent.fill_rule),
+            stroke: self.stroke.clone().unwrap_or_else(|| parent.stroke.clone()),
+            stroke_width: self.stroke_width.unwrap_or(parent.stroke_width),
+            stroke_opacity: self.stroke_opacity.unwrap_or(parent.stroke_opacity),
+            stroke_linecap: self.stroke_linecap.unwrap_or(parent.stroke_linecap),
+            stroke_linejoin: self.stroke_linejoin.unwrap_or(parent.stroke_linejoin),
+            stroke_miterlimit: self.stroke_miterlimit.unwrap_or(parent.stroke_miterlimit),
+            stroke_dasharray: match self.stroke_dasharray {
+                Some(ref dashes) => dashes.clone(),
+                None => parent.stroke_dasharray.clone(),
+            },
+            stroke_dashoffset: self.stroke_dashoffset.unwrap_or(parent.stroke_dashoffset),
+            color: self.color.unwrap_or(parent.color),
+            opacity: parent.opacity * self.opacity.unwrap_or(1.0),
+            visible: self.visibility.unwrap_or(parent.visible),
+        }
+    }
+}
+
+/// The style an element is drawn with, after inheritance.
+#[derive(Clone, Debug)]
+pub(crate) struct ComputedStyle {
+    pub fill: Paint,
+    pub fill_opacity: f32,
+    pub fill_rule: FillRule,
+    pub stroke: Paint,
+    pub stroke_width: Length,
+    pub stroke_opacity: f32,
+    pub stroke_linecap: LineCap,
+    pub stroke_linejoin: LineJoin,
+    pub stroke_miterlimit: f32,
+    pub stroke_dasharray: Vec<Length>,
+    pub stroke_dashoffset: Length,
+    pub color: (u32, u32, u32),
+    pub opacity: f32,
+    pub visible: bool,
+}
+
+impl ComputedStyle {
+    /// Returns the initial values of SVG properties.
+    pub fn initial() -> ComputedStyle {
+        ComputedStyle {
+            fill: Paint::Color(0, 0, 0),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: Paint::None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            stroke_linecap: LineCap::Butt,
+            stroke_linejoin: LineJoin::Miter,
+            stroke_miterlimit: 4.0,
+            stroke_dasharray: Vec::new(),
+            stroke_dashoffset: 0.0,
+            color: (0, 0, 0),
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+
+    /// Returns the color of a paint, or None if it isn't painted.
+    pub fn resolve(&self, paint: &Paint) -> Option<(u32, u32, u32)> {
+        match *paint {
+            Paint::None => None,
+            Paint::CurrentColor => Some(self.color),
+            Paint::Color(r, g, b) => Some((r, g, b)),
+            Paint::Url(_, ref fallback) => self.resolve(fallback),
+        }
+    }
+}
+
+/// Parses a length, converting absolute units to user units.
+///
+/// Percentages aren't supported and give None.
+pub fn parse_length(s: &str) -> Option<Length> {
+    let mut scanner = Scanner::new(s.trim());
+    let n = scanner.number()?;
+    let scale = match s.trim()[scanner.position()..].trim() {
+        "" | "px" => 1.0,
+        "pt" => 1.25,
+        "pc" => 15.0,
+        "mm" => 3.543307,
+        "cm" => 35.43307,
+        "in" => 90.0,
+        _ => return None,
+    };
+    Some(n * scale)
+}
+
+fn parse_opacity(s: &str) -> Option<f32> {
+    s.parse::<f32>().ok().map(|a| a.max(0.0).min(1.0))
+}
+
+fn parse_paint(s: &str) -> Option<Paint> {
+    // A reference may be followed by the paint to use if it can't be drawn.
+    if s.starts_with("url(") {
+        let end = s.find(')')?;
+        let id = s[4..end].trim().trim_matches(|c| c == '\'' || c == '"');
+        let fallback = match s[end + 1..].trim() {
+            "" => Paint::None,
+            fallback if fallback.starts_with("url(") => return None,
+            fallback => parse_paint(fallback)?,
+        };
+        return Some(Paint::Url(id.to_string(), Box::new(fallback)));
+    }
+    match s {
+        "none" => Some(Paint::None),
+        "currentColor" => Some(Paint::CurrentColor),
+        _ => parse_color(s).map(|(r, g, b)| Paint::Color(r, g, b)),
+    }
+}
+
+/// Parses a color given as a name, as `#rgb` or `#rrggbb`, or as `rgb(r, g, b)` with
+/// integers or percentages.
+pub fn parse_color(s: &str) -> Option<(u32, u32, u32)> {
+    let s = s.trim();
+    if s.starts_with('#') {
+        let hex = &s[1..];
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        return match hex.len() {
+            3 => Some((
+                (value >> 8) * 0x11,
+                ((value >> 4) & 0xf) * 0x11,
+                (value & 0xf) * 0x11,
+            )),
+            6 => Some((value >> 16, (value >> 8) & 0xff, value & 0xff)),
+            _ => None,
+        };
+    }
+    if s.starts_with("rgb(") && s.ends_with(')') {
+        let channels: Vec<u32> = s[4..s.len() - 1]
+            .split(',')
+            .map(|c| {
+                let c = c.trim();
+                let value = if c.ends_with('%') {
+                    c[..c.len() - 1].parse::<f32>().ok().map(|p| p * 2.55)
+                } else {
+                    c.parse::<f32>().ok()
+                };
+                value.map(|v| v.max(0.0).min(255.0).round() as u32)
+            })
+            .collect::<Option<Vec<u32>>>()?;
+        return if channels.len() == 3 {
+            Some((channels[0], channels[1], channels[2]))
+        } else {
+            None
+        };
+    }
+    match s {
+        "black" => Some((0, 0, 0)),
+        "silver" => Some((192, 192, 192)),
+        "gray" | "grey" => Some((128, 128, 128)),
+        "white" => Some((255, 255, 255)),
+        "maroon" => Some((128, 0, 0)),
+        "red" => Some((255, 0, 0)),
+        "purple" => Some((128, 0, 128)),
+        "fuchsia" => Some((255, 0, 255)),
+        "green" => Some((0, 128, 0)),
+        "lime" => Some((0, 255, 0)),
+        "olive" => Some((128, 128, 0)),
+        "yellow" => Some((255, 255, 0)),
+        "navy" => Some((0, 0, 128)),
+        "blue" => Some((0, 0, 255)),
+        "teal" => Some((0, 128, 128)),
+        "aqua" => Some((0, 255, 255)),
+        _ => None,
+    }
+}
+
+/// Parses a list of transforms, such as `translate(10 20) rotate(45)`, into the
+/// transform that applies them from right to left.
+pub fn parse_transform(s: &str) -> Option<Affine> {
+    let mut t = Affine::identity();
+    for item in s.split(')') {
+        let item = item.trim_matches(|c: char| c.is_whitespace() || c == ',');
+        if item.is_empty() {
+            continue;
+        }
+        let mut parts = item.splitn(2, '(');
+        let name = parts.next()?.trim();
+        let args = parse_numbers(parts.next()?);
+        let next = match (name, args.len()) {
+            ("matrix", 6) => Affine::new(args[0], args[1], args[2], args[3], args[4], args[5]),
+            ("translate", 1) => Affine::translation(args[0], 0.0),
+            ("translate", 2) => Affine::translation(args[0], args[1]),
+            ("scale", 1) => Affine::scaling(args[0], args[0]),
+            ("scale", 2) => Affine::scaling(args[0], args[1]),
+            ("rotate", 1) => Affine::rotation(args[0]),
+            ("rotate", 3) => Affine::translation(args[1], args[2])
+                .multiply(&Affine::rotation(args[0]))
+                .multiply(&Affine::translation(-args[1], -args[2])),
+            ("skewX", 1) => Affine::shearing(args[0].to_radians().tan(), 0.0),
+            ("skewY", 1) => Affine::shearing(0.0, args[0].to_radians().tan()),
+            _ => return None,
+        };
+        t = t.multiply(&next);
+    }
+    Some(t)
+}
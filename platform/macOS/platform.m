@@ -65,7 +65,63 @@ static int k[KEYCODE_MAX];
     k[i] = -1;
   }
   k[0] = KEY_A;
+  k[1] = KEY_S;
+  k[2] = KEY_D;
+  k[3] = KEY_F;
+  k[4] = KEY_H;
+  k[5] = KEY_G;
+  k[6] = KEY_Z;
+  k[7] = KEY_X;
+  k[8] = KEY_C;
+  k[9] = KEY_V;
+  k[11] = KEY_B;
+  k[12] = KEY_Q;
+  k[13] = KEY_W;
+  k[14] = KEY_E;
+  k[15] = KEY_R;
+  k[16] = KEY_Y;
+  k[17] = KEY_T;
+  k[18] = KEY_1;
+  k[19] = KEY_2;
+  k[20] = KEY_3;
+  k[21] = KEY_4;
+  k[22] = KEY_6;
+  k[23] = KEY_5;
+  k[24] = KEY_EQUAL;
+  k[25] = KEY_9;
+  k[26] = KEY_7;
+  k[27] = KEY_MINUS;
+  k[28] = KEY_8;
+  k[29] = KEY_0;
+  k[30] = KEY_RIGHTBRACE;
+  k[31] = KEY_O;
+  k[32] = KEY_U;
+  k[33] = KEY_LEFTBRACE;
+  k[34] = KEY_I;
+  k[35] = KEY_P;
+  k[36] = KEY_ENTER;
+  k[37] = KEY_L;
+  k[38] = KEY_J;
+  k[39] = KEY_APOSTROPHE;
+  k[40] = KEY_K;
+  k[41] = KEY_SEMICOLON;
+  k[42] = KEY_BACKSLASH;
+  k[43] = KEY_COMMA;
+  k[44] = KEY_SLASH;
+  k[45] = KEY_N;
+  k[46] = KEY_M;
+  k[47] = KEY_DOT;
+  k[48] = KEY_TAB;
   k[49] = KEY_SPACE;
+  k[50] = KEY_GRAVE;
+  k[51] = KEY_BACKSPACE;
+  k[53] = KEY_ESC;
+  k[111] = KEY_F12;
+  k[115] = KEY_HOME;
+  k[116] = KEY_PAGEUP;
+  k[117] = KEY_DELETE;
+  k[119] = KEY_END;
+  k[121] = KEY_PAGEDOWN;
   k[123] = KEY_LEFT;
   k[124] = KEY_RIGHT;
   k[125] = KEY_DOWN;
@@ -380,6 +436,15 @@ static CVReturn displayLinkCallback(CVDisplayLinkRef displayLink,
 
 - (void) flagsChanged:(NSEvent *)theEvent {
   // NSLog(@"flagsChanged: %@", theEvent);
+  NSEventModifierFlags flags = theEvent.modifierFlags;
+  switch (theEvent.keyCode) {
+    case 56: wkEventHandler(EV_KEY, KEY_LEFTSHIFT, (flags & NSEventModifierFlagShift) != 0); break;
+    case 60: wkEventHandler(EV_KEY, KEY_RIGHTSHIFT, (flags & NSEventModifierFlagShift) != 0); break;
+    case 59: wkEventHandler(EV_KEY, KEY_LEFTCTRL, (flags & NSEventModifierFlagControl) != 0); break;
+    case 62: wkEventHandler(EV_KEY, KEY_RIGHTCTRL, (flags & NSEventModifierFlagControl) != 0); break;
+    case 55: wkEventHandler(EV_KEY, KEY_LEFTMETA, (flags & NSEventModifierFlagCommand) != 0); break;
+    case 54: wkEventHandler(EV_KEY, KEY_RIGHTMETA, (flags & NSEventModifierFlagCommand) != 0); break;
+  }
 }
 
 
@@ -15,6 +15,7 @@
 static EGLDisplay display;
 static EGLContext context;
 static EGLSurface surface;
+static EGLConfig config;
 
 static EGL_DISPMANX_WINDOW_T window;
 
@@ -28,7 +29,8 @@ static const EGLint attribute_list[] = {
 	EGL_GREEN_SIZE, 8,
 	EGL_BLUE_SIZE, 8,
 	EGL_ALPHA_SIZE, 8,
-	EGL_SURFACE_TYPE, EGL_WINDOW_BIT,
+	EGL_ALPHA_MASK_SIZE, 8,
+	EGL_SURFACE_TYPE, EGL_WINDOW_BIT | EGL_PBUFFER_BIT,
 	EGL_NONE
 };
  
@@ -46,7 +48,6 @@ void egl_init(uint32_t *w, uint32_t *h) {
 	eglBindAPI(EGL_OPENVG_API);
 
 	// get an appropriate EGL frame buffer configuration
-	EGLConfig config;
 	EGLint num_config;
 	result = eglChooseConfig(display, attribute_list, &config, 1, &num_config);
 	assert(EGL_FALSE != result);
@@ -107,3 +108,25 @@ void egl_finish() {
 void egl_swap_buffers() {
 	eglSwapBuffers(display, surface);
 }
+
+EGLSurface egl_create_image_surface(VGImage image) {
+	// render into an OpenVG image through a pbuffer surface
+	EGLSurface image_surface = eglCreatePbufferFromClientBuffer(
+		display, EGL_OPENVG_IMAGE, (EGLClientBuffer)(uintptr_t)image, config, NULL);
+	if (image_surface == EGL_NO_SURFACE) {
+		return NULL;
+	}
+	return image_surface;
+}
+
+int egl_make_current(EGLSurface target) {
+	// a null target selects the window surface
+	if (target == NULL) {
+		target = surface;
+	}
+	return eglMakeCurrent(display, target, target, context) == EGL_TRUE;
+}
+
+void egl_destroy_surface(EGLSurface target) {
+	eglDestroySurface(display, target);
+}
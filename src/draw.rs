@@ -14,8 +14,17 @@
 
 //! Utilities for drawing text and shapes.
 
+use egl;
+use filter::Filter;
 use font::*;
+use image::{bitmap_format, Bitmap, Error, Format, Image, ImageHandle};
 use openvg::*;
+use rich_text::AttributedString;
+use transform::Affine;
+
+use std::path::Path;
+use std::ptr;
+use std::sync::{Arc, Mutex};
 
 /// Represents a drawing area.
 pub struct Canvas {
@@ -27,10 +36,14 @@ impl Canvas {
     /// Creates a new Canvas.
     pub fn new(w: u32, h: u32) -> Canvas {
         let canvas = Canvas { w: w, h: h };
+        begin_frame(w, h);
         canvas.background(255, 255, 255);
         reset();
         unsafe {
-            vgLoadIdentity();
+            vgSeti(VGParamType::VG_MATRIX_MODE, MatrixMode::Path.value());
+        }
+        for mode in MatrixMode::all().iter() {
+            set_transform_for(*mode, &Affine::identity());
         }
         canvas
     }
@@ -66,6 +79,54 @@ impl Canvas {
             vgClear(x, y, w, h);
         }
     }
+
+    /// Copies the current contents of the canvas into a new Image.
+    pub fn snapshot(&self) -> Result<Image, Error> {
+        let image = Image::new(self.w, self.h, Format::Rgba)?;
+        unsafe {
+            vgGetPixels(image.handle(), 0, 0, 0, 0, self.w as VGint, self.h as VGint);
+        }
+        Ok(image)
+    }
+
+    /// Reads the current contents of the canvas into a Bitmap.
+    pub fn bitmap(&self) -> Bitmap {
+        read_pixels(0, 0, self.w, self.h)
+    }
+
+    /// Saves the current contents of the canvas as a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.bitmap().save_png(path)
+    }
+}
+
+/// Reads a rectangle in window coordinates into a Bitmap.
+pub fn read_pixels(x: u32, y: u32, w: u32, h: u32) -> Bitmap {
+    let mut bitmap = Bitmap::new(w, h);
+    if w == 0 || h == 0 {
+        return bitmap;
+    }
+    // The window starts with the bottom row, so pass the last row with a negative stride.
+    let stride = (w * 4) as VGint;
+    let last = (h as usize - 1) * stride as usize;
+    unsafe {
+        vgReadPixels(
+            bitmap.pixels[last..].as_mut_ptr() as *const VGbyte,
+            -stride,
+            bitmap_format(),
+            x as VGint,
+            y as VGint,
+            w as VGint,
+            h as VGint,
+        );
+    }
+    bitmap
+}
+
+/// Returns the size of the surface being drawn, as given to `Canvas::new()`.
+pub fn canvas_size() -> (u32, u32) {
+    let context = CONTEXT.lock().unwrap();
+    (context.width, context.height)
 }
 
 /// Resets drawing colors to black and stroke width to zero.
@@ -76,64 +137,516 @@ pub fn reset() {
 }
 
 /// Returns the width of a text string at the specified font and size.
-pub fn text_width(s: &str, f: &Font, pointsize: u32) -> f32 {
-    let mut tw: VGfloat = 0.0;
-    let size = pointsize as VGfloat;
-    for c in s.chars() {
-        let glyph_index = f.character_map[c as usize];
-        if glyph_index != -1 {
-            tw += size * f.glyph_advances[glyph_index as usize] as f32 / 65536.0;
+///
+/// Text functions accept a Font or a FontFamily, which draws each character with the
+/// first of its fonts that has a glyph for it. Text is kerned and combining marks are
+/// composed as described for `shape()`.
+pub fn text_width<F: GlyphSource + ?Sized>(s: &str, f: &F, pointsize: VGfloat) -> f32 {
+    glyphs_width(&shape(s, f), pointsize)
+}
+
+/// Returns the width of shaped glyphs.
+fn glyphs_width(glyphs: &[PlacedGlyph], pointsize: VGfloat) -> VGfloat {
+    let width = glyphs.iter().map(|g| g.x + g.advance).max().unwrap_or(0);
+    pointsize * width as f32 / 65536.0
+}
+
+/// Specifies which parts of glyphs the text functions draw.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TextMode {
+    /// Fills glyphs with the fill paint.
+    Fill,
+    /// Outlines glyphs with the stroke paint and width.
+    Stroke,
+    /// Fills glyphs and outlines them with the stroke paint and width (the default).
+    FillAndStroke,
+}
+
+impl TextMode {
+    fn paint_modes(&self) -> VGbitfield {
+        match *self {
+            TextMode::Fill => VGPaintMode::VG_FILL_PATH as u32,
+            TextMode::Stroke => VGPaintMode::VG_STROKE_PATH as u32,
+            TextMode::FillAndStroke => {
+                VGPaintMode::VG_FILL_PATH as u32 | VGPaintMode::VG_STROKE_PATH as u32
+            }
+        }
+    }
+}
+
+/// Describes a shadow drawn behind text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextShadow {
+    /// The horizontal offset of the shadow.
+    pub dx: VGfloat,
+    /// The vertical offset of the shadow, negative to drop it below the text.
+    pub dy: VGfloat,
+    /// The standard deviation of the blur, or zero for a sharp shadow.
+    ///
+    /// Blurring renders the text offscreen, which is slow on the Pi. The blurred image
+    /// is reused while the same text is drawn again at the same size, so text that
+    /// changes every frame should use a sharp shadow.
+    pub blur: VGfloat,
+    pub color: [VGfloat; 4],
+}
+
+impl TextShadow {
+    /// Creates a new TextShadow.
+    pub fn new(dx: VGfloat, dy: VGfloat, blur: VGfloat, color: [VGfloat; 4]) -> TextShadow {
+        TextShadow {
+            dx: dx,
+            dy: dy,
+            blur: blur,
+            color: color,
         }
     }
-    return tw as f32;
+}
+
+/// Sets which parts of glyphs the text functions draw.
+pub fn set_text_mode(mode: TextMode) {
+    CONTEXT.lock().unwrap().text_mode = mode;
+}
+
+/// Returns which parts of glyphs the text functions draw.
+pub fn text_mode() -> TextMode {
+    CONTEXT.lock().unwrap().text_mode
+}
+
+/// Sets the shadow drawn behind text, or None for no shadow.
+///
+/// The shadow takes the shape of the text mode's glyph parts.
+pub fn set_text_shadow(shadow: Option<TextShadow>) {
+    CONTEXT.lock().unwrap().text_shadow = shadow;
+}
+
+/// Returns the shadow drawn behind text.
+pub fn text_shadow() -> Option<TextShadow> {
+    CONTEXT.lock().unwrap().text_shadow
 }
 
 /// Renders a string of text at a specified location, size, using the specified font glyphs.
-pub fn text(x: VGfloat, y: VGfloat, s: &str, f: &Font, pointsize: u32) {
-    let size = pointsize as VGfloat;
-    let mut xx = x;
-    let mm: [VGfloat; 9] = [0.0; 9];
-    unsafe {
-        vgGetMatrix(&mm as *const VGfloat);
-        for c in s.chars() {
-            let glyph_index = f.character_map[c as usize];
-            if glyph_index == -1 {
-                continue;
-            }
-            let mat: [VGfloat; 9] = [size, 0.0, 0.0, 0.0, size, 0.0, xx, y, 1.0];
-            vgLoadMatrix(&mm as *const VGfloat);
-            vgMultMatrix(&mat as *const VGfloat);
-            let path = f.glyphs[glyph_index as usize];
-            vgDrawPath(
-                path,
-                VGPaintMode::VG_FILL_PATH as u32 | VGPaintMode::VG_STROKE_PATH as u32,
+///
+/// The text starts at (x,y): right-to-left text, as decided by `is_rtl()`, ends there.
+/// Text is drawn according to the text mode and shadow. Each run of glyphs from the same
+/// font is drawn with one `vgDrawGlyphs` call.
+pub fn text<F: GlyphSource + ?Sized>(x: VGfloat, y: VGfloat, s: &str, f: &F, pointsize: VGfloat) {
+    if is_rtl(s) {
+        let tw = text_width(s, f, pointsize);
+        text_from_left(x - tw, y, s, f, pointsize);
+    } else {
+        text_from_left(x, y, s, f, pointsize);
+    }
+}
+
+/// Renders text with its left edge at x, whatever its direction.
+fn text_from_left<F: GlyphSource + ?Sized>(
+    x: VGfloat,
+    y: VGfloat,
+    s: &str,
+    f: &F,
+    pointsize: VGfloat,
+) {
+    draw_shaped(x, y, &shape(s, f), pointsize);
+}
+
+/// Draws shaped glyphs, and their shadow, with their baseline starting at (x,y).
+fn draw_shaped(x: VGfloat, y: VGfloat, glyphs: &[PlacedGlyph], pointsize: VGfloat) {
+    if glyphs.is_empty() {
+        return;
+    }
+    let (mode, shadow) = {
+        let context = CONTEXT.lock().unwrap();
+        (context.text_mode, context.text_shadow)
+    };
+    if let Some(ref shadow) = shadow {
+        draw_text_shadow(x, y, glyphs, pointsize, mode, shadow);
+    }
+    draw_glyphs(x, y, glyphs, pointsize, mode.paint_modes());
+}
+
+/// Draws shaped glyphs with their baseline starting at (x,y).
+fn draw_glyphs(
+    x: VGfloat,
+    y: VGfloat,
+    glyphs: &[PlacedGlyph],
+    pointsize: VGfloat,
+    paint_modes: VGbitfield,
+) {
+    let glyph_transform = current_transform_for(MatrixMode::Glyph);
+    let mut origin: [VGfloat; 2] = [0.0; 2];
+    unsafe {
+        vgGetfv(VGParamType::VG_GLYPH_ORIGIN, 2, origin.as_mut_ptr());
+    }
+    let placement = current_transform_for(MatrixMode::Path)
+        .multiply(&Affine::translation(x, y))
+        .multiply(&Affine::scaling(pointsize, pointsize));
+    set_transform_for(MatrixMode::Glyph, &placement);
+
+    let mut start = 0;
+    while start < glyphs.len() {
+        let font = glyphs[start].glyph.font;
+        let end = glyphs[start..]
+            .iter()
+            .position(|g| g.glyph.font != font)
+            .map(|n| start + n)
+            .unwrap_or(glyphs.len());
+        let run = &glyphs[start..end];
+        let indices: Vec<VGuint> = run.iter().map(|g| g.glyph.index).collect();
+        // Escapements carry each glyph's own advance; the adjustments move the origin on
+        // to where shape() placed the next glyph.
+        let adjustments: Vec<VGfloat> = run
+            .iter()
+            .enumerate()
+            .map(|(i, g)| match run.get(i + 1) {
+                Some(next) => (next.x - g.x - g.glyph.advance) as VGfloat / 65536.0,
+                None => 0.0,
+            })
+            .collect();
+        let run_origin: [VGfloat; 2] = [run[0].x as VGfloat / 65536.0, 0.0];
+        unsafe {
+            vgSetfv(VGParamType::VG_GLYPH_ORIGIN, 2, run_origin.as_ptr());
+            vgDrawGlyphs(
+                font,
+                run.len() as VGint,
+                indices.as_ptr(),
+                adjustments.as_ptr(),
+                ptr::null(),
+                paint_modes,
+                VGboolean::VG_FALSE,
             );
-            xx += size * f.glyph_advances[glyph_index as usize] as f32 / 65536.0;
         }
-        vgLoadMatrix(&mm as *const VGfloat);
+        start = end;
+    }
+
+    set_transform_for(MatrixMode::Glyph, &glyph_transform);
+    unsafe {
+        vgSetfv(VGParamType::VG_GLYPH_ORIGIN, 2, origin.as_ptr());
+    }
+}
+
+/// Draws the shadow of shaped glyphs.
+///
+/// Blurred shadows are drawn into a RenderTarget, blurred and drawn back as an image,
+/// which is kept so that drawing the same text again doesn't blur it again.
+fn draw_text_shadow(
+    x: VGfloat,
+    y: VGfloat,
+    glyphs: &[PlacedGlyph],
+    pointsize: VGfloat,
+    mode: TextMode,
+    shadow: &TextShadow,
+) {
+    let (fill, stroke) = {
+        let context = CONTEXT.lock().unwrap();
+        (context.fill.clone(), context.stroke.clone())
+    };
+    let shadow_paint = Paint::Color(shadow.color);
+    let all = VGPaintMode::VG_FILL_PATH as u32 | VGPaintMode::VG_STROKE_PATH as u32;
+    if shadow.blur <= 0.0 {
+        apply_paint(&shadow_paint, all);
+        draw_glyphs(
+            x + shadow.dx,
+            y + shadow.dy,
+            glyphs,
+            pointsize,
+            mode.paint_modes(),
+        );
+        apply_paint(&fill, VGPaintMode::VG_FILL_PATH as u32);
+        apply_paint(&stroke, VGPaintMode::VG_STROKE_PATH as u32);
+        return;
     }
+
+    let stroke_width = if mode == TextMode::Fill {
+        0.0
+    } else {
+        unsafe { vgGetf(VGParamType::VG_STROKE_LINE_WIDTH) }
+    };
+    let key = ShadowKey {
+        glyphs: glyphs.iter().map(|g| (g.glyph.path, g.x)).collect(),
+        pointsize: pointsize,
+        mode: mode,
+        stroke_width: stroke_width,
+        blur: shadow.blur,
+        color: shadow.color,
+    };
+    let mut shadows = SHADOWS.lock().unwrap();
+    let cached = match shadows.iter().position(|s| s.key == key) {
+        Some(i) => shadows.remove(i),
+        None => match blur_text_shadow(key, glyphs) {
+            Some(blurred) => blurred,
+            None => return,
+        },
+    };
+    image(
+        &cached.image,
+        x + shadow.dx + cached.left,
+        y + shadow.dy + cached.bottom,
+        cached.image.width() as VGfloat,
+        cached.image.height() as VGfloat,
+    );
+    // Keep the most recently drawn shadows at the end.
+    if shadows.len() == SHADOW_CACHE_SIZE {
+        shadows.remove(0);
+    }
+    shadows.push(cached);
+}
+
+/// Identifies the image of a blurred text shadow, which doesn't depend on where the
+/// text or its shadow are drawn.
+#[derive(PartialEq)]
+struct ShadowKey {
+    glyphs: Vec<(VGPath, i32)>,
+    pointsize: VGfloat,
+    mode: TextMode,
+    stroke_width: VGfloat,
+    blur: VGfloat,
+    color: [VGfloat; 4],
+}
+
+/// A blurred text shadow, with the offset of its image from the start of the text.
+struct BlurredShadow {
+    key: ShadowKey,
+    image: Image,
+    left: VGfloat,
+    bottom: VGfloat,
+}
+
+/// The number of blurred text shadows kept for reuse.
+const SHADOW_CACHE_SIZE: usize = 16;
+
+lazy_static! {
+    static ref SHADOWS: Mutex<Vec<BlurredShadow>> = Mutex::new(Vec::new());
+}
+
+/// Renders and blurs the shadow of shaped glyphs.
+fn blur_text_shadow(key: ShadowKey, glyphs: &[PlacedGlyph]) -> Option<BlurredShadow> {
+    let ink = ink_bounds(glyphs, key.pointsize)?;
+    // Leave room for the blur to fade out.
+    let margin = 3.0 * key.blur + key.stroke_width / 2.0;
+    let left = (ink[0] - margin).floor();
+    let bottom = (ink[1] - margin).floor();
+    let w = (ink[2] + margin - left).ceil().max(1.0);
+    let h = (ink[3] + margin - bottom).ceil().max(1.0);
+    let mut target = RenderTarget::new(w as u32, h as u32).ok()?;
+    let all = VGPaintMode::VG_FILL_PATH as u32 | VGPaintMode::VG_STROKE_PATH as u32;
+    target.draw(|| {
+        apply_paint(&Paint::Color(key.color), all);
+        draw_glyphs(
+            -left,
+            -bottom,
+            glyphs,
+            key.pointsize,
+            key.mode.paint_modes(),
+        );
+    });
+    let blurred = Filter::blur(key.blur).apply(target.image()).ok()?;
+    Some(BlurredShadow {
+        key: key,
+        image: blurred,
+        left: left,
+        bottom: bottom,
+    })
+}
+
+/// Discards blurred text shadows, whose glyph paths may be reused once a font is
+/// dropped.
+pub(crate) fn forget_text_shadows() {
+    SHADOWS.lock().unwrap().clear();
+}
+
+/// Returns the box covering the outlines of shaped glyphs as [x0, y0, x1, y1].
+fn ink_bounds(glyphs: &[PlacedGlyph], pointsize: VGfloat) -> Option<[VGfloat; 4]> {
+    let mut ink: Option<[VGfloat; 4]> = None;
+    for g in glyphs.iter() {
+        let (mut x, mut y, mut w, mut h) = (0.0, 0.0, -1.0, -1.0);
+        unsafe {
+            vgPathBounds(g.glyph.path, &mut x, &mut y, &mut w, &mut h);
+        }
+        if w < 0.0 || h < 0.0 || (w == 0.0 && h == 0.0) {
+            continue;
+        }
+        let x0 = pointsize * (x + g.x as VGfloat / 65536.0);
+        let y0 = pointsize * y;
+        let x1 = x0 + pointsize * w;
+        let y1 = y0 + pointsize * h;
+        ink = Some(match ink {
+            Some(r) => [r[0].min(x0), r[1].min(y0), r[2].max(x1), r[3].max(y1)],
+            None => [x0, y0, x1, y1],
+        });
+    }
+    ink
 }
 
 /// Draws text centered on (x,y).
-pub fn text_mid(x: VGfloat, y: VGfloat, s: &str, f: &Font, pointsize: u32) {
+pub fn text_mid<F: GlyphSource + ?Sized>(
+    x: VGfloat,
+    y: VGfloat,
+    s: &str,
+    f: &F,
+    pointsize: VGfloat,
+) {
     let tw = text_width(s, f, pointsize);
-    text(x - (tw / 2.0), y, s, f, pointsize);
+    text_from_left(x - (tw / 2.0), y, s, f, pointsize);
 }
 
-/// Draws text with its end aligned to (x,y).
-pub fn text_end(x: VGfloat, y: VGfloat, s: &str, f: &Font, pointsize: u32) {
-    let tw = text_width(s, f, pointsize);
-    text(x - tw, y, s, f, pointsize);
+/// Draws text with its end aligned to (x,y): its right edge, or its left edge for
+/// right-to-left text.
+pub fn text_end<F: GlyphSource + ?Sized>(
+    x: VGfloat,
+    y: VGfloat,
+    s: &str,
+    f: &F,
+    pointsize: VGfloat,
+) {
+    if is_rtl(s) {
+        text_from_left(x, y, s, f, pointsize);
+    } else {
+        let tw = text_width(s, f, pointsize);
+        text_from_left(x - tw, y, s, f, pointsize);
+    }
+}
+
+/// The measurements of a string of text, relative to the start of its baseline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextMetrics {
+    /// The distance to where the next character would be placed.
+    pub width: VGfloat,
+    /// How far the font extends above the baseline.
+    pub ascent: VGfloat,
+    /// How far the font extends below the baseline.
+    pub descent: VGfloat,
+    /// The box covering the outlines of the glyphs as [x, y, w, h], with y measured up
+    /// from the baseline. The box is empty at the origin if nothing would be drawn.
+    pub ink: [VGfloat; 4],
+}
+
+/// Measures a string of text, including the bounds of the ink it would draw.
+pub fn measure_text<F: GlyphSource + ?Sized>(s: &str, f: &F, pointsize: VGfloat) -> TextMetrics {
+    let glyphs = shape(s, f);
+    let width = glyphs.iter().map(|g| g.x + g.advance).max().unwrap_or(0);
+    let ink = ink_bounds(&glyphs, pointsize).unwrap_or([0.0; 4]);
+    TextMetrics {
+        width: pointsize * width as VGfloat / 65536.0,
+        ascent: text_height(f, pointsize) - text_depth(f, pointsize),
+        descent: text_depth(f, pointsize),
+        ink: [ink[0], ink[1], ink[2] - ink[0], ink[3] - ink[1]],
+    }
+}
+
+/// Renders text made of styled spans, starting on the baseline at (x,y).
+///
+/// Spans with a color are drawn in it and the fill paint is restored afterwards.
+/// Right-to-left text is reordered across spans, as `text` reorders a string.
+pub fn rich_text(x: VGfloat, y: VGfloat, s: &AttributedString) {
+    rich_text_spaced(x, y, s, 0.0, None);
+}
+
+/// Splits rich text into the pieces of spans it is drawn in, from left to right, each
+/// with its span's index and its characters in visual order.
+fn visual_pieces(s: &AttributedString, rtl: Option<bool>) -> Vec<(usize, String)> {
+    let mut line = String::new();
+    let mut ranges = Vec::with_capacity(s.spans.len());
+    for span in s.spans.iter() {
+        let start = line.len();
+        line.push_str(&span.text);
+        ranges.push(start..line.len());
+    }
+    let mut pieces: Vec<(usize, String)> = Vec::new();
+    for (run, run_rtl) in visual_runs(&line, rtl) {
+        let mut parts = Vec::new();
+        for (i, range) in ranges.iter().enumerate() {
+            let start = range.start.max(run.start);
+            let end = range.end.min(run.end);
+            if start < end {
+                parts.push((i, run_text(&line[start..end], run_rtl)));
+            }
+        }
+        if run_rtl {
+            parts.reverse();
+        }
+        // Runs of the same span that end up side by side are drawn together.
+        for (i, text) in parts {
+            if let Some(last) = pieces.last_mut() {
+                if last.0 == i {
+                    last.1.push_str(&text);
+                    continue;
+                }
+            }
+            pieces.push((i, text));
+        }
+    }
+    pieces
+}
+
+/// Renders rich text with word_gap added to the width of each space, in a paragraph
+/// whose direction is given by rtl or by its first strong character.
+pub(crate) fn rich_text_spaced(
+    x: VGfloat,
+    y: VGfloat,
+    s: &AttributedString,
+    word_gap: VGfloat,
+    rtl: Option<bool>,
+) {
+    let fill = CONTEXT.lock().unwrap().fill.clone();
+    let mut recolored = false;
+    let mut pen = x;
+    for (i, text) in visual_pieces(s, rtl) {
+        let style = &s.spans[i].style;
+        if let Some(ref color) = style.color {
+            set_fill(color);
+            recolored = true;
+        }
+        let baseline = y + style.baseline_shift;
+        let start = pen;
+        if word_gap == 0.0 {
+            let glyphs = shape_visual(&text, style.font);
+            draw_shaped(pen, baseline, &glyphs, style.pointsize);
+            pen += glyphs_width(&glyphs, style.pointsize);
+        } else {
+            let space = text_width(" ", style.font, style.pointsize) + word_gap;
+            for (j, word) in text.split(' ').enumerate() {
+                if j > 0 {
+                    pen += space;
+                }
+                let glyphs = shape_visual(word, style.font);
+                draw_shaped(pen, baseline, &glyphs, style.pointsize);
+                pen += glyphs_width(&glyphs, style.pointsize);
+            }
+        }
+        if style.underline || style.strikethrough {
+            let height = text_height(style.font, style.pointsize);
+            let depth = text_depth(style.font, style.pointsize);
+            let thickness = (height / 20.0).max(1.0);
+            if style.underline {
+                rect(
+                    start,
+                    baseline - depth / 2.0 - thickness / 2.0,
+                    pen - start,
+                    thickness,
+                );
+            }
+            if style.strikethrough {
+                let middle = baseline + (height - depth) * 0.3;
+                rect(start, middle - thickness / 2.0, pen - start, thickness);
+            }
+        }
+        if style.color.is_some() {
+            apply_paint(&fill, VGPaintMode::VG_FILL_PATH as u32);
+        }
+    }
+    if recolored {
+        CONTEXT.lock().unwrap().fill = fill;
+    }
 }
 
 /// Reports a font's height.
-pub fn text_height(f: &Font, pointsize: u32) -> VGfloat {
-    return (f.font_height * pointsize as i32) as VGfloat / 65536.0;
+pub fn text_height<F: GlyphSource + ?Sized>(f: &F, pointsize: VGfloat) -> VGfloat {
+    return f.font_height() as VGfloat * pointsize / 65536.0;
 }
 
 /// Reports a font's depth (how far under the baseline it goes).
-pub fn text_depth(f: &Font, pointsize: u32) -> VGfloat {
-    return (-f.descender_height * pointsize as i32) as VGfloat / 65536.0;
+pub fn text_depth<F: GlyphSource + ?Sized>(f: &F, pointsize: VGfloat) -> VGfloat {
+    return -f.descender_height() as VGfloat * pointsize / 65536.0;
 }
 
 //
@@ -168,47 +681,899 @@ pub fn scale(x: VGfloat, y: VGfloat) {
     }
 }
 
+/// Multiplies the current transformation by t, so that t is applied first.
+pub fn transform(t: &Affine) {
+    let m = t.to_matrix();
+    unsafe {
+        vgMultMatrix(m.as_ptr());
+    }
+}
+
+/// Replaces the current transformation with t.
+pub fn set_transform(t: &Affine) {
+    let m = t.to_matrix();
+    unsafe {
+        vgLoadMatrix(m.as_ptr());
+    }
+}
+
+/// Returns the current transformation.
+pub fn current_transform() -> Affine {
+    let mut m: [VGfloat; 9] = [0.0; 9];
+    unsafe {
+        vgGetMatrix(m.as_mut_ptr());
+    }
+    Affine::from_matrix(&m)
+}
+
+/// Identifies one of the transformations maintained by OpenVG.
+///
+/// Drawing functions use the Path transformation unless another mode is selected
+/// with `with_matrix_mode`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MatrixMode {
+    /// Maps path coordinates to the drawing surface.
+    Path,
+    /// Maps image coordinates to the drawing surface.
+    Image,
+    /// Maps fill paint coordinates (such as gradient endpoints) to path coordinates.
+    FillPaint,
+    /// Maps stroke paint coordinates to path coordinates.
+    StrokePaint,
+    /// Maps glyph coordinates to the drawing surface.
+    Glyph,
+}
+
+impl MatrixMode {
+    /// Returns all matrix modes.
+    pub fn all() -> [MatrixMode; 5] {
+        [
+            MatrixMode::Path,
+            MatrixMode::Image,
+            MatrixMode::FillPaint,
+            MatrixMode::StrokePaint,
+            MatrixMode::Glyph,
+        ]
+    }
+
+    fn value(&self) -> VGint {
+        match *self {
+            MatrixMode::Path => VGMatrixMode::VG_MATRIX_PATH_USER_TO_SURFACE as VGint,
+            MatrixMode::Image => VGMatrixMode::VG_MATRIX_IMAGE_USER_TO_SURFACE as VGint,
+            MatrixMode::FillPaint => VGMatrixMode::VG_MATRIX_FILL_PAINT_TO_USER as VGint,
+            MatrixMode::StrokePaint => VGMatrixMode::VG_MATRIX_STROKE_PAINT_TO_USER as VGint,
+            MatrixMode::Glyph => VGMatrixMode::VG_MATRIX_GLYPH_USER_TO_SURFACE as VGint,
+        }
+    }
+}
+
+/// Runs f with the given matrix mode selected, so that translate, rotate, shear, scale
+/// and the other transformation functions apply to that mode's transformation.
+pub fn with_matrix_mode<F, R>(mode: MatrixMode, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    unsafe {
+        let previous = vgGeti(VGParamType::VG_MATRIX_MODE);
+        vgSeti(VGParamType::VG_MATRIX_MODE, mode.value());
+        let result = f();
+        vgSeti(VGParamType::VG_MATRIX_MODE, previous);
+        result
+    }
+}
+
+/// Multiplies the transformation for a matrix mode by t, so that t is applied first.
+pub fn transform_for(mode: MatrixMode, t: &Affine) {
+    with_matrix_mode(mode, || transform(t));
+}
+
+/// Replaces the transformation for a matrix mode with t.
+pub fn set_transform_for(mode: MatrixMode, t: &Affine) {
+    with_matrix_mode(mode, || set_transform(t));
+}
+
+/// Returns the transformation for a matrix mode.
+pub fn current_transform_for(mode: MatrixMode) -> Affine {
+    with_matrix_mode(mode, || current_transform())
+}
+
+/// Draws an OpenVG image placed by t in the current coordinate system.
+pub fn place_image(image: VGImage, t: &Affine) {
+    let placement = current_transform_for(MatrixMode::Path).multiply(t);
+    let (opacity, fill) = {
+        let context = CONTEXT.lock().unwrap();
+        (context.image_opacity, context.fill.clone())
+    };
+    unsafe {
+        let image_mode = vgGeti(VGParamType::VG_IMAGE_MODE);
+        if opacity < 1.0 {
+            // In multiply mode, image colors are multiplied by the fill paint.
+            vgSeti(
+                VGParamType::VG_IMAGE_MODE,
+                VGImageMode::VG_DRAW_IMAGE_MULTIPLY as VGint,
+            );
+            apply_paint(
+                &Paint::Color([1.0, 1.0, 1.0, opacity]),
+                VGPaintMode::VG_FILL_PATH as u32,
+            );
+        }
+        with_matrix_mode(MatrixMode::Image, || {
+            let previous = current_transform();
+            set_transform(&placement);
+            vgDrawImage(image);
+            set_transform(&previous);
+        });
+        if opacity < 1.0 {
+            apply_paint(&fill, VGPaintMode::VG_FILL_PATH as u32);
+            vgSeti(VGParamType::VG_IMAGE_MODE, image_mode);
+        }
+    }
+}
+
 //
-// Style functions
+// Image functions
 //
 
-/// Sets the fill color.
-fn set_fill(color: &[VGfloat]) {
+/// Specifies how an image is fitted into a destination rectangle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Fit {
+    /// Stretches the image to fill the rectangle.
+    Fill,
+    /// Scales the image to fit inside the rectangle, keeping its aspect ratio.
+    Contain,
+    /// Scales the image to cover the rectangle, keeping its aspect ratio and cropping.
+    Cover,
+}
+
+/// Sets the opacity used to draw images.
+pub fn image_opacity(opacity: VGfloat) {
+    CONTEXT.lock().unwrap().image_opacity = opacity.max(0.0).min(1.0);
+}
+
+/// Draws an image stretched into the specified rectangle.
+pub fn image(img: &Image, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+    image_crop(
+        img,
+        0,
+        0,
+        img.width() as VGint,
+        img.height() as VGint,
+        x,
+        y,
+        w,
+        h,
+    );
+}
+
+/// Draws an image in the specified rectangle, scaled according to fit.
+pub fn image_fit(img: &Image, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat, fit: Fit) {
+    let iw = img.width() as VGfloat;
+    let ih = img.height() as VGfloat;
+    if iw == 0.0 || ih == 0.0 {
+        return;
+    }
+    match fit {
+        Fit::Fill => image(img, x, y, w, h),
+        Fit::Contain => {
+            let s = (w / iw).min(h / ih);
+            image(
+                img,
+                x + (w - iw * s) / 2.0,
+                y + (h - ih * s) / 2.0,
+                iw * s,
+                ih * s,
+            );
+        }
+        Fit::Cover => {
+            // Crop the source to the part of the image that lands in the rectangle.
+            let s = (w / iw).max(h / ih);
+            let sw = (w / s).round().min(iw);
+            let sh = (h / s).round().min(ih);
+            image_crop(
+                img,
+                ((iw - sw) / 2.0) as VGint,
+                ((ih - sh) / 2.0) as VGint,
+                sw as VGint,
+                sh as VGint,
+                x,
+                y,
+                w,
+                h,
+            );
+        }
+    }
+}
+
+/// Draws the part of an image in the source rectangle sx, sy, sw, sh (in image pixels,
+/// measured from the bottom left) stretched into the destination rectangle x, y, w, h.
+pub fn image_crop(
+    img: &Image,
+    sx: VGint,
+    sy: VGint,
+    sw: VGint,
+    sh: VGint,
+    x: VGfloat,
+    y: VGfloat,
+    w: VGfloat,
+    h: VGfloat,
+) {
+    let x0 = sx.max(0);
+    let y0 = sy.max(0);
+    let x1 = (sx + sw).min(img.width() as VGint);
+    let y1 = (sy + sh).min(img.height() as VGint);
+    if x1 <= x0 || y1 <= y0 {
+        return;
+    }
+    let t = Affine::translation(x, y).multiply(&Affine::scaling(
+        w / (x1 - x0) as VGfloat,
+        h / (y1 - y0) as VGfloat,
+    ));
+    if x0 == 0 && y0 == 0 && x1 == img.width() as VGint && y1 == img.height() as VGint {
+        place_image(img.handle(), &t);
+    } else {
+        unsafe {
+            let child = vgChildImage(img.handle(), x0, y0, x1 - x0, y1 - y0);
+            place_image(child, &t);
+            vgDestroyImage(child);
+        }
+    }
+}
+
+/// Draws an image into the specified rectangle as a nine-slice: corners are drawn at
+/// their natural size, edges are stretched along their length and the center is
+/// stretched in both directions.
+///
+/// The insets give the sizes of the left, bottom, right and top borders in image pixels.
+/// If the rectangle is smaller than the borders, the borders are scaled down to fit.
+pub fn image_nine_slice(
+    img: &Image,
+    left: VGint,
+    bottom: VGint,
+    right: VGint,
+    top: VGint,
+    x: VGfloat,
+    y: VGfloat,
+    w: VGfloat,
+    h: VGfloat,
+) {
+    let iw = img.width() as VGint;
+    let ih = img.height() as VGint;
+    let sx = [0, left, iw - right, iw];
+    let sy = [0, bottom, ih - top, ih];
+    let horizontal = (left + right) as VGfloat;
+    let vertical = (bottom + top) as VGfloat;
+    let kx = if horizontal > w { w / horizontal } else { 1.0 };
+    let ky = if vertical > h { h / vertical } else { 1.0 };
+    let dx = [
+        x,
+        x + left as VGfloat * kx,
+        x + w - right as VGfloat * kx,
+        x + w,
+    ];
+    let dy = [
+        y,
+        y + bottom as VGfloat * ky,
+        y + h - top as VGfloat * ky,
+        y + h,
+    ];
+    for j in 0..3 {
+        for i in 0..3 {
+            if sx[i + 1] > sx[i] && sy[j + 1] > sy[j] && dx[i + 1] > dx[i] && dy[j + 1] > dy[j] {
+                image_crop(
+                    img,
+                    sx[i],
+                    sy[j],
+                    sx[i + 1] - sx[i],
+                    sy[j + 1] - sy[j],
+                    dx[i],
+                    dy[j],
+                    dx[i + 1] - dx[i],
+                    dy[j + 1] - dy[j],
+                );
+            }
+        }
+    }
+}
+
+//
+// Graphics state
+//
+
+/// Describes a paint so that it can be recreated when a state is restored.
+#[derive(Clone)]
+enum Paint {
+    Color([VGfloat; 4]),
+    LinearGradient([VGfloat; 4], Vec<VGfloat>),
+    RadialGradient([VGfloat; 5], Vec<VGfloat>),
+    Pattern(Arc<ImageHandle>, VGint),
+}
+
+/// A snapshot of the drawing state taken by `save()`.
+struct State {
+    matrix_mode: VGint,
+    transforms: [Affine; 5],
+    fill: Paint,
+    stroke: Paint,
+    stroke_width: VGfloat,
+    cap_style: VGint,
+    join_style: VGint,
+    miter_limit: VGfloat,
+    dash_pattern: Vec<VGfloat>,
+    dash_phase: VGfloat,
+    fill_rule: VGint,
+    scissoring: VGint,
+    scissor_rects: Vec<VGint>,
+    blend_mode: VGint,
+    image_opacity: VGfloat,
+    text_mode: TextMode,
+    text_shadow: Option<TextShadow>,
+    masking: VGint,
+    mask: VGMaskLayer,
+}
+
+/// Tracks drawing state that can't be read back from OpenVG.
+struct Context {
+    width: u32,
+    height: u32,
+    fill: Paint,
+    stroke: Paint,
+    image_opacity: VGfloat,
+    text_mode: TextMode,
+    text_shadow: Option<TextShadow>,
+    stack: Vec<State>,
+    layers: Vec<Layer>,
+}
+
+lazy_static! {
+    static ref CONTEXT: Mutex<Context> = Mutex::new(Context {
+        width: 0,
+        height: 0,
+        fill: Paint::Color([0.0, 0.0, 0.0, 1.0]),
+        stroke: Paint::Color([0.0, 0.0, 0.0, 1.0]),
+        image_opacity: 1.0,
+        text_mode: TextMode::FillAndStroke,
+        text_shadow: None,
+        stack: Vec::new(),
+        layers: Vec::new(),
+    });
+}
+
+/// Records the surface size and discards any states left over from the previous frame.
+fn begin_frame(w: u32, h: u32) {
+    let mut context = CONTEXT.lock().unwrap();
+    context.width = w;
+    context.height = h;
+    context.image_opacity = 1.0;
+    context.text_mode = TextMode::FillAndStroke;
+    context.text_shadow = None;
+    for state in context.stack.drain(..) {
+        if state.mask != VG_INVALID_HANDLE {
+            unsafe { vgDestroyMaskLayer(state.mask) }
+        }
+    }
+    for layer in context.layers.drain(..) {
+        unsafe { vgDestroyImage(layer.backdrop) }
+    }
+}
+
+/// Saves the current transforms, paints, stroke style, fill rule, clipping, blend mode, image
+/// opacity, text mode and shadow, and mask.
+pub fn save() {
+    let mut context = CONTEXT.lock().unwrap();
     unsafe {
-        let fill_paint = vgCreatePaint();
-        vgSetParameteri(
-            fill_paint,
-            VGPaintParamType::VG_PAINT_TYPE as i32,
-            VGPaintType::VG_PAINT_TYPE_COLOR as i32,
+        let modes = MatrixMode::all();
+        let mut transforms = [Affine::identity(); 5];
+        for i in 0..modes.len() {
+            transforms[i] = current_transform_for(modes[i]);
+        }
+
+        let n = vgGetVectorSize(VGParamType::VG_STROKE_DASH_PATTERN);
+        let mut dash_pattern = vec![0.0 as VGfloat; n as usize];
+        vgGetfv(
+            VGParamType::VG_STROKE_DASH_PATTERN,
+            n,
+            dash_pattern.as_mut_ptr(),
         );
-        vgSetParameterfv(
-            fill_paint,
-            VGPaintParamType::VG_PAINT_COLOR as i32,
-            4,
-            color.as_ptr(),
+
+        let n = vgGetVectorSize(VGParamType::VG_SCISSOR_RECTS);
+        let mut scissor_rects = vec![0 as VGint; n as usize];
+        vgGetiv(VGParamType::VG_SCISSOR_RECTS, n, scissor_rects.as_mut_ptr());
+
+        let masking = vgGeti(VGParamType::VG_MASKING);
+        let mut mask = VG_INVALID_HANDLE;
+        if masking == VGboolean::VG_TRUE as VGint {
+            let w = context.width as VGint;
+            let h = context.height as VGint;
+            mask = vgCreateMaskLayer(w, h);
+            vgCopyMask(mask, 0, 0, 0, 0, w, h);
+        }
+
+        let state = State {
+            matrix_mode: vgGeti(VGParamType::VG_MATRIX_MODE),
+            transforms: transforms,
+            fill: context.fill.clone(),
+            stroke: context.stroke.clone(),
+            stroke_width: vgGetf(VGParamType::VG_STROKE_LINE_WIDTH),
+            cap_style: vgGeti(VGParamType::VG_STROKE_CAP_STYLE),
+            join_style: vgGeti(VGParamType::VG_STROKE_JOIN_STYLE),
+            miter_limit: vgGetf(VGParamType::VG_STROKE_MITER_LIMIT),
+            dash_pattern: dash_pattern,
+            dash_phase: vgGetf(VGParamType::VG_STROKE_DASH_PHASE),
+            fill_rule: vgGeti(VGParamType::VG_FILL_RULE),
+            scissoring: vgGeti(VGParamType::VG_SCISSORING),
+            scissor_rects: scissor_rects,
+            blend_mode: vgGeti(VGParamType::VG_BLEND_MODE),
+            image_opacity: context.image_opacity,
+            text_mode: context.text_mode,
+            text_shadow: context.text_shadow,
+            masking: masking,
+            mask: mask,
+        };
+        context.stack.push(state);
+    }
+}
+
+/// Restores the state most recently saved with `save()`.
+pub fn restore() {
+    let mut context = CONTEXT.lock().unwrap();
+    let state = match context.stack.pop() {
+        Some(state) => state,
+        None => return,
+    };
+    apply_paint(&state.fill, VGPaintMode::VG_FILL_PATH as u32);
+    apply_paint(&state.stroke, VGPaintMode::VG_STROKE_PATH as u32);
+    let modes = MatrixMode::all();
+    for i in 0..modes.len() {
+        set_transform_for(modes[i], &state.transforms[i]);
+    }
+    unsafe {
+        vgSeti(VGParamType::VG_MATRIX_MODE, state.matrix_mode);
+        vgSetf(VGParamType::VG_STROKE_LINE_WIDTH, state.stroke_width);
+        vgSeti(VGParamType::VG_STROKE_CAP_STYLE, state.cap_style);
+        vgSeti(VGParamType::VG_STROKE_JOIN_STYLE, state.join_style);
+        vgSetf(VGParamType::VG_STROKE_MITER_LIMIT, state.miter_limit);
+        vgSetfv(
+            VGParamType::VG_STROKE_DASH_PATTERN,
+            state.dash_pattern.len() as VGint,
+            state.dash_pattern.as_ptr(),
+        );
+        vgSetf(VGParamType::VG_STROKE_DASH_PHASE, state.dash_phase);
+        vgSeti(VGParamType::VG_FILL_RULE, state.fill_rule);
+        vgSetiv(
+            VGParamType::VG_SCISSOR_RECTS,
+            state.scissor_rects.len() as VGint,
+            state.scissor_rects.as_ptr(),
         );
-        vgSetPaint(fill_paint, VGPaintMode::VG_FILL_PATH as u32);
-        vgDestroyPaint(fill_paint);
+        vgSeti(VGParamType::VG_SCISSORING, state.scissoring);
+        vgSeti(VGParamType::VG_BLEND_MODE, state.blend_mode);
+        if state.mask != VG_INVALID_HANDLE {
+            vgMask(
+                state.mask,
+                VGMaskOperation::VG_SET_MASK,
+                0,
+                0,
+                context.width as VGint,
+                context.height as VGint,
+            );
+            vgDestroyMaskLayer(state.mask);
+        }
+        vgSeti(VGParamType::VG_MASKING, state.masking);
     }
+    context.fill = state.fill;
+    context.stroke = state.stroke;
+    context.image_opacity = state.image_opacity;
+    context.text_mode = state.text_mode;
+    context.text_shadow = state.text_shadow;
 }
 
-/// Sets the stroke color.
-fn set_stroke(color: &[VGfloat]) {
+/// Saves the drawing state when created and restores it when dropped.
+pub struct SavedState {}
+
+impl SavedState {
+    /// Creates a new SavedState.
+    pub fn new() -> SavedState {
+        save();
+        SavedState {}
+    }
+}
+
+impl Drop for SavedState {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+//
+// Blending
+//
+
+/// Specifies how drawing is combined with what is already on the canvas.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BlendMode {
+    /// Replaces the destination.
+    Src,
+    /// Draws over the destination (the default).
+    SrcOver,
+    /// Draws under the destination.
+    DstOver,
+    /// Keeps the source where the destination is opaque.
+    SrcIn,
+    /// Keeps the destination where the source is opaque.
+    DstIn,
+    /// Multiplies source and destination colors, darkening.
+    Multiply,
+    /// Inverts, multiplies and inverts colors, lightening.
+    Screen,
+    /// Keeps the darker of source and destination colors.
+    Darken,
+    /// Keeps the lighter of source and destination colors.
+    Lighten,
+    /// Adds source and destination colors, useful for glows.
+    Additive,
+}
+
+impl BlendMode {
+    fn value(&self) -> VGint {
+        let mode = match *self {
+            BlendMode::Src => VGBlendMode::VG_BLEND_SRC,
+            BlendMode::SrcOver => VGBlendMode::VG_BLEND_SRC_OVER,
+            BlendMode::DstOver => VGBlendMode::VG_BLEND_DST_OVER,
+            BlendMode::SrcIn => VGBlendMode::VG_BLEND_SRC_IN,
+            BlendMode::DstIn => VGBlendMode::VG_BLEND_DST_IN,
+            BlendMode::Multiply => VGBlendMode::VG_BLEND_MULTIPLY,
+            BlendMode::Screen => VGBlendMode::VG_BLEND_SCREEN,
+            BlendMode::Darken => VGBlendMode::VG_BLEND_DARKEN,
+            BlendMode::Lighten => VGBlendMode::VG_BLEND_LIGHTEN,
+            BlendMode::Additive => VGBlendMode::VG_BLEND_ADDITIVE,
+        };
+        mode as VGint
+    }
+
+    fn from_value(value: VGint) -> BlendMode {
+        let modes = [
+            BlendMode::Src,
+            BlendMode::SrcOver,
+            BlendMode::DstOver,
+            BlendMode::SrcIn,
+            BlendMode::DstIn,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Darken,
+            BlendMode::Lighten,
+            BlendMode::Additive,
+        ];
+        for mode in modes.iter() {
+            if mode.value() == value {
+                return *mode;
+            }
+        }
+        BlendMode::SrcOver
+    }
+}
+
+/// Sets the blend mode used for subsequent drawing.
+pub fn set_blend_mode(mode: BlendMode) {
     unsafe {
-        let stroke_paint = vgCreatePaint();
-        vgSetParameteri(
-            stroke_paint,
-            VGPaintParamType::VG_PAINT_TYPE as i32,
-            VGPaintType::VG_PAINT_TYPE_COLOR as i32,
+        vgSeti(VGParamType::VG_BLEND_MODE, mode.value());
+    }
+}
+
+/// Returns the current blend mode.
+pub fn blend_mode() -> BlendMode {
+    unsafe { BlendMode::from_value(vgGeti(VGParamType::VG_BLEND_MODE)) }
+}
+
+/// An offscreen group of drawing started by `begin_layer()`.
+struct Layer {
+    backdrop: VGImage,
+    opacity: VGfloat,
+    mode: BlendMode,
+}
+
+/// Runs f with scissoring and masking turned off.
+fn unclipped<F: FnOnce()>(f: F) {
+    unsafe {
+        let scissoring = vgGeti(VGParamType::VG_SCISSORING);
+        let masking = vgGeti(VGParamType::VG_MASKING);
+        vgSeti(VGParamType::VG_SCISSORING, VGboolean::VG_FALSE as i32);
+        vgSeti(VGParamType::VG_MASKING, VGboolean::VG_FALSE as i32);
+        f();
+        vgSeti(VGParamType::VG_SCISSORING, scissoring);
+        vgSeti(VGParamType::VG_MASKING, masking);
+    }
+}
+
+/// Starts a layer: drawing up to the matching `end_layer()` is collected offscreen and
+/// then composited onto the canvas as a group with the given opacity and blend mode.
+///
+/// Layers may be nested.
+pub fn begin_layer(opacity: VGfloat, mode: BlendMode) {
+    let mut context = CONTEXT.lock().unwrap();
+    let w = context.width as VGint;
+    let h = context.height as VGint;
+    let mut backdrop = VG_INVALID_HANDLE;
+    unclipped(|| unsafe {
+        backdrop = vgCreateImage(
+            VGImageFormat::VG_sRGBA_8888_PRE,
+            w,
+            h,
+            VGImageQuality::VG_IMAGE_QUALITY_BETTER as u32,
         );
-        vgSetParameterfv(
-            stroke_paint,
-            VGPaintParamType::VG_PAINT_COLOR as i32,
-            4,
-            color.as_ptr(),
+        vgGetPixels(backdrop, 0, 0, 0, 0, w, h);
+        let mut clear_color: [VGfloat; 4] = [0.0; 4];
+        vgGetfv(VGParamType::VG_CLEAR_COLOR, 4, clear_color.as_mut_ptr());
+        let transparent: [VGfloat; 4] = [0.0; 4];
+        vgSetfv(VGParamType::VG_CLEAR_COLOR, 4, transparent.as_ptr());
+        vgClear(0, 0, w as VGuint, h as VGuint);
+        vgSetfv(VGParamType::VG_CLEAR_COLOR, 4, clear_color.as_ptr());
+    });
+    context.layers.push(Layer {
+        backdrop: backdrop,
+        opacity: opacity,
+        mode: mode,
+    });
+}
+
+/// Ends the layer most recently started with `begin_layer()` and composites it.
+pub fn end_layer() {
+    let mut context = CONTEXT.lock().unwrap();
+    let layer = match context.layers.pop() {
+        Some(layer) => layer,
+        None => return,
+    };
+    let w = context.width as VGint;
+    let h = context.height as VGint;
+    let fill = context.fill.clone();
+    unclipped(|| unsafe {
+        let group = vgCreateImage(
+            VGImageFormat::VG_sRGBA_8888_PRE,
+            w,
+            h,
+            VGImageQuality::VG_IMAGE_QUALITY_BETTER as u32,
         );
-        vgSetPaint(stroke_paint, VGPaintMode::VG_STROKE_PATH as u32);
-        vgDestroyPaint(stroke_paint);
+        vgGetPixels(group, 0, 0, 0, 0, w, h);
+        vgSetPixels(0, 0, layer.backdrop, 0, 0, w, h);
+        vgDestroyImage(layer.backdrop);
+
+        // In multiply mode, image colors are multiplied by the fill paint.
+        let image_mode = vgGeti(VGParamType::VG_IMAGE_MODE);
+        let blend_mode = vgGeti(VGParamType::VG_BLEND_MODE);
+        vgSeti(
+            VGParamType::VG_IMAGE_MODE,
+            VGImageMode::VG_DRAW_IMAGE_MULTIPLY as VGint,
+        );
+        vgSeti(VGParamType::VG_BLEND_MODE, layer.mode.value());
+        apply_paint(
+            &Paint::Color([1.0, 1.0, 1.0, layer.opacity]),
+            VGPaintMode::VG_FILL_PATH as u32,
+        );
+        with_matrix_mode(MatrixMode::Image, || {
+            let previous = current_transform();
+            set_transform(&Affine::identity());
+            vgDrawImage(group);
+            set_transform(&previous);
+        });
+        apply_paint(&fill, VGPaintMode::VG_FILL_PATH as u32);
+        vgSeti(VGParamType::VG_IMAGE_MODE, image_mode);
+        vgSeti(VGParamType::VG_BLEND_MODE, blend_mode);
+        vgDestroyImage(group);
+    });
+}
+
+//
+// Offscreen drawing
+//
+
+/// An Image that can be drawn into with the drawing functions.
+///
+/// On the Raspberry Pi drawing goes to an EGL pbuffer bound to the image. Elsewhere the
+/// target is drawn in the bottom left corner of the window and copied out, so it must
+/// not be larger than the window.
+pub struct RenderTarget {
+    // Declared first so that the surface is destroyed before its image.
+    surface: Option<egl::Surface>,
+    image: Image,
+}
+
+impl RenderTarget {
+    /// Creates a new RenderTarget with a transparent image.
+    pub fn new(width: u32, height: u32) -> Result<RenderTarget, Error> {
+        let image = Image::new(width, height, Format::RgbaPremultiplied)?;
+        let surface = if cfg!(target_os = "linux") {
+            egl::create_image_surface(image.handle())
+        } else {
+            None
+        };
+        let target = RenderTarget {
+            surface: surface,
+            image: image,
+        };
+        target.clear();
+        Ok(target)
+    }
+
+    /// Returns the image that holds the drawing.
+    ///
+    /// The image can be drawn with `image()`, used as a pattern with `fill_pattern()`
+    /// or exported with `Image::save_png()`.
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Returns the width of the target in pixels.
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// Returns the height of the target in pixels.
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Clears the target to transparent.
+    pub fn clear(&self) {
+        unsafe {
+            let mut clear_color: [VGfloat; 4] = [0.0; 4];
+            vgGetfv(VGParamType::VG_CLEAR_COLOR, 4, clear_color.as_mut_ptr());
+            let transparent: [VGfloat; 4] = [0.0; 4];
+            vgSetfv(VGParamType::VG_CLEAR_COLOR, 4, transparent.as_ptr());
+            vgClearImage(
+                self.image.handle(),
+                0,
+                0,
+                self.width() as VGint,
+                self.height() as VGint,
+            );
+            vgSetfv(VGParamType::VG_CLEAR_COLOR, 4, clear_color.as_ptr());
+        }
+    }
+
+    /// Runs f with drawing directed into the target.
+    ///
+    /// Drawing starts with identity transformations and no clipping, and adds to what
+    /// the target already holds. The drawing state is restored afterwards.
+    pub fn draw<F: FnOnce()>(&mut self, f: F) {
+        let w = self.width();
+        let h = self.height();
+        let (window_width, window_height, depth) = {
+            let mut context = CONTEXT.lock().unwrap();
+            let size = (context.width, context.height, context.stack.len());
+            context.width = w;
+            context.height = h;
+            size
+        };
+        let onscreen = match self.surface {
+            Some(ref surface) => !egl::make_current(Some(surface)),
+            None => true,
+        };
+
+        let mut backdrop = VG_INVALID_HANDLE;
+        if onscreen {
+            let w = w.min(window_width) as VGint;
+            let h = h.min(window_height) as VGint;
+            unclipped(|| unsafe {
+                backdrop = vgCreateImage(
+                    VGImageFormat::VG_sRGBA_8888_PRE,
+                    w,
+                    h,
+                    VGImageQuality::VG_IMAGE_QUALITY_BETTER as u32,
+                );
+                vgGetPixels(backdrop, 0, 0, 0, 0, w, h);
+                vgSetPixels(0, 0, self.image.handle(), 0, 0, w, h);
+            });
+        }
+
+        save();
+        unsafe {
+            vgSeti(VGParamType::VG_SCISSORING, VGboolean::VG_FALSE as i32);
+            vgSeti(VGParamType::VG_MASKING, VGboolean::VG_FALSE as i32);
+        }
+        for mode in MatrixMode::all().iter() {
+            set_transform_for(*mode, &Affine::identity());
+        }
+        f();
+        // Drop any states that f saved without restoring.
+        while CONTEXT.lock().unwrap().stack.len() > depth {
+            restore();
+        }
+
+        if onscreen {
+            let w = w.min(window_width) as VGint;
+            let h = h.min(window_height) as VGint;
+            unclipped(|| unsafe {
+                vgGetPixels(self.image.handle(), 0, 0, 0, 0, w, h);
+                vgSetPixels(0, 0, backdrop, 0, 0, w, h);
+                vgDestroyImage(backdrop);
+            });
+        } else {
+            egl::make_current(None);
+        }
+        let mut context = CONTEXT.lock().unwrap();
+        context.width = window_width;
+        context.height = window_height;
+    }
+}
+
+//
+// Style functions
+//
+
+/// Sets the fill color.
+fn set_fill(color: &[VGfloat]) {
+    let paint = Paint::Color([color[0], color[1], color[2], color[3]]);
+    apply_paint(&paint, VGPaintMode::VG_FILL_PATH as u32);
+    CONTEXT.lock().unwrap().fill = paint;
+}
+
+/// Sets the stroke color.
+fn set_stroke(color: &[VGfloat]) {
+    let paint = Paint::Color([color[0], color[1], color[2], color[3]]);
+    apply_paint(&paint, VGPaintMode::VG_STROKE_PATH as u32);
+    CONTEXT.lock().unwrap().stroke = paint;
+}
+
+/// Creates an OpenVG paint object from a paint description and makes it current.
+fn apply_paint(p: &Paint, modes: VGbitfield) {
+    unsafe {
+        let paint = vgCreatePaint();
+        match p {
+            &Paint::Color(ref color) => {
+                vgSetParameteri(
+                    paint,
+                    VGPaintParamType::VG_PAINT_TYPE as i32,
+                    VGPaintType::VG_PAINT_TYPE_COLOR as i32,
+                );
+                vgSetParameterfv(
+                    paint,
+                    VGPaintParamType::VG_PAINT_COLOR as i32,
+                    4,
+                    color.as_ptr(),
+                );
+            }
+            &Paint::LinearGradient(ref coords, ref stops) => {
+                vgSetParameteri(
+                    paint,
+                    VGPaintParamType::VG_PAINT_TYPE as i32,
+                    VGPaintType::VG_PAINT_TYPE_LINEAR_GRADIENT as i32,
+                );
+                vgSetParameterfv(
+                    paint,
+                    VGPaintParamType::VG_PAINT_LINEAR_GRADIENT as i32,
+                    4,
+                    coords.as_ptr(),
+                );
+                set_ramp(paint, stops, (stops.len() / 5) as i32);
+            }
+            &Paint::RadialGradient(ref coords, ref stops) => {
+                vgSetParameteri(
+                    paint,
+                    VGPaintParamType::VG_PAINT_TYPE as i32,
+                    VGPaintType::VG_PAINT_TYPE_RADIAL_GRADIENT as i32,
+                );
+                vgSetParameterfv(
+                    paint,
+                    VGPaintParamType::VG_PAINT_RADIAL_GRADIENT as i32,
+                    5,
+                    coords.as_ptr(),
+                );
+                set_ramp(paint, stops, (stops.len() / 5) as i32);
+            }
+            &Paint::Pattern(ref image, tiling) => {
+                vgSetParameteri(
+                    paint,
+                    VGPaintParamType::VG_PAINT_TYPE as i32,
+                    VGPaintType::VG_PAINT_TYPE_PATTERN as i32,
+                );
+                vgSetParameteri(
+                    paint,
+                    VGPaintParamType::VG_PAINT_PATTERN_TILING_MODE as i32,
+                    tiling,
+                );
+                vgPaintPattern(paint, image.get());
+            }
+        }
+        vgSetPaint(paint, modes);
+        vgDestroyPaint(paint);
     }
 }
 
@@ -227,6 +1592,42 @@ pub fn stroke_width(width: VGfloat) {
     }
 }
 
+/// Sets how the ends of stroked lines are drawn.
+pub fn stroke_cap(cap: VGCapStyle) {
+    unsafe {
+        vgSeti(VGParamType::VG_STROKE_CAP_STYLE, cap as i32);
+    }
+}
+
+/// Sets how stroked lines are joined, and how far a miter join may extend as a multiple
+/// of the stroke width before it is drawn beveled.
+pub fn stroke_join(join: VGJoinStyle, miter_limit: VGfloat) {
+    unsafe {
+        vgSeti(VGParamType::VG_STROKE_JOIN_STYLE, join as i32);
+        vgSetf(VGParamType::VG_STROKE_MITER_LIMIT, miter_limit);
+    }
+}
+
+/// Sets the lengths of alternating dashes and gaps in strokes, starting phase units
+/// into the pattern. An empty pattern draws solid strokes.
+pub fn stroke_dash(pattern: &[VGfloat], phase: VGfloat) {
+    unsafe {
+        vgSetfv(
+            VGParamType::VG_STROKE_DASH_PATTERN,
+            pattern.len() as VGint,
+            pattern.as_ptr(),
+        );
+        vgSetf(VGParamType::VG_STROKE_DASH_PHASE, phase);
+    }
+}
+
+/// Sets the rule that decides which parts of self-intersecting paths are filled.
+pub fn fill_rule(rule: VGFillRule) {
+    unsafe {
+        vgSeti(VGParamType::VG_FILL_RULE, rule as i32);
+    }
+}
+
 //
 // Color functions
 //
@@ -268,6 +1669,14 @@ pub fn fill(r: u32, g: u32, b: u32, a: VGfloat) {
 
 /// Sets color stops for gradients.
 pub fn set_stop(paint: VGPaint, stops: &[VGfloat], n: i32) {
+    set_ramp(paint, stops, n);
+    unsafe {
+        vgSetPaint(paint, VGPaintMode::VG_FILL_PATH as u32);
+    }
+}
+
+/// Sets the color ramp parameters of a gradient paint.
+fn set_ramp(paint: VGPaint, stops: &[VGfloat], n: i32) {
     unsafe {
         let multmode = VGboolean::VG_FALSE;
         let spreadmode = VGColorRampSpreadMode::VG_COLOR_RAMP_SPREAD_REPEAT;
@@ -287,7 +1696,6 @@ pub fn set_stop(paint: VGPaint, stops: &[VGfloat], n: i32) {
             5 * n,
             stops.as_ptr(),
         );
-        vgSetPaint(paint, VGPaintMode::VG_FILL_PATH as u32);
     }
 }
 
@@ -300,23 +1708,9 @@ pub fn fill_linear_gradient(
     stops: &[VGfloat],
     ns: i32,
 ) {
-    unsafe {
-        let lgcoord: [VGfloat; 4] = [x1, y1, x2, y2];
-        let paint = vgCreatePaint();
-        vgSetParameteri(
-            paint,
-            VGPaintParamType::VG_PAINT_TYPE as i32,
-            VGPaintType::VG_PAINT_TYPE_LINEAR_GRADIENT as i32,
-        );
-        vgSetParameterfv(
-            paint,
-            VGPaintParamType::VG_PAINT_LINEAR_GRADIENT as i32,
-            4,
-            lgcoord.as_ptr(),
-        );
-        set_stop(paint, stops, ns);
-        vgDestroyPaint(paint);
-    }
+    let paint = Paint::LinearGradient([x1, y1, x2, y2], stops[..5 * ns as usize].to_vec());
+    apply_paint(&paint, VGPaintMode::VG_FILL_PATH as u32);
+    CONTEXT.lock().unwrap().fill = paint;
 }
 
 /// Fills with a radial gradient.
@@ -329,38 +1723,205 @@ pub fn fill_radial_gradient(
     stops: &[VGfloat],
     ns: i32,
 ) {
-    unsafe {
-        let radialcoord: [VGfloat; 5] = [cx, cy, fx, fy, radius];
-        let paint = vgCreatePaint();
-        vgSetParameteri(
-            paint,
-            VGPaintParamType::VG_PAINT_TYPE as i32,
-            VGPaintType::VG_PAINT_TYPE_RADIAL_GRADIENT as i32,
-        );
-        vgSetParameterfv(
-            paint,
-            VGPaintParamType::VG_PAINT_RADIAL_GRADIENT as i32,
-            5,
-            radialcoord.as_ptr(),
-        );
-        set_stop(paint, stops, ns);
-        vgDestroyPaint(paint);
-    }
+    let paint = Paint::RadialGradient([cx, cy, fx, fy, radius], stops[..5 * ns as usize].to_vec());
+    apply_paint(&paint, VGPaintMode::VG_FILL_PATH as u32);
+    CONTEXT.lock().unwrap().fill = paint;
+}
+
+/// Fills with an image repeated across the plane.
+///
+/// The pattern is placed by the FillPaint transformation, with the image's bottom left
+/// corner at the origin. The fill, and any state saved while it is set, keep the
+/// image's pixels alive after the image itself is dropped.
+pub fn fill_pattern(img: &Image, tiling: VGTilingMode) {
+    let paint = Paint::Pattern(img.shared_handle(), tiling as VGint);
+    apply_paint(&paint, VGPaintMode::VG_FILL_PATH as u32);
+    CONTEXT.lock().unwrap().fill = paint;
 }
 
+//
+// Clipping
+//
+
 /// Limits the drawing area to specified rectangle.
+///
+/// Rectangles are given in window coordinates (unaffected by transformations). If
+/// scissoring is already on, the new rectangle is intersected with the current ones.
 pub fn clip_rect(x: VGint, y: VGint, w: VGint, h: VGint) {
+    clip_rects(&[[x, y, w, h]]);
+}
+
+/// Limits the drawing area to the union of the specified [x, y, w, h] rectangles.
+///
+/// If scissoring is already on, the union is intersected with the current rectangles.
+pub fn clip_rects(rects: &[[VGint; 4]]) {
     unsafe {
+        let mut coords: Vec<VGint> = Vec::new();
+        if vgGeti(VGParamType::VG_SCISSORING) == VGboolean::VG_TRUE as VGint {
+            let n = vgGetVectorSize(VGParamType::VG_SCISSOR_RECTS);
+            let mut current = vec![0 as VGint; n as usize];
+            vgGetiv(VGParamType::VG_SCISSOR_RECTS, n, current.as_mut_ptr());
+            for c in current.chunks(4) {
+                for r in rects {
+                    let x0 = c[0].max(r[0]);
+                    let y0 = c[1].max(r[1]);
+                    let x1 = (c[0] + c[2]).min(r[0] + r[2]);
+                    let y1 = (c[1] + c[3]).min(r[1] + r[3]);
+                    if x1 > x0 && y1 > y0 {
+                        coords.extend_from_slice(&[x0, y0, x1 - x0, y1 - y0]);
+                    }
+                }
+            }
+            if coords.is_empty() {
+                // Nothing is left visible, but an empty list would disable clipping.
+                coords.extend_from_slice(&[0, 0, 0, 0]);
+            }
+        } else {
+            for r in rects {
+                coords.extend_from_slice(r);
+            }
+        }
+        let max = 4 * vgGeti(VGParamType::VG_MAX_SCISSOR_RECTS) as usize;
+        coords.truncate(max);
+        vgSetiv(
+            VGParamType::VG_SCISSOR_RECTS,
+            coords.len() as VGint,
+            coords.as_ptr(),
+        );
         vgSeti(VGParamType::VG_SCISSORING, VGboolean::VG_TRUE as i32);
-        let coords: [VGint; 4] = [x, y, w, h];
-        vgSetiv(VGParamType::VG_SCISSOR_RECTS, 4, coords.as_ptr());
     }
 }
 
-/// Stops limiting drawing area to specified rectangle.
+/// Stops limiting the drawing area, turning off both scissoring and masking.
+///
+/// Use `save()` and `restore()` to undo a single clip while keeping enclosing ones.
 pub fn clip_end() {
     unsafe {
         vgSeti(VGParamType::VG_SCISSORING, VGboolean::VG_FALSE as i32);
+        vgSeti(VGParamType::VG_MASKING, VGboolean::VG_FALSE as i32);
+    }
+}
+
+/// Turns on masking, starting from a fully opaque mask if masking was off.
+fn begin_mask() {
+    unsafe {
+        if vgGeti(VGParamType::VG_MASKING) == VGboolean::VG_FALSE as VGint {
+            let context = CONTEXT.lock().unwrap();
+            vgMask(
+                VG_INVALID_HANDLE,
+                VGMaskOperation::VG_FILL_MASK,
+                0,
+                0,
+                context.width as VGint,
+                context.height as VGint,
+            );
+            vgSeti(VGParamType::VG_MASKING, VGboolean::VG_TRUE as i32);
+        }
+    }
+}
+
+/// Intersects the current clip with the filled area of a path and destroys the path.
+fn clip_to_path(path: VGPath) {
+    begin_mask();
+    unsafe {
+        vgRenderToMask(
+            path,
+            VGPaintMode::VG_FILL_PATH as u32,
+            VGMaskOperation::VG_INTERSECT_MASK,
+        );
+        vgDestroyPath(path);
+    }
+}
+
+/// Limits the drawing area to a rectangle in the current coordinate system.
+pub fn clip_path_rect(x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+    let path = new_path();
+    unsafe {
+        vguRect(path, x, y, w, h);
+    }
+    clip_to_path(path);
+}
+
+/// Limits the drawing area to a rounded rectangle in the current coordinate system.
+pub fn clip_round_rect(x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat, rw: VGfloat, rh: VGfloat) {
+    let path = new_path();
+    unsafe {
+        vguRoundRect(path, x, y, w, h, rw, rh);
+    }
+    clip_to_path(path);
+}
+
+/// Limits the drawing area to an ellipse in the current coordinate system.
+pub fn clip_ellipse(x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+    let path = new_path();
+    unsafe {
+        vguEllipse(path, x, y, w, h);
+    }
+    clip_to_path(path);
+}
+
+/// Limits the drawing area to a circle in the current coordinate system.
+pub fn clip_circle(x: VGfloat, y: VGfloat, r: VGfloat) {
+    clip_ellipse(x, y, r, r);
+}
+
+/// Limits the drawing area to a polygon with vertices in x, y arrays.
+pub fn clip_polygon(x: &[VGfloat], y: &[VGfloat], n: VGint) {
+    let mut points = vec![0.0f32; (n as usize) * 2];
+    interleave(x, y, n, points.as_mut_slice());
+    let path = new_path();
+    unsafe {
+        vguPolygon(path, points.as_ptr(), n, VGboolean::VG_TRUE);
+    }
+    clip_to_path(path);
+}
+
+/// Limits the drawing area to a path made from segments and coordinates.
+pub fn clip_curve(segments: &[VGubyte], coords: &[VGfloat]) {
+    let path = new_path();
+    unsafe {
+        vgAppendPathData(
+            path,
+            segments.len() as VGint,
+            segments.as_ptr(),
+            coords.as_ptr() as *const i8,
+        );
+    }
+    clip_to_path(path);
+}
+
+/// Scales the opacity of everything drawn in a window rectangle by alpha.
+///
+/// This is a soft clip: it is intersected with the current mask, so areas can be
+/// faded out as well as hidden.
+pub fn mask_rect(x: VGint, y: VGint, w: VGint, h: VGint, alpha: VGfloat) {
+    if w <= 0 || h <= 0 {
+        return;
+    }
+    begin_mask();
+    unsafe {
+        let layer = vgCreateMaskLayer(w, h);
+        vgFillMaskLayer(layer, 0, 0, w, h, alpha);
+        vgMask(layer, VGMaskOperation::VG_INTERSECT_MASK, x, y, w, h);
+        vgDestroyMaskLayer(layer);
+    }
+}
+
+/// Scales the opacity of everything drawn by the alpha channel of an image placed
+/// with its lower left corner at window coordinates x, y.
+///
+/// Outside the image, the mask is left unchanged.
+pub fn mask_image(image: &Image, x: VGint, y: VGint) {
+    begin_mask();
+    unsafe {
+        vgMask(
+            image.handle(),
+            VGMaskOperation::VG_INTERSECT_MASK,
+            x,
+            y,
+            image.width() as VGint,
+            image.height() as VGint,
+        );
     }
 }
 
@@ -387,7 +1948,12 @@ fn new_path() -> VGPath {
 pub fn make_curve(segments: &[VGubyte], coords: &[VGfloat], flags: VGbitfield) {
     let path = new_path();
     unsafe {
-        vgAppendPathData(path, 2, segments.as_ptr(), coords.as_ptr() as *const i8);
+        vgAppendPathData(
+            path,
+            segments.len() as VGint,
+            segments.as_ptr(),
+            coords.as_ptr() as *const i8,
+        );
         vgDrawPath(path, flags);
         vgDestroyPath(path);
     }
@@ -12,11 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use libc::{c_int, c_void};
+use openvg::VGImage;
+
+use std::ptr;
+
+type SurfaceHandle = *mut c_void;
+
+/// An EGL surface that renders into an OpenVG image, destroyed when dropped.
+pub struct Surface {
+    handle: SurfaceHandle,
+}
+
+impl Drop for Surface {
+    fn drop(&mut self) {
+        unsafe {
+            egl_destroy_surface(self.handle);
+        }
+    }
+}
+
 extern "C" {
     fn bcm_host_init();
     fn egl_init(w: &mut u32, h: &mut u32);
     fn egl_finish();
     fn egl_swap_buffers();
+    fn egl_create_image_surface(image: VGImage) -> SurfaceHandle;
+    fn egl_make_current(surface: SurfaceHandle) -> c_int;
+    fn egl_destroy_surface(surface: SurfaceHandle);
 }
 
 pub fn init(w: &mut u32, h: &mut u32) {
@@ -37,3 +60,22 @@ pub fn finish() {
         egl_finish();
     }
 }
+
+/// Creates a surface that draws into an image, or None if EGL can't render into it.
+pub fn create_image_surface(image: VGImage) -> Option<Surface> {
+    let handle = unsafe { egl_create_image_surface(image) };
+    if handle.is_null() {
+        None
+    } else {
+        Some(Surface { handle: handle })
+    }
+}
+
+/// Directs drawing to a surface, or to the window if surface is None.
+pub fn make_current(surface: Option<&Surface>) -> bool {
+    let handle = match surface {
+        Some(surface) => surface.handle,
+        None => ptr::null_mut(),
+    };
+    unsafe { egl_make_current(handle) != 0 }
+}
@@ -17,9 +17,13 @@
 use std::time::{Duration, Instant};
 
 /// Represents applicatoin events.
+#[derive(Clone, Debug)]
 pub enum Event {
     Touch(Touch, Duration),
     Key(Key, Duration),
+    /// Text entered without a key code of its own, such as an accented letter chosen on
+    /// the on-screen keyboard.
+    Text(String, Duration),
     Tick(Duration),
 }
 
@@ -39,6 +43,10 @@ impl Event {
     pub fn new_key(k: Key) -> Event {
         Event::Key(k, elapsed())
     }
+    /// Creates a new Text event.
+    pub fn new_text(s: &str) -> Event {
+        Event::Text(s.to_string(), elapsed())
+    }
     /// Creates a new Tick event.
     pub fn new_tick() -> Event {
         Event::Tick(elapsed())
@@ -0,0 +1,406 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Image filters.
+//!
+//! Filters are applied to an `Image` with OpenVG or to a `Bitmap` on the CPU.
+//! Both produce the same results, with pixels beyond the edges repeating the
+//! nearest edge pixel.
+
+use image::{Bitmap, Error, Format, Image};
+use openvg::*;
+
+/// Represents a convolution kernel.
+#[derive(Clone, Debug)]
+pub struct Kernel {
+    /// The number of columns.
+    pub width: usize,
+    /// The number of rows.
+    pub height: usize,
+    /// Weights stored row by row, starting with the top row.
+    pub values: Vec<VGfloat>,
+    /// A value from 0 to 1 added to each weighted sum.
+    pub bias: VGfloat,
+}
+
+impl Kernel {
+    /// Creates a new Kernel.
+    pub fn new(width: usize, height: usize, values: &[VGfloat]) -> Kernel {
+        Kernel {
+            width: width,
+            height: height,
+            values: values[..width * height].to_vec(),
+            bias: 0.0,
+        }
+    }
+}
+
+/// Describes an image filter.
+#[derive(Clone, Debug)]
+pub enum Filter {
+    /// Blurs with the given standard deviations in x and y; a deviation of 0 leaves
+    /// that direction unblurred.
+    GaussianBlur(VGfloat, VGfloat),
+    /// Replaces each pixel with a weighted sum of its neighbors.
+    Convolve(Kernel),
+    /// Transforms colors with a 4x5 matrix stored column by column: each output
+    /// channel c is m[c] * r + m[4 + c] * g + m[8 + c] * b + m[12 + c] * a + m[16 + c],
+    /// with channels from 0 to 1.
+    ColorMatrix([VGfloat; 20]),
+    /// Maps each red, green, blue and alpha value through a table of 256 entries.
+    ///
+    /// Filters with tables of any other length can't be applied.
+    Lookup {
+        red: Vec<u8>,
+        green: Vec<u8>,
+        blue: Vec<u8>,
+        alpha: Vec<u8>,
+    },
+}
+
+impl Filter {
+    /// Creates a gaussian blur with the same standard deviation in x and y.
+    pub fn blur(std_deviation: VGfloat) -> Filter {
+        Filter::GaussianBlur(std_deviation, std_deviation)
+    }
+
+    /// Creates a sharpening filter; amount 0 leaves the image unchanged.
+    pub fn sharpen(amount: VGfloat) -> Filter {
+        let a = amount;
+        Filter::Convolve(Kernel::new(
+            3,
+            3,
+            &[0.0, -a, 0.0, -a, 1.0 + 4.0 * a, -a, 0.0, -a, 0.0],
+        ))
+    }
+
+    /// Creates a filter that removes color.
+    pub fn grayscale() -> Filter {
+        Filter::saturation(0.0)
+    }
+
+    /// Creates a filter that scales color saturation; 0 is gray and 1 is unchanged.
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn saturation(s: VGfloat) -> Filter {
+        let (r, g, b) = (0.299 * (1.0 - s), 0.587 * (1.0 - s), 0.114 * (1.0 - s));
+        Filter::ColorMatrix([
+            r + s, r, r, 0.0,
+            g, g + s, g, 0.0,
+            b, b, b + s, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+            0.0, 0.0, 0.0, 0.0,
+        ])
+    }
+
+    /// Creates a filter that tints colors brown like an old photograph.
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn sepia() -> Filter {
+        Filter::ColorMatrix([
+            0.393, 0.349, 0.272, 0.0,
+            0.769, 0.686, 0.534, 0.0,
+            0.189, 0.168, 0.131, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+            0.0, 0.0, 0.0, 0.0,
+        ])
+    }
+
+    /// Creates a filter that adds b (from -1 to 1) to each color channel.
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn brightness(b: VGfloat) -> Filter {
+        Filter::ColorMatrix([
+            1.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+            b, b, b, 0.0,
+        ])
+    }
+
+    /// Creates a filter that scales contrast around middle gray; 1 is unchanged.
+    #[cfg_attr(rustfmt, rustfmt_skip)]
+    pub fn contrast(c: VGfloat) -> Filter {
+        let o = 0.5 * (1.0 - c);
+        Filter::ColorMatrix([
+            c, 0.0, 0.0, 0.0,
+            0.0, c, 0.0, 0.0,
+            0.0, 0.0, c, 0.0,
+            0.0, 0.0, 0.0, 1.0,
+            o, o, o, 0.0,
+        ])
+    }
+
+    /// Creates a filter that inverts colors.
+    pub fn invert() -> Filter {
+        Filter::lookup(|v| 255 - v, |v| 255 - v, |v| 255 - v, |v| v)
+    }
+
+    /// Creates a filter that applies a gamma curve to colors.
+    pub fn gamma(g: VGfloat) -> Filter {
+        let f = |v: u8| ((v as VGfloat / 255.0).powf(g) * 255.0).round() as u8;
+        Filter::lookup(f, f, f, |v| v)
+    }
+
+    /// Creates a lookup filter from functions that map each channel.
+    pub fn lookup<R, G, B, A>(r: R, g: G, b: B, a: A) -> Filter
+    where
+        R: Fn(u8) -> u8,
+        G: Fn(u8) -> u8,
+        B: Fn(u8) -> u8,
+        A: Fn(u8) -> u8,
+    {
+        Filter::Lookup {
+            red: (0..256).map(|v| r(v as u8)).collect(),
+            green: (0..256).map(|v| g(v as u8)).collect(),
+            blue: (0..256).map(|v| b(v as u8)).collect(),
+            alpha: (0..256).map(|v| a(v as u8)).collect(),
+        }
+    }
+
+    /// Applies the filter to an image with OpenVG, returning a new image.
+    pub fn apply(&self, src: &Image) -> Result<Image, Error> {
+        self.check()?;
+        let format = match src.format() {
+            Format::Rgbx => Format::Rgba,
+            format => format,
+        };
+        let dst = Image::new(src.width(), src.height(), format)?;
+        unsafe {
+            match *self {
+                Filter::GaussianBlur(x, y) if x <= 0.0 && y <= 0.0 => {
+                    vgCopyImage(
+                        dst.handle(),
+                        0,
+                        0,
+                        src.handle(),
+                        0,
+                        0,
+                        src.width() as VGint,
+                        src.height() as VGint,
+                        VGboolean::VG_FALSE,
+                    );
+                }
+                Filter::GaussianBlur(x, y) => {
+                    // OpenVG rejects a deviation of 0, so blur that direction too
+                    // little to change any pixel.
+                    let max = vgGetf(VGParamType::VG_MAX_GAUSSIAN_STD_DEVIATION);
+                    vgGaussianBlur(
+                        dst.handle(),
+                        src.handle(),
+                        x.max(MIN_STD_DEVIATION).min(max),
+                        y.max(MIN_STD_DEVIATION).min(max),
+                        VGTilingMode::VG_TILE_PAD,
+                    );
+                }
+                Filter::Convolve(ref kernel) => {
+                    let (values, scale) = vg_kernel(kernel);
+                    vgConvolve(
+                        dst.handle(),
+                        src.handle(),
+                        kernel.width as VGint,
+                        kernel.height as VGint,
+                        (kernel.width / 2) as VGint,
+                        (kernel.height - 1 - kernel.height / 2) as VGint,
+                        values.as_ptr(),
+                        scale,
+                        kernel.bias,
+                        VGTilingMode::VG_TILE_PAD,
+                    );
+                }
+                Filter::ColorMatrix(ref m) => {
+                    vgColorMatrix(dst.handle(), src.handle(), m.as_ptr());
+                }
+                Filter::Lookup {
+                    ref red,
+                    ref green,
+                    ref blue,
+                    ref alpha,
+                } => {
+                    vgLookup(
+                        dst.handle(),
+                        src.handle(),
+                        red.as_ptr(),
+                        green.as_ptr(),
+                        blue.as_ptr(),
+                        alpha.as_ptr(),
+                        VGboolean::VG_FALSE,
+                        VGboolean::VG_FALSE,
+                    );
+                }
+            }
+        }
+        Ok(dst)
+    }
+
+    /// Applies the filter to a bitmap on the CPU, returning a new bitmap.
+    pub fn apply_bitmap(&self, src: &Bitmap) -> Result<Bitmap, Error> {
+        self.check()?;
+        Ok(match *self {
+            Filter::GaussianBlur(x, y) => {
+                let horizontal = gaussian_kernel(x);
+                let vertical = gaussian_kernel(y);
+                let tmp = convolve(src, &horizontal, horizontal.len(), 1, 0.0);
+                convolve(&tmp, &vertical, 1, vertical.len(), 0.0)
+            }
+            Filter::Convolve(ref kernel) => convolve(
+                src,
+                &kernel.values,
+                kernel.width,
+                kernel.height,
+                kernel.bias,
+            ),
+            Filter::ColorMatrix(ref m) => {
+                let mut dst = src.clone();
+                for p in dst.pixels.chunks_mut(4) {
+                    let v = [
+                        p[0] as VGfloat / 255.0,
+                        p[1] as VGfloat / 255.0,
+                        p[2] as VGfloat / 255.0,
+                        p[3] as VGfloat / 255.0,
+                    ];
+                    for c in 0..4 {
+                        let sum = m[c] * v[0]
+                            + m[4 + c] * v[1]
+                            + m[8 + c] * v[2]
+                            + m[12 + c] * v[3]
+                            + m[16 + c];
+                        p[c] = to_byte(sum);
+                    }
+                }
+                dst
+            }
+            Filter::Lookup {
+                ref red,
+                ref green,
+                ref blue,
+                ref alpha,
+            } => {
+                let mut dst = src.clone();
+                for p in dst.pixels.chunks_mut(4) {
+                    p[0] = red[p[0] as usize];
+                    p[1] = green[p[1] as usize];
+                    p[2] = blue[p[2] as usize];
+                    p[3] = alpha[p[3] as usize];
+                }
+                dst
+            }
+        })
+    }
+
+    /// Returns an error if the kernel or lookup tables are too short to be applied.
+    fn check(&self) -> Result<(), Error> {
+        let valid = match *self {
+            Filter::GaussianBlur(..) | Filter::ColorMatrix(_) => true,
+            Filter::Convolve(ref kernel) => {
+                kernel.width > 0
+                    && kernel.height > 0
+                    && kernel.values.len() == kernel.width * kernel.height
+            }
+            Filter::Lookup {
+                ref red,
+                ref green,
+                ref blue,
+                ref alpha,
+            } => [red, green, blue, alpha].iter().all(|t| t.len() == 256),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidFilter)
+        }
+    }
+}
+
+/// The smallest deviation passed to vgGaussianBlur, which spreads less than 1/255 of
+/// a pixel to its neighbors.
+const MIN_STD_DEVIATION: VGfloat = 0.1;
+
+/// Converts a channel value from 0 to 1 to a byte.
+fn to_byte(v: VGfloat) -> u8 {
+    (v.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+/// Converts a kernel to the fixed-point, column-major, flipped layout used by vgConvolve.
+fn vg_kernel(kernel: &Kernel) -> (Vec<VGshort>, VGfloat) {
+    let largest = kernel
+        .values
+        .iter()
+        .fold(0.0 as VGfloat, |m, v| m.max(v.abs()));
+    let scale = if largest > 0.0 {
+        largest / VG_MAXSHORT as VGfloat
+    } else {
+        1.0
+    };
+    let (w, h) = (kernel.width, kernel.height);
+    let mut values = vec![0 as VGshort; w * h];
+    for r in 0..h {
+        for c in 0..w {
+            values[(w - 1 - c) * h + r] = (kernel.values[r * w + c] / scale).round() as VGshort;
+        }
+    }
+    (values, scale)
+}
+
+/// Returns normalized one-dimensional gaussian weights extending three deviations out.
+fn gaussian_kernel(std_deviation: VGfloat) -> Vec<VGfloat> {
+    if std_deviation <= 0.0 {
+        return vec![1.0];
+    }
+    let radius = (3.0 * std_deviation).ceil() as i32;
+    let mut weights: Vec<VGfloat> = (-radius..radius + 1)
+        .map(|i| (-(i * i) as VGfloat / (2.0 * std_deviation * std_deviation)).exp())
+        .collect();
+    let sum: VGfloat = weights.iter().sum();
+    for w in weights.iter_mut() {
+        *w /= sum;
+    }
+    weights
+}
+
+/// Computes weighted sums of neighboring pixels, repeating edge pixels as needed.
+fn convolve(src: &Bitmap, values: &[VGfloat], w: usize, h: usize, bias: VGfloat) -> Bitmap {
+    let mut dst = Bitmap::new(src.width, src.height);
+    let (cx, cy) = ((w / 2) as i64, (h / 2) as i64);
+    let max_x = src.width as i64 - 1;
+    let max_y = src.height as i64 - 1;
+    for y in 0..src.height as i64 {
+        for x in 0..src.width as i64 {
+            let mut sum = [0.0 as VGfloat; 4];
+            for r in 0..h {
+                let sy = (y + r as i64 - cy).max(0).min(max_y);
+                for c in 0..w {
+                    let k = values[r * w + c];
+                    if k == 0.0 {
+                        continue;
+                    }
+                    let sx = (x + c as i64 - cx).max(0).min(max_x);
+                    let p = src.pixel(sx as u32, sy as u32);
+                    for i in 0..4 {
+                        sum[i] += k * p[i] as VGfloat / 255.0;
+                    }
+                }
+            }
+            dst.set_pixel(
+                x as u32,
+                y as u32,
+                [
+                    to_byte(sum[0] + bias),
+                    to_byte(sum[1] + bias),
+                    to_byte(sum[2] + bias),
+                    to_byte(sum[3] + bias),
+                ],
+            );
+        }
+    }
+    dst
+}
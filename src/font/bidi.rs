@@ -0,0 +1,129 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reordering of bidirectional text with the Unicode Bidirectional Algorithm.
+
+use unicode_bidi::{BidiInfo, Level};
+use unicode_normalization::char::is_combining_mark;
+
+use std::ops::Range;
+
+/// Returns true if a paragraph of text is right to left, as decided by its first strong
+/// character.
+pub fn is_rtl(s: &str) -> bool {
+    match BidiInfo::new(s, None).paragraphs.first() {
+        Some(paragraph) => paragraph.level.is_rtl(),
+        None => false,
+    }
+}
+
+/// Reorders a line of text from logical order into the left-to-right order in which its
+/// characters are drawn.
+///
+/// Right-to-left runs are reversed with combining marks kept after their base
+/// characters, brackets in them are mirrored, and bidi formatting characters are
+/// removed.
+pub fn visual_order(s: &str) -> String {
+    let mut visual = String::with_capacity(s.len());
+    for (run, rtl) in visual_runs(s, None) {
+        visual.push_str(&run_text(&s[run], rtl));
+    }
+    visual
+}
+
+/// Splits a line of text into the runs it is drawn in, from left to right, each with
+/// the byte range it covers and whether it is drawn right to left.
+///
+/// The direction of each paragraph comes from its first strong character unless rtl
+/// gives it.
+pub(crate) fn visual_runs(s: &str, rtl: Option<bool>) -> Vec<(Range<usize>, bool)> {
+    let level = rtl.map(|rtl| if rtl { Level::rtl() } else { Level::ltr() });
+    let info = BidiInfo::new(s, level);
+    if !info.has_rtl() && !rtl.unwrap_or(false) {
+        return vec![(0..s.len(), false)];
+    }
+    let mut runs = Vec::new();
+    for paragraph in info.paragraphs.iter() {
+        let (levels, visual) = info.visual_runs(paragraph, paragraph.range.clone());
+        for run in visual {
+            let rtl = levels[run.start].is_rtl();
+            runs.push((run, rtl));
+        }
+    }
+    runs
+}
+
+/// Returns the characters of a run in the order they are drawn.
+pub(crate) fn run_text(s: &str, rtl: bool) -> String {
+    if rtl {
+        let mut visual = String::with_capacity(s.len());
+        push_reversed(&mut visual, s);
+        visual
+    } else {
+        s.chars().filter(|&c| !is_bidi_control(c)).collect()
+    }
+}
+
+/// Appends a right-to-left run in reverse, one base character and its marks at a time.
+fn push_reversed(visual: &mut String, s: &str) {
+    let mut clusters = Vec::new();
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if i > start && !is_combining_mark(c) {
+            clusters.push(&s[start..i]);
+            start = i;
+        }
+    }
+    if start < s.len() {
+        clusters.push(&s[start..]);
+    }
+    for cluster in clusters.iter().rev() {
+        for c in cluster.chars().filter(|&c| !is_bidi_control(c)) {
+            visual.push(mirror(c));
+        }
+    }
+}
+
+/// Returns true for the invisible characters that mark or override direction.
+fn is_bidi_control(c: char) -> bool {
+    match c {
+        '\u{061C}'
+        | '\u{200E}'
+        | '\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2066}'..='\u{2069}' => true,
+        _ => false,
+    }
+}
+
+/// Returns the mirror image of paired punctuation drawn right to left.
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '<' => '>',
+        '>' => '<',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '\u{AB}' => '\u{BB}',
+        '\u{BB}' => '\u{AB}',
+        '\u{2039}' => '\u{203A}',
+        '\u{203A}' => '\u{2039}',
+        '\u{2264}' => '\u{2265}',
+        '\u{2265}' => '\u{2264}',
+        _ => c,
+    }
+}
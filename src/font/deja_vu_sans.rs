@@ -3091,3 +3091,377 @@ pub static CHARACTER_MAP: [i16; 500] = [
     434, 435, 436, 437, 438, 439, 440, 441, 442, 443, 444, 445, 446, 447, 448, 449, 450, 451, 452,
     453, 454, 455, 456, 457, 458, 459, 460, 461, 462, 463, 464, 465, 466, 467,
 ];
+
+/* kerning pairs from the GPOS table of DejaVu Sans 2.37: left glyph, right glyph, adjustment */
+
+pub static KERNING_PAIRS: [i32; 2122 * 3] = [
+    13, 33, -1920, 13, 34, -3115, 13, 39, 3200, 13, 42, 4864, 13, 47, 2432, 13, 49, 3200, 13, 52,
+    -8021, 13, 54, -5120, 13, 55, -3541, 13, 56, -4352, 13, 57, -10368, 13, 79, 1621, 13, 86,
+    -2347, 13, 89, -1536, 13, 160, -1920, 13, 161, -1920, 13, 162, -1920, 13, 163, -1920, 13, 164,
+    -1920, 13, 178, 2432, 13, 179, 2432, 13, 180, 2432, 13, 181, 2432, 13, 182, 2432, 13, 189,
+    -10368, 13, 210, 1621, 13, 211, 1621, 13, 212, 1621, 13, 213, 1621, 13, 214, 1621, 13, 221,
+    -1536, 13, 223, -1536, 13, 224, -1920, 13, 226, -1920, 13, 228, -1920, 13, 254, 3200, 13, 324,
+    -8021, 13, 344, -10368, 33, 13, -1920, 33, 14, -1536, 33, 26, -1536, 33, 33, 2432, 33, 35,
+    -1536, 33, 39, -1536, 33, 47, -1536, 33, 49, -1536, 33, 52, -6784, 33, 54, -5589, 33, 55,
+    -4779, 33, 57, -6784, 33, 67, -1536, 33, 68, -1536, 33, 69, -1536, 33, 70, -3115, 33, 79,
+    -1536, 33, 81, -1536, 33, 84, -1536, 33, 86, -5120, 33, 87, -3541, 33, 89, -5931, 33, 139,
+    -3115, 33, 160, 2432, 33, 161, 2432, 33, 162, 2432, 33, 163, 2432, 33, 164, 2432, 33, 167,
+    -1536, 33, 178, -1536, 33, 179, -1536, 33, 180, -1536, 33, 181, -1536, 33, 182, -1536, 33, 189,
+    -6784, 33, 199, -1536, 33, 200, -1536, 33, 201, -1536, 33, 202, -1536, 33, 203, -1536, 33, 210,
+    -1536, 33, 211, -1536, 33, 212, -1536, 33, 213, -1536, 33, 214, -1536, 33, 221, -5931, 33, 223,
+    -5931, 33, 224, 2432, 33, 226, 2432, 33, 228, 2432, 33, 230, -1536, 33, 231, -1536, 33, 232,
+    -1536, 33, 233, -1536, 33, 234, -1536, 33, 235, -1536, 33, 236, -1536, 33, 237, -1536, 33, 239,
+    -1536, 33, 241, -1536, 33, 243, -1536, 33, 245, -1536, 33, 247, -1536, 33, 249, -1536, 33, 251,
+    -1536, 33, 252, -1536, 33, 254, -1536, 33, 256, -1536, 33, 300, -1536, 33, 301, -1536, 33, 302,
+    -1536, 33, 303, -1536, 33, 304, -1536, 33, 305, -1536, 33, 322, -6784, 33, 323, -1536, 33, 324,
+    -6784, 33, 325, -1536, 33, 340, -4779, 33, 341, -3541, 33, 342, -6784, 33, 343, -5931, 33, 344,
+    -6784, 33, 422, -1536, 33, 458, -1536, 33, 459, -1536, 33, 460, -1536, 33, 461, -1536, 34, 35,
+    -1536, 34, 39, -1536, 34, 47, -1536, 34, 51, -1536, 34, 54, -2688, 34, 55, -3115, 34, 57,
+    -4779, 34, 139, -2688, 34, 155, -1536, 34, 167, -1536, 34, 178, -1536, 34, 179, -1536, 34, 180,
+    -1536, 34, 181, -1536, 34, 182, -1536, 34, 189, -4779, 34, 230, -1536, 34, 232, -1536, 34, 234,
+    -1536, 34, 236, -1536, 34, 252, -1536, 34, 254, -1536, 34, 256, -1536, 34, 258, -1536, 34, 300,
+    -1536, 34, 302, -1536, 34, 304, -1536, 34, 314, -1536, 34, 316, -1536, 34, 318, -1536, 34, 320,
+    -1536, 34, 340, -3115, 34, 342, -4779, 34, 344, -4779, 34, 458, -1536, 34, 460, -1536, 35, 57,
+    -1536, 35, 139, -1536, 35, 155, -1536, 35, 189, -1536, 35, 342, -1536, 35, 344, -1536, 36, 33,
+    -1536, 36, 54, -1536, 36, 57, -4779, 36, 139, -1536, 36, 155, -1536, 36, 160, -1536, 36, 161,
+    -1536, 36, 162, -1536, 36, 163, -1536, 36, 164, -1536, 36, 189, -4779, 36, 224, -1536, 36, 226,
+    -1536, 36, 228, -1536, 36, 342, -4779, 36, 344, -4779, 38, 14, -14037, 38, 26, -6784, 38, 33,
+    -8021, 38, 51, -1536, 38, 52, -1536, 38, 65, -8021, 38, 69, -4779, 38, 73, -6357, 38, 79,
+    -3115, 38, 82, -6357, 38, 85, -4779, 38, 89, -8021, 38, 160, -8021, 38, 161, -8021, 38, 162,
+    -8021, 38, 163, -8021, 38, 164, -8021, 38, 192, -8021, 38, 193, -8021, 38, 194, -8021, 38, 195,
+    -8021, 38, 196, -8021, 38, 197, -8021, 38, 200, -4779, 38, 201, -4779, 38, 202, -4779, 38, 203,
+    -4779, 38, 210, -3115, 38, 211, -3115, 38, 212, -3115, 38, 213, -3115, 38, 214, -3115, 38, 217,
+    -4779, 38, 218, -4779, 38, 219, -4779, 38, 220, -4779, 38, 221, -8021, 38, 223, -8021, 38, 224,
+    -8021, 38, 225, -8021, 38, 226, -8021, 38, 227, -8021, 38, 228, -8021, 38, 229, -8021, 38, 243,
+    -4779, 38, 245, -4779, 38, 247, -4779, 38, 249, -4779, 38, 251, -4779, 38, 271, -6357, 38, 275,
+    -6357, 38, 301, -3115, 38, 303, -3115, 38, 305, -3115, 38, 309, -6357, 38, 311, -6357, 38, 313,
+    -6357, 38, 314, -1536, 38, 316, -1536, 38, 318, -1536, 38, 320, -1536, 38, 322, -1536, 38, 324,
+    -1536, 38, 326, -1536, 38, 329, -4779, 38, 331, -4779, 38, 333, -4779, 38, 335, -4779, 38, 337,
+    -4779, 38, 339, -4779, 38, 343, -8021, 38, 459, -3115, 38, 461, -3115, 39, 52, -3115, 39, 57,
+    -4352, 39, 139, -1536, 39, 155, -1536, 39, 189, -4352, 39, 324, -3115, 39, 344, -4352, 40, 14,
+    -1536, 42, 13, -3115, 42, 33, -1536, 42, 139, -1536, 42, 155, -1536, 42, 160, -1536, 42, 161,
+    -1536, 42, 162, -1536, 42, 163, -1536, 42, 164, -1536, 43, 13, -9173, 43, 33, -1536, 43, 35,
+    -4779, 43, 47, -4779, 43, 52, -6784, 43, 53, -2347, 43, 55, -3115, 43, 57, -3115, 43, 65,
+    -1536, 43, 69, -4352, 43, 79, -4352, 43, 85, -4352, 43, 89, -6357, 43, 139, -5589, 43, 160,
+    -1536, 43, 161, -1536, 43, 162, -1536, 43, 163, -1536, 43, 164, -1536, 43, 167, -4779, 43, 178,
+    -4779, 43, 179, -4779, 43, 180, -4779, 43, 181, -4779, 43, 182, -4779, 43, 185, -2347, 43, 186,
+    -2347, 43, 187, -2347, 43, 188, -2347, 43, 189, -3115, 43, 192, -1536, 43, 193, -1536, 43, 194,
+    -1536, 43, 195, -1536, 43, 196, -1536, 43, 197, -1536, 43, 200, -4352, 43, 201, -4352, 43, 202,
+    -4352, 43, 203, -4352, 43, 210, -4352, 43, 211, -4352, 43, 212, -4352, 43, 213, -4352, 43, 214,
+    -4352, 43, 217, -4352, 43, 218, -4352, 43, 219, -4352, 43, 220, -4352, 43, 221, -6357, 43, 223,
+    -6357, 43, 230, -4779, 43, 236, -4779, 43, 251, -4352, 43, 324, -6784, 43, 334, -2347, 43, 335,
+    -4352, 43, 344, -3115, 44, 13, -1536, 44, 33, 2005, 44, 47, -3115, 44, 52, -12032, 44, 53,
+    -4352, 44, 54, -9600, 44, 55, -8021, 44, 57, -11605, 44, 69, -1536, 44, 79, -1536, 44, 85,
+    -1536, 44, 89, -8021, 44, 160, 2005, 44, 161, 2005, 44, 162, 2005, 44, 163, 2005, 44, 164,
+    2005, 44, 178, -3115, 44, 179, -3115, 44, 180, -3115, 44, 181, -3115, 44, 182, -3115, 44, 185,
+    -4352, 44, 186, -4352, 44, 187, -4352, 44, 188, -4352, 44, 189, -11605, 44, 200, -1536, 44,
+    201, -1536, 44, 202, -1536, 44, 203, -1536, 44, 210, -1536, 44, 211, -1536, 44, 212, -1536, 44,
+    213, -1536, 44, 214, -1536, 44, 217, -1536, 44, 218, -1536, 44, 219, -1536, 44, 220, -1536, 44,
+    221, -8021, 44, 223, -8021, 44, 251, -1536, 44, 324, -12032, 44, 334, -4352, 44, 335, -1536,
+    44, 344, -11605, 47, 13, 2432, 47, 14, -3541, 47, 26, -1536, 47, 33, -1536, 47, 54, -1536, 47,
+    56, -5589, 47, 57, -4779, 47, 139, -1536, 47, 160, -1536, 47, 161, -1536, 47, 162, -1536, 47,
+    163, -1536, 47, 164, -1536, 47, 189, -4779, 47, 344, -4779, 48, 13, -1920, 48, 14, -13611, 48,
+    33, -5589, 48, 57, -1920, 48, 65, -3925, 48, 69, -3115, 48, 73, -1920, 48, 78, -1536, 48, 79,
+    -3115, 48, 82, -1536, 48, 83, -1536, 48, 85, -1536, 48, 139, -1536, 48, 160, -5589, 48, 161,
+    -5589, 48, 162, -5589, 48, 163, -5589, 48, 164, -5589, 48, 189, -1920, 48, 192, -3925, 48, 193,
+    -3925, 48, 194, -3925, 48, 195, -3925, 48, 196, -3925, 48, 197, -3925, 48, 200, -3115, 48, 201,
+    -3115, 48, 202, -3115, 48, 203, -3115, 48, 209, -1536, 48, 210, -3115, 48, 211, -3115, 48, 212,
+    -3115, 48, 213, -3115, 48, 214, -3115, 48, 217, -1536, 48, 218, -1536, 48, 219, -1536, 48, 220,
+    -1536, 48, 251, -3115, 48, 296, -1536, 48, 309, -1536, 48, 313, -1536, 48, 319, -1536, 48, 321,
+    -1536, 48, 335, -1536, 48, 344, -1920, 49, 13, 2432, 50, 13, -3541, 50, 14, -3115, 50, 26,
+    -2688, 50, 33, -3541, 50, 35, -4352, 50, 52, -6357, 50, 54, -4779, 50, 55, -3541, 50, 57,
+    -5589, 50, 65, -1920, 50, 69, -3925, 50, 79, -3925, 50, 85, -3925, 50, 89, -4779, 50, 139,
+    -4779, 50, 155, -1536, 50, 160, -3541, 50, 161, -3541, 50, 162, -3541, 50, 163, -3541, 50, 164,
+    -3541, 50, 167, -4352, 50, 189, -5589, 50, 192, -1920, 50, 193, -1920, 50, 194, -1920, 50, 195,
+    -1920, 50, 196, -1920, 50, 197, -1920, 50, 200, -3925, 50, 201, -3925, 50, 202, -3925, 50, 203,
+    -3925, 50, 210, -3925, 50, 211, -3925, 50, 212, -3925, 50, 213, -3925, 50, 214, -3925, 50, 217,
+    -3925, 50, 218, -3925, 50, 219, -3925, 50, 220, -3925, 50, 221, -4779, 50, 223, -4779, 50, 230,
+    -4352, 50, 236, -4352, 50, 251, -3925, 50, 324, -6357, 50, 335, -3925, 50, 344, -5589, 51, 33,
+    1621, 51, 160, 1621, 51, 161, 1621, 51, 162, 1621, 51, 163, 1621, 51, 164, 1621, 52, 13, -8021,
+    52, 14, -10368, 52, 26, -9600, 52, 33, -6784, 52, 35, -5120, 52, 52, -1536, 52, 65, -14464, 52,
+    67, -14848, 52, 69, -14848, 52, 73, -2688, 52, 79, -14848, 52, 82, -12843, 52, 83, -14464, 52,
+    85, -13269, 52, 87, -14464, 52, 89, -13611, 52, 139, -8021, 52, 155, -4779, 52, 160, -6784, 52,
+    161, -6784, 52, 162, -6784, 52, 163, -6784, 52, 164, -6784, 52, 167, -5120, 52, 192, -10197,
+    52, 193, -14464, 52, 194, -10197, 52, 195, -10197, 52, 196, -10197, 52, 197, -10197, 52, 199,
+    -14848, 52, 200, -12288, 52, 201, -14848, 52, 202, -12288, 52, 203, -12288, 52, 210, -11435,
+    52, 211, -14848, 52, 212, -11435, 52, 213, -11435, 52, 214, -11435, 52, 217, -11989, 52, 218,
+    -13269, 52, 219, -11989, 52, 220, -11989, 52, 221, -13611, 52, 223, -13611, 52, 230, -5120, 52,
+    231, -14848, 52, 236, -5120, 52, 237, -14848, 52, 251, -14848, 52, 309, -12843, 52, 313,
+    -12843, 52, 319, -14464, 52, 321, -14464, 52, 324, -1536, 52, 335, -13269, 53, 58, -1536, 53,
+    349, -1536, 54, 13, -5120, 54, 14, -11264, 54, 26, -7125, 54, 33, -5589, 54, 47, -1536, 54, 65,
+    -6784, 54, 69, -6784, 54, 73, -1920, 54, 79, -6784, 54, 85, -5931, 54, 89, -2347, 54, 139,
+    -7595, 54, 155, -4779, 54, 160, -5589, 54, 161, -5589, 54, 162, -5589, 54, 163, -5589, 54, 164,
+    -5589, 54, 178, -1536, 54, 179, -1536, 54, 180, -1536, 54, 181, -1536, 54, 182, -1536, 54, 192,
+    -6784, 54, 193, -6784, 54, 194, -6784, 54, 195, -6784, 54, 196, -6784, 54, 197, -6784, 54, 200,
+    -6784, 54, 201, -6784, 54, 202, -6784, 54, 203, -6784, 54, 210, -6784, 54, 211, -6784, 54, 212,
+    -6784, 54, 213, -6784, 54, 214, -6784, 54, 217, -5931, 54, 218, -5931, 54, 219, -5931, 54, 220,
+    -5931, 54, 221, -2347, 54, 223, -2347, 54, 251, -6784, 54, 335, -5931, 55, 13, -3541, 55, 14,
+    -10027, 55, 26, -5120, 55, 33, -4779, 55, 65, -5589, 55, 69, -5120, 55, 73, -1920, 55, 79,
+    -5120, 55, 82, -3925, 55, 85, -3115, 55, 89, -1536, 55, 139, -4779, 55, 155, -1536, 55, 160,
+    -4779, 55, 161, -4779, 55, 162, -4779, 55, 163, -4779, 55, 164, -4779, 55, 192, -5589, 55, 193,
+    -5589, 55, 194, -5589, 55, 195, -5589, 55, 196, -5589, 55, 197, -5589, 55, 200, -5120, 55, 201,
+    -5120, 55, 202, -5120, 55, 203, -5120, 55, 210, -5120, 55, 211, -5120, 55, 212, -5120, 55, 213,
+    -5120, 55, 214, -5120, 55, 217, -3115, 55, 218, -3115, 55, 219, -3115, 55, 220, -3115, 55, 221,
+    -1536, 55, 223, -1536, 55, 251, -5120, 55, 309, -3925, 55, 313, -3925, 55, 335, -3115, 56, 13,
+    -4352, 56, 35, -6357, 56, 47, -5589, 56, 52, -1536, 56, 69, -3925, 56, 139, -4779, 56, 167,
+    -6357, 56, 178, -5589, 56, 179, -5589, 56, 180, -5589, 56, 181, -5589, 56, 182, -5589, 56, 200,
+    -3925, 56, 201, -3925, 56, 202, -3925, 56, 203, -3925, 56, 230, -6357, 56, 236, -6357, 56, 251,
+    -3925, 56, 324, -1536, 57, 13, -10368, 57, 14, -17707, 57, 26, -11605, 57, 33, -6784, 57, 35,
+    -4779, 57, 47, -4779, 57, 65, -12032, 57, 69, -11605, 57, 73, -3115, 57, 79, -11605, 57, 85,
+    -10027, 57, 139, -9600, 57, 155, -6357, 57, 160, -6784, 57, 161, -6784, 57, 162, -6784, 57,
+    163, -6784, 57, 164, -6784, 57, 167, -4779, 57, 178, -4779, 57, 179, -4779, 57, 180, -4779, 57,
+    181, -4779, 57, 182, -4779, 57, 192, -12032, 57, 193, -12032, 57, 194, -12032, 57, 195, -12032,
+    57, 196, -12032, 57, 197, -12032, 57, 200, -11605, 57, 201, -11605, 57, 202, -11605, 57, 203,
+    -11605, 57, 210, -11605, 57, 211, -11605, 57, 212, -11605, 57, 213, -11605, 57, 214, -11605,
+    57, 217, -10027, 57, 218, -10027, 57, 219, -10027, 57, 220, -10027, 57, 230, -4779, 57, 236,
+    -4779, 57, 251, -11605, 57, 335, -10027, 58, 13, -1536, 69, 88, -1536, 70, 13, -4779, 70, 14,
+    -6357, 70, 26, -3115, 70, 84, -1536, 70, 87, -1536, 70, 89, -1536, 70, 139, -3115, 70, 155,
+    -1536, 70, 221, -1536, 70, 223, -1536, 70, 325, -1536, 75, 65, -1536, 75, 69, -3115, 75, 79,
+    -3115, 75, 85, -2688, 75, 89, -3115, 75, 192, -1536, 75, 193, -1536, 75, 194, -1536, 75, 195,
+    -1536, 75, 196, -1536, 75, 197, -1536, 75, 200, -3115, 75, 201, -3115, 75, 202, -3115, 75, 203,
+    -3115, 75, 210, -3115, 75, 211, -3115, 75, 212, -3115, 75, 213, -3115, 75, 214, -3115, 75, 217,
+    -2688, 75, 218, -2688, 75, 219, -2688, 75, 220, -2688, 75, 221, -3115, 75, 223, -3115, 75, 251,
+    -3115, 75, 335, -2688, 79, 13, 1621, 79, 14, -1536, 79, 88, -2688, 82, 13, -5589, 82, 14,
+    -8021, 82, 26, -1536, 82, 67, -1920, 82, 68, -1536, 82, 69, -1920, 82, 71, -1536, 82, 72,
+    -1536, 82, 77, -1536, 82, 78, -1536, 82, 79, -1920, 82, 81, -1536, 82, 82, -1536, 82, 88,
+    -2347, 82, 139, -3115, 82, 199, -1920, 82, 200, -1920, 82, 201, -1920, 82, 202, -1920, 82, 203,
+    -1920, 82, 209, -1536, 82, 210, -1920, 82, 211, -1920, 82, 212, -1920, 82, 213, -1920, 82, 214,
+    -1920, 82, 231, -1920, 82, 237, -1920, 82, 239, 3072, 82, 251, -1920, 82, 255, -1536, 82, 296,
+    -1536, 82, 309, -1536, 82, 313, -1536, 86, 13, -2347, 86, 14, -6784, 86, 26, -4779, 86, 139,
+    -1536, 86, 155, -1536, 87, 14, -8021, 87, 26, -4779, 87, 139, -1536, 87, 155, -1536, 88, 67,
+    -1536, 88, 69, -2688, 88, 79, -2688, 88, 199, -1536, 88, 200, -2688, 88, 201, -2688, 88, 202,
+    -2688, 88, 203, -2688, 88, 210, -2688, 88, 211, -2688, 88, 212, -2688, 88, 213, -2688, 88, 214,
+    -2688, 88, 231, -1536, 88, 237, -1536, 88, 251, -2688, 89, 13, -1536, 89, 14, -12459, 89, 26,
+    -6357, 89, 139, -1536, 89, 155, -1536, 139, 34, -1536, 139, 35, -1536, 139, 36, -1536, 139, 39,
+    -1536, 139, 42, -1536, 139, 52, -4779, 139, 54, -4779, 139, 55, -1536, 139, 57, -6357, 139, 86,
+    -1536, 139, 87, -1536, 139, 89, -1536, 139, 166, 6443, 139, 167, -1536, 139, 189, -6357, 139,
+    221, -1536, 139, 223, -1536, 139, 230, -1536, 139, 236, -1536, 139, 238, -1536, 139, 254,
+    -1536, 139, 324, -4779, 139, 344, -6357, 155, 33, -3115, 155, 34, -3115, 155, 35, -1536, 155,
+    36, -1536, 155, 42, -1536, 155, 47, -1536, 155, 52, -8021, 155, 54, -7595, 155, 55, -4779, 155,
+    56, -4779, 155, 57, -9600, 155, 86, -1536, 155, 87, -1536, 155, 89, -1536, 155, 160, -3115,
+    155, 161, -3115, 155, 162, -3115, 155, 163, -3115, 155, 164, -3115, 155, 167, -1536, 155, 178,
+    -1536, 155, 179, -1536, 155, 180, -1536, 155, 181, -1536, 155, 182, -1536, 155, 189, -9600,
+    155, 221, -1536, 155, 223, -1536, 155, 230, -1536, 155, 236, -1536, 155, 238, -1536, 155, 324,
+    -8021, 155, 344, -9600, 160, 13, -1920, 160, 14, -1536, 160, 26, -1536, 160, 33, 2432, 160, 35,
+    -1536, 160, 39, -1536, 160, 47, -1536, 160, 49, -1536, 160, 52, -6784, 160, 54, -5589, 160, 55,
+    -4779, 160, 57, -6784, 160, 67, -1536, 160, 68, -1536, 160, 69, -1536, 160, 70, -3115, 160, 79,
+    -1536, 160, 81, -1536, 160, 84, -1536, 160, 86, -5120, 160, 87, -3541, 160, 89, -5931, 160,
+    139, -3115, 160, 160, 2432, 160, 161, 2432, 160, 162, 2432, 160, 163, 2432, 160, 164, 2432,
+    160, 167, -1536, 160, 178, -1536, 160, 179, -1536, 160, 180, -1536, 160, 181, -1536, 160, 182,
+    -1536, 160, 189, -6784, 160, 199, -1536, 160, 200, -1536, 160, 201, -1536, 160, 202, -1536,
+    160, 203, -1536, 160, 210, -1536, 160, 211, -1536, 160, 212, -1536, 160, 213, -1536, 160, 214,
+    -1536, 160, 221, -5931, 160, 223, -5931, 160, 224, 2432, 160, 226, 2432, 160, 228, 2432, 160,
+    230, -1536, 160, 231, -1536, 160, 232, -1536, 160, 234, -1536, 160, 235, -1536, 160, 236,
+    -1536, 160, 237, -1536, 160, 239, -1536, 160, 243, -1536, 160, 245, -1536, 160, 247, -1536,
+    160, 249, -1536, 160, 251, -1536, 160, 252, -1536, 160, 254, -1536, 160, 256, -1536, 160, 300,
+    -1536, 160, 301, -1536, 160, 302, -1536, 160, 303, -1536, 160, 304, -1536, 160, 305, -1536,
+    160, 322, -6784, 160, 323, -1536, 160, 324, -6784, 160, 325, -1536, 160, 340, -4779, 160, 341,
+    -3541, 160, 342, -6784, 160, 343, -5931, 160, 344, -6784, 161, 13, -1920, 161, 14, -1536, 161,
+    26, -1536, 161, 33, 2432, 161, 35, -1536, 161, 39, -1536, 161, 47, -1536, 161, 49, -1536, 161,
+    52, -6784, 161, 54, -5589, 161, 55, -4779, 161, 57, -6784, 161, 67, -1536, 161, 68, -1536, 161,
+    69, -1536, 161, 70, -3115, 161, 79, -1536, 161, 81, -1536, 161, 84, -1536, 161, 86, -5120, 161,
+    87, -3541, 161, 89, -5931, 161, 139, -3115, 161, 160, 2432, 161, 161, 2432, 161, 162, 2432,
+    161, 163, 2432, 161, 164, 2432, 161, 167, -1536, 161, 178, -1536, 161, 179, -1536, 161, 180,
+    -1536, 161, 181, -1536, 161, 182, -1536, 161, 189, -6784, 161, 199, -1536, 161, 200, -1536,
+    161, 201, -1536, 161, 202, -1536, 161, 203, -1536, 161, 210, -1536, 161, 211, -1536, 161, 212,
+    -1536, 161, 213, -1536, 161, 214, -1536, 161, 221, -5931, 161, 223, -5931, 161, 224, 2432, 161,
+    226, 2432, 161, 228, 2432, 161, 230, -1536, 161, 231, -1536, 161, 232, -1536, 161, 234, -1536,
+    161, 235, -1536, 161, 236, -1536, 161, 237, -1536, 161, 239, -1536, 161, 243, -1536, 161, 245,
+    -1536, 161, 247, -1536, 161, 249, -1536, 161, 251, -1536, 161, 252, -1536, 161, 254, -1536,
+    161, 256, -1536, 161, 300, -1536, 161, 301, -1536, 161, 302, -1536, 161, 303, -1536, 161, 304,
+    -1536, 161, 305, -1536, 161, 322, -6784, 161, 323, -1536, 161, 324, -6784, 161, 325, -1536,
+    161, 340, -4779, 161, 341, -3541, 161, 342, -6784, 161, 343, -5931, 161, 344, -6784, 162, 13,
+    -1920, 162, 14, -1536, 162, 26, -1536, 162, 33, 2432, 162, 35, -1536, 162, 39, -1536, 162, 47,
+    -1536, 162, 49, -1536, 162, 52, -6784, 162, 54, -5589, 162, 55, -4779, 162, 57, -6784, 162, 67,
+    -1536, 162, 68, -1536, 162, 69, -1536, 162, 70, -3115, 162, 79, -1536, 162, 81, -1536, 162, 84,
+    -1536, 162, 86, -5120, 162, 87, -3541, 162, 89, -5931, 162, 139, -3115, 162, 160, 2432, 162,
+    161, 2432, 162, 162, 2432, 162, 163, 2432, 162, 164, 2432, 162, 167, -1536, 162, 178, -1536,
+    162, 179, -1536, 162, 180, -1536, 162, 181, -1536, 162, 182, -1536, 162, 189, -6784, 162, 199,
+    -1536, 162, 200, -1536, 162, 201, -1536, 162, 202, -1536, 162, 203, -1536, 162, 210, -1536,
+    162, 211, -1536, 162, 212, -1536, 162, 213, -1536, 162, 214, -1536, 162, 221, -5931, 162, 223,
+    -5931, 162, 224, 2432, 162, 226, 2432, 162, 228, 2432, 162, 230, -1536, 162, 231, -1536, 162,
+    232, -1536, 162, 234, -1536, 162, 235, -1536, 162, 236, -1536, 162, 237, -1536, 162, 239,
+    -1536, 162, 243, -1536, 162, 245, -1536, 162, 247, -1536, 162, 249, -1536, 162, 251, -1536,
+    162, 252, -1536, 162, 254, -1536, 162, 256, -1536, 162, 300, -1536, 162, 301, -1536, 162, 302,
+    -1536, 162, 303, -1536, 162, 304, -1536, 162, 305, -1536, 162, 322, -6784, 162, 323, -1536,
+    162, 324, -6784, 162, 325, -1536, 162, 340, -4779, 162, 341, -3541, 162, 342, -6784, 162, 343,
+    -5931, 162, 344, -6784, 163, 13, -1920, 163, 14, -1536, 163, 26, -1536, 163, 33, 2432, 163, 35,
+    -1536, 163, 39, -1536, 163, 47, -1536, 163, 49, -1536, 163, 52, -6784, 163, 54, -5589, 163, 55,
+    -4779, 163, 57, -6784, 163, 67, -1536, 163, 68, -1536, 163, 69, -1536, 163, 70, -3115, 163, 79,
+    -1536, 163, 81, -1536, 163, 84, -1536, 163, 86, -5120, 163, 87, -3541, 163, 89, -5931, 163,
+    139, -3115, 163, 160, 2432, 163, 161, 2432, 163, 162, 2432, 163, 163, 2432, 163, 164, 2432,
+    163, 167, -1536, 163, 178, -1536, 163, 179, -1536, 163, 180, -1536, 163, 181, -1536, 163, 182,
+    -1536, 163, 189, -6784, 163, 199, -1536, 163, 200, -1536, 163, 201, -1536, 163, 202, -1536,
+    163, 203, -1536, 163, 210, -1536, 163, 211, -1536, 163, 212, -1536, 163, 213, -1536, 163, 214,
+    -1536, 163, 221, -5931, 163, 223, -5931, 163, 224, 2432, 163, 226, 2432, 163, 228, 2432, 163,
+    230, -1536, 163, 231, -1536, 163, 232, -1536, 163, 234, -1536, 163, 235, -1536, 163, 236,
+    -1536, 163, 237, -1536, 163, 239, -1536, 163, 243, -1536, 163, 245, -1536, 163, 247, -1536,
+    163, 249, -1536, 163, 251, -1536, 163, 252, -1536, 163, 254, -1536, 163, 256, -1536, 163, 300,
+    -1536, 163, 301, -1536, 163, 302, -1536, 163, 303, -1536, 163, 304, -1536, 163, 305, -1536,
+    163, 322, -6784, 163, 323, -1536, 163, 324, -6784, 163, 325, -1536, 163, 340, -4779, 163, 341,
+    -3541, 163, 342, -6784, 163, 343, -5931, 163, 344, -6784, 164, 13, -1920, 164, 14, -1536, 164,
+    26, -1536, 164, 33, 2432, 164, 35, -1536, 164, 39, -1536, 164, 47, -1536, 164, 49, -1536, 164,
+    52, -6784, 164, 54, -5589, 164, 55, -4779, 164, 57, -6784, 164, 67, -1536, 164, 68, -1536, 164,
+    69, -1536, 164, 70, -3115, 164, 79, -1536, 164, 81, -1536, 164, 84, -1536, 164, 86, -5120, 164,
+    87, -3541, 164, 89, -5931, 164, 139, -3115, 164, 160, 2432, 164, 161, 2432, 164, 162, 2432,
+    164, 163, 2432, 164, 164, 2432, 164, 167, -1536, 164, 178, -1536, 164, 179, -1536, 164, 180,
+    -1536, 164, 181, -1536, 164, 182, -1536, 164, 189, -6784, 164, 199, -1536, 164, 200, -1536,
+    164, 201, -1536, 164, 202, -1536, 164, 203, -1536, 164, 210, -1536, 164, 211, -1536, 164, 212,
+    -1536, 164, 213, -1536, 164, 214, -1536, 164, 221, -5931, 164, 223, -5931, 164, 224, 2432, 164,
+    226, 2432, 164, 228, 2432, 164, 230, -1536, 164, 231, -1536, 164, 232, -1536, 164, 234, -1536,
+    164, 235, -1536, 164, 236, -1536, 164, 237, -1536, 164, 239, -1536, 164, 243, -1536, 164, 245,
+    -1536, 164, 247, -1536, 164, 249, -1536, 164, 251, -1536, 164, 252, -1536, 164, 254, -1536,
+    164, 256, -1536, 164, 300, -1536, 164, 301, -1536, 164, 302, -1536, 164, 303, -1536, 164, 304,
+    -1536, 164, 305, -1536, 164, 322, -6784, 164, 323, -1536, 164, 324, -6784, 164, 325, -1536,
+    164, 340, -4779, 164, 341, -3541, 164, 342, -6784, 164, 343, -5931, 164, 344, -6784, 167, 57,
+    -1536, 167, 139, -1536, 167, 155, -1536, 167, 189, -1536, 167, 344, -1536, 176, 33, -1536, 176,
+    54, -1536, 176, 57, -4779, 176, 139, -1536, 176, 155, -1536, 176, 160, -1536, 176, 161, -1536,
+    176, 162, -1536, 176, 163, -1536, 176, 164, -1536, 176, 189, -4779, 176, 224, -1536, 176, 226,
+    -1536, 176, 228, -1536, 176, 342, -4779, 176, 344, -4779, 178, 13, 2432, 178, 14, -3541, 178,
+    26, -1536, 178, 33, -1536, 178, 54, -1536, 178, 56, -5589, 178, 57, -4779, 178, 139, -1536,
+    178, 160, -1536, 178, 161, -1536, 178, 162, -1536, 178, 163, -1536, 178, 164, -1536, 178, 189,
+    -4779, 178, 344, -4779, 179, 13, 2432, 179, 14, -3541, 179, 26, -1536, 179, 33, -1536, 179, 54,
+    -1536, 179, 56, -5589, 179, 57, -4779, 179, 139, -1536, 179, 160, -1536, 179, 161, -1536, 179,
+    162, -1536, 179, 163, -1536, 179, 164, -1536, 179, 189, -4779, 179, 344, -4779, 180, 13, 2432,
+    180, 14, -3541, 180, 26, -1536, 180, 33, -1536, 180, 54, -1536, 180, 56, -5589, 180, 57, -4779,
+    180, 139, -1536, 180, 160, -1536, 180, 161, -1536, 180, 162, -1536, 180, 163, -1536, 180, 164,
+    -1536, 180, 189, -4779, 180, 344, -4779, 181, 13, 2432, 181, 14, -3541, 181, 26, -1536, 181,
+    33, -1536, 181, 54, -1536, 181, 56, -5589, 181, 57, -4779, 181, 139, -1536, 181, 160, -1536,
+    181, 161, -1536, 181, 162, -1536, 181, 163, -1536, 181, 164, -1536, 181, 189, -4779, 181, 344,
+    -4779, 182, 13, 2432, 182, 14, -3541, 182, 26, -1536, 182, 33, -1536, 182, 54, -1536, 182, 56,
+    -5589, 182, 57, -4779, 182, 139, -1536, 182, 160, -1536, 182, 161, -1536, 182, 162, -1536, 182,
+    163, -1536, 182, 164, -1536, 182, 189, -4779, 182, 344, -4779, 185, 58, -1536, 185, 349, -1536,
+    186, 58, -1536, 186, 349, -1536, 187, 58, -1536, 187, 349, -1536, 188, 58, -1536, 188, 349,
+    -1536, 189, 13, -10368, 189, 14, -17707, 189, 26, -11605, 189, 33, -6784, 189, 35, -4779, 189,
+    47, -4779, 189, 65, -12032, 189, 69, -11605, 189, 73, -3115, 189, 79, -11605, 189, 85, -10027,
+    189, 139, -9600, 189, 155, -6357, 189, 160, -6784, 189, 161, -6784, 189, 162, -6784, 189, 163,
+    -6784, 189, 164, -6784, 189, 167, -4779, 189, 178, -4779, 189, 179, -4779, 189, 180, -4779,
+    189, 181, -4779, 189, 182, -4779, 189, 192, -12032, 189, 193, -12032, 189, 194, -12032, 189,
+    195, -12032, 189, 196, -12032, 189, 197, -12032, 189, 200, -11605, 189, 201, -11605, 189, 202,
+    -11605, 189, 203, -11605, 189, 210, -11605, 189, 211, -11605, 189, 212, -11605, 189, 213,
+    -11605, 189, 214, -11605, 189, 217, -10027, 189, 218, -10027, 189, 219, -10027, 189, 220,
+    -10027, 189, 230, -4779, 189, 236, -4779, 189, 251, -11605, 189, 335, -10027, 190, 14, -6357,
+    190, 26, -3115, 191, 13, 1621, 200, 88, -1536, 201, 88, -1536, 202, 88, -1536, 203, 88, -1536,
+    210, 13, 1621, 210, 14, -1536, 210, 88, -2688, 211, 13, 1621, 211, 14, -1536, 211, 88, -2688,
+    212, 13, 1621, 212, 14, -1536, 212, 88, -2688, 213, 13, 1621, 213, 14, -1536, 213, 88, -2688,
+    214, 13, 1621, 214, 14, -1536, 214, 88, -2688, 221, 13, -1536, 221, 14, -12459, 221, 26, -6357,
+    221, 139, -1536, 221, 155, -1536, 223, 13, -1536, 223, 14, -12459, 223, 26, -6357, 223, 139,
+    -1536, 223, 155, -1536, 224, 13, -1920, 224, 14, -1536, 224, 26, -1536, 224, 33, 2432, 224, 35,
+    -1536, 224, 39, -1536, 224, 47, -1536, 224, 49, -1536, 224, 52, -6784, 224, 54, -5589, 224, 55,
+    -4779, 224, 57, -6784, 224, 67, -1536, 224, 68, -1536, 224, 69, -1536, 224, 70, -3115, 224, 79,
+    -1536, 224, 81, -1536, 224, 84, -1536, 224, 86, -5120, 224, 87, -3541, 224, 89, -5931, 224,
+    139, -3115, 224, 160, 2432, 224, 161, 2432, 224, 162, 2432, 224, 163, 2432, 224, 164, 2432,
+    224, 178, -1536, 224, 179, -1536, 224, 180, -1536, 224, 181, -1536, 224, 182, -1536, 224, 189,
+    -6784, 224, 199, -1536, 224, 200, -1536, 224, 201, -1536, 224, 202, -1536, 224, 203, -1536,
+    224, 210, -1536, 224, 211, -1536, 224, 212, -1536, 224, 213, -1536, 224, 214, -1536, 224, 221,
+    -5931, 224, 223, -5931, 224, 224, 2432, 224, 226, 2432, 224, 228, 2432, 224, 230, -1536, 224,
+    232, -1536, 224, 234, -1536, 224, 235, -1536, 224, 236, -1536, 224, 239, -1536, 224, 241,
+    -1536, 224, 243, -1536, 224, 245, -1536, 224, 247, -1536, 224, 249, -1536, 224, 251, -1536,
+    224, 252, -1536, 224, 256, -1536, 224, 300, -1536, 224, 301, -1536, 224, 302, -1536, 224, 303,
+    -1536, 224, 304, -1536, 224, 305, -1536, 224, 322, -6784, 224, 323, -1536, 224, 324, -6784,
+    224, 325, -1536, 224, 340, -4779, 224, 341, -3541, 224, 342, -6784, 224, 343, -5931, 224, 344,
+    -6784, 226, 13, -1920, 226, 14, -1536, 226, 26, -1536, 226, 33, 2432, 226, 35, -1536, 226, 39,
+    -1536, 226, 47, -1536, 226, 49, -1536, 226, 52, -6784, 226, 54, -5589, 226, 55, -4779, 226, 57,
+    -6784, 226, 67, -1536, 226, 68, -1536, 226, 69, -1536, 226, 70, -3115, 226, 79, -1536, 226, 81,
+    -1536, 226, 84, -1536, 226, 86, -5120, 226, 87, -3541, 226, 89, -5931, 226, 139, -3115, 226,
+    160, 2432, 226, 161, 2432, 226, 162, 2432, 226, 163, 2432, 226, 164, 2432, 226, 178, -1536,
+    226, 179, -1536, 226, 180, -1536, 226, 181, -1536, 226, 182, -1536, 226, 189, -6784, 226, 199,
+    -1536, 226, 200, -1536, 226, 201, -1536, 226, 202, -1536, 226, 203, -1536, 226, 210, -1536,
+    226, 211, -1536, 226, 212, -1536, 226, 213, -1536, 226, 214, -1536, 226, 221, -5931, 226, 223,
+    -5931, 226, 224, 2432, 226, 226, 2432, 226, 228, 2432, 226, 230, -1536, 226, 232, -1536, 226,
+    234, -1536, 226, 235, -1536, 226, 236, -1536, 226, 239, -1536, 226, 241, -1536, 226, 243,
+    -1536, 226, 245, -1536, 226, 247, -1536, 226, 249, -1536, 226, 251, -1536, 226, 252, -1536,
+    226, 256, -1536, 226, 300, -1536, 226, 301, -1536, 226, 302, -1536, 226, 303, -1536, 226, 304,
+    -1536, 226, 305, -1536, 226, 322, -6784, 226, 323, -1536, 226, 324, -6784, 226, 325, -1536,
+    226, 340, -4779, 226, 341, -3541, 226, 342, -6784, 226, 343, -5931, 226, 344, -6784, 228, 13,
+    -1920, 228, 14, -1536, 228, 26, -1536, 228, 33, 2432, 228, 35, -1536, 228, 39, -1536, 228, 47,
+    -1536, 228, 49, -1536, 228, 52, -6784, 228, 54, -5589, 228, 55, -4779, 228, 57, -6784, 228, 67,
+    -1536, 228, 68, -1536, 228, 69, -1536, 228, 70, -3115, 228, 79, -1536, 228, 81, -1536, 228, 84,
+    -1536, 228, 86, -5120, 228, 87, -3541, 228, 139, -3115, 228, 160, 2432, 228, 161, 2432, 228,
+    162, 2432, 228, 163, 2432, 228, 164, 2432, 228, 178, -1536, 228, 179, -1536, 228, 180, -1536,
+    228, 181, -1536, 228, 182, -1536, 228, 189, -6784, 228, 199, -1536, 228, 200, -1536, 228, 201,
+    -1536, 228, 202, -1536, 228, 203, -1536, 228, 210, -1536, 228, 211, -1536, 228, 212, -1536,
+    228, 213, -1536, 228, 214, -1536, 228, 224, 2432, 228, 226, 2432, 228, 228, 2432, 228, 230,
+    -1536, 228, 232, -1536, 228, 234, -1536, 228, 235, -1536, 228, 236, -1536, 228, 239, -1536,
+    228, 241, -1536, 228, 243, -1536, 228, 245, -1536, 228, 247, -1536, 228, 249, -1536, 228, 251,
+    -1536, 228, 252, -1536, 228, 256, -1536, 228, 300, -1536, 228, 301, -1536, 228, 302, -1536,
+    228, 303, -1536, 228, 305, -1536, 228, 322, -6784, 228, 323, -1536, 228, 324, -6784, 228, 325,
+    -1536, 228, 340, -4779, 228, 341, -3541, 228, 342, -6784, 228, 344, -6784, 230, 57, -1536, 230,
+    139, -1536, 230, 155, -1536, 230, 189, -1536, 230, 344, -1536, 236, 57, -1536, 236, 139, -1536,
+    236, 155, -1536, 236, 189, -1536, 236, 344, -1536, 238, 33, -1536, 238, 54, -1536, 238, 57,
+    -4779, 238, 139, -1536, 238, 155, -1536, 238, 160, -1536, 238, 161, -1536, 238, 162, -1536,
+    238, 163, -1536, 238, 164, -1536, 238, 189, -4779, 238, 224, -1536, 238, 226, -1536, 238, 228,
+    -1536, 238, 342, -4779, 238, 344, -4779, 240, 33, -1536, 240, 54, -1536, 240, 57, -4779, 240,
+    139, -1536, 240, 155, -1536, 240, 160, -1536, 240, 161, -1536, 240, 162, -1536, 240, 163,
+    -1536, 240, 164, -1536, 240, 189, -4779, 240, 344, -4779, 251, 88, -1536, 254, 52, -3115, 254,
+    57, -4352, 254, 139, -1536, 254, 155, -1536, 254, 189, -4352, 254, 324, -3115, 254, 344, -4352,
+    281, 13, -1536, 281, 33, 2005, 281, 47, -3115, 281, 52, -12032, 281, 53, -4352, 281, 54, -9600,
+    281, 55, -8021, 281, 57, -11605, 281, 69, -1536, 281, 79, -1536, 281, 85, -1536, 281, 89,
+    -8021, 281, 160, 2005, 281, 161, 2005, 281, 162, 2005, 281, 163, 2005, 281, 164, 2005, 281,
+    178, -3115, 281, 179, -3115, 281, 180, -3115, 281, 181, -3115, 281, 182, -3115, 281, 185,
+    -4352, 281, 186, -4352, 281, 187, -4352, 281, 188, -4352, 281, 189, -11605, 281, 200, -1536,
+    281, 201, -1536, 281, 202, -1536, 281, 203, -1536, 281, 210, -1536, 281, 211, -1536, 281, 212,
+    -1536, 281, 213, -1536, 281, 214, -1536, 281, 217, -1536, 281, 218, -1536, 281, 219, -1536,
+    281, 220, -1536, 281, 221, -8021, 281, 223, -8021, 281, 251, -1536, 281, 324, -12032, 281, 334,
+    -4352, 281, 335, -1536, 281, 344, -11605, 285, 13, -1536, 285, 33, 2005, 285, 47, -3115, 285,
+    52, -12032, 285, 53, -4352, 285, 54, -9600, 285, 55, -8021, 285, 57, -11605, 285, 69, -1536,
+    285, 79, -1536, 285, 85, -1536, 285, 89, -8021, 285, 160, 2005, 285, 161, 2005, 285, 162, 2005,
+    285, 163, 2005, 285, 164, 2005, 285, 178, -3115, 285, 179, -3115, 285, 180, -3115, 285, 181,
+    -3115, 285, 182, -3115, 285, 185, -4352, 285, 186, -4352, 285, 187, -4352, 285, 188, -4352,
+    285, 189, -11605, 285, 200, -1536, 285, 201, -1536, 285, 202, -1536, 285, 203, -1536, 285, 210,
+    -1536, 285, 211, -1536, 285, 212, -1536, 285, 213, -1536, 285, 214, -1536, 285, 217, -1536,
+    285, 218, -1536, 285, 219, -1536, 285, 220, -1536, 285, 221, -8021, 285, 223, -8021, 285, 251,
+    -1536, 285, 324, -12032, 285, 334, -4352, 285, 335, -1536, 285, 344, -11605, 288, 76, -8235,
+    308, 13, -3541, 308, 14, -3115, 308, 26, -2688, 308, 33, -3541, 308, 35, -4352, 308, 52, -6357,
+    308, 54, -4779, 308, 55, -3541, 308, 57, -5589, 308, 65, -1920, 308, 69, -3925, 308, 79, -3925,
+    308, 85, -3925, 308, 89, -4779, 308, 139, -4779, 308, 155, -1536, 308, 160, -3541, 308, 161,
+    -3541, 308, 162, -3541, 308, 163, -3541, 308, 164, -3541, 308, 167, -4352, 308, 189, -5589,
+    308, 192, -1920, 308, 193, -1920, 308, 194, -1920, 308, 195, -1920, 308, 196, -1920, 308, 197,
+    -1920, 308, 200, -3925, 308, 201, -3925, 308, 202, -3925, 308, 203, -3925, 308, 210, -3925,
+    308, 211, -3925, 308, 212, -3925, 308, 213, -3925, 308, 214, -3925, 308, 217, -3925, 308, 218,
+    -3925, 308, 219, -3925, 308, 220, -3925, 308, 221, -4779, 308, 223, -4779, 308, 230, -4352,
+    308, 236, -4352, 308, 251, -3925, 308, 324, -6357, 308, 335, -3925, 308, 344, -5589, 309, 13,
+    -5589, 309, 14, -8021, 309, 26, -1536, 309, 67, -1920, 309, 68, -1536, 309, 69, -1920, 309, 71,
+    -1536, 309, 72, -1536, 309, 77, -1536, 309, 78, -1536, 309, 79, -1920, 309, 81, -1536, 309, 82,
+    -1536, 309, 88, -2347, 309, 139, -3115, 309, 199, -1920, 309, 200, -1920, 309, 201, -1920, 309,
+    202, -1920, 309, 203, -1920, 309, 209, -1536, 309, 210, -1920, 309, 211, -1920, 309, 212,
+    -1920, 309, 213, -1920, 309, 214, -1920, 309, 231, -1920, 309, 237, -1920, 309, 239, 3072, 309,
+    251, -1920, 309, 255, -1536, 309, 296, -1536, 309, 309, -1536, 309, 313, -1536, 312, 13, -3541,
+    312, 14, -3115, 312, 26, -2688, 312, 33, -3541, 312, 35, -4352, 312, 52, -6357, 312, 54, -4779,
+    312, 55, -3541, 312, 57, -5589, 312, 65, -1920, 312, 69, -3925, 312, 79, -3925, 312, 85, -3925,
+    312, 89, -4779, 312, 139, -4779, 312, 155, -1536, 312, 160, -3541, 312, 161, -3541, 312, 162,
+    -3541, 312, 163, -3541, 312, 164, -3541, 312, 167, -4352, 312, 189, -5589, 312, 192, -1920,
+    312, 193, -1920, 312, 194, -1920, 312, 195, -1920, 312, 196, -1920, 312, 197, -1920, 312, 200,
+    -3925, 312, 201, -3925, 312, 202, -3925, 312, 203, -3925, 312, 210, -3925, 312, 211, -3925,
+    312, 212, -3925, 312, 213, -3925, 312, 214, -3925, 312, 217, -3925, 312, 218, -3925, 312, 219,
+    -3925, 312, 220, -3925, 312, 221, -4779, 312, 223, -4779, 312, 230, -4352, 312, 236, -4352,
+    312, 251, -3925, 312, 324, -6357, 312, 335, -3925, 312, 344, -5589, 313, 13, -5589, 313, 14,
+    -8021, 313, 26, -1536, 313, 67, -1920, 313, 68, -1536, 313, 69, -1920, 313, 71, -1536, 313, 72,
+    -1536, 313, 77, -1536, 313, 78, -1536, 313, 79, -1920, 313, 81, -1536, 313, 82, -1536, 313, 88,
+    -2347, 313, 139, -3115, 313, 199, -1920, 313, 200, -1920, 313, 201, -1920, 313, 202, -1920,
+    313, 203, -1920, 313, 209, -1536, 313, 210, -1920, 313, 211, -1920, 313, 212, -1920, 313, 213,
+    -1920, 313, 214, -1920, 313, 231, -1920, 313, 237, -1920, 313, 239, -1536, 313, 251, -1920,
+    313, 255, -1536, 313, 296, -1536, 313, 309, -1536, 313, 313, -1536, 318, 33, 1621, 318, 160,
+    1621, 318, 161, 1621, 318, 162, 1621, 318, 163, 1621, 318, 164, 1621, 320, 33, 1621, 320, 160,
+    1621, 320, 161, 1621, 320, 162, 1621, 320, 163, 1621, 320, 164, 1621, 324, 13, -8021, 324, 14,
+    -10368, 324, 26, -9600, 324, 33, -6784, 324, 35, -5120, 324, 52, -1536, 324, 65, -14464, 324,
+    67, -14848, 324, 69, -14848, 324, 73, -2688, 324, 79, -14848, 324, 82, -12843, 324, 83, -14464,
+    324, 85, -13269, 324, 87, -14464, 324, 89, -13611, 324, 139, -8021, 324, 155, -4779, 324, 160,
+    -6784, 324, 161, -6784, 324, 162, -6784, 324, 163, -6784, 324, 164, -6784, 324, 167, -5120,
+    324, 192, -14464, 324, 193, -14464, 324, 194, -14464, 324, 195, -14464, 324, 196, -14464, 324,
+    197, -14464, 324, 199, -14848, 324, 200, -14848, 324, 201, -14848, 324, 202, -14848, 324, 203,
+    -14848, 324, 210, -14848, 324, 211, -14848, 324, 212, -14848, 324, 213, -14848, 324, 214,
+    -14848, 324, 217, -13269, 324, 218, -13269, 324, 219, -13269, 324, 220, -13269, 324, 221,
+    -13611, 324, 223, -13611, 324, 230, -5120, 324, 231, -14848, 324, 236, -5120, 324, 237, -14848,
+    324, 251, -14848, 324, 309, -12843, 324, 313, -12843, 324, 319, -14464, 324, 321, -14464, 324,
+    324, -1536, 324, 335, -13269, 334, 58, -1536, 334, 349, -1536, 344, 13, -10368, 344, 14,
+    -17707, 344, 26, -11605, 344, 33, -6784, 344, 35, -4779, 344, 47, -4779, 344, 65, -12032, 344,
+    69, -11605, 344, 73, -3115, 344, 79, -11605, 344, 85, -10027, 344, 139, -9600, 344, 155, -6357,
+    344, 160, -6784, 344, 161, -6784, 344, 162, -6784, 344, 163, -6784, 344, 164, -6784, 344, 167,
+    -4779, 344, 178, -4779, 344, 179, -4779, 344, 180, -4779, 344, 181, -4779, 344, 182, -4779,
+    344, 192, -12032, 344, 193, -12032, 344, 194, -12032, 344, 195, -12032, 344, 196, -12032, 344,
+    197, -12032, 344, 200, -11605, 344, 201, -11605, 344, 202, -11605, 344, 203, -11605, 344, 210,
+    -11605, 344, 211, -11605, 344, 212, -11605, 344, 213, -11605, 344, 214, -11605, 344, 217,
+    -10027, 344, 218, -10027, 344, 219, -10027, 344, 220, -10027, 344, 230, -4779, 344, 236, -4779,
+    344, 251, -11605, 344, 335, -10027, 349, 13, -1536,
+];
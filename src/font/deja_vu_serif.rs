@@ -3771,3 +3771,194 @@ pub static CHARACTER_MAP: [i16; 500] = [
     434, 435, 436, 437, 438, 439, 440, 441, 442, 443, 444, 445, 446, 447, 448, 449, 450, 451, 452,
     453, 454, 455, 456, 457, 458, 459, 460, 461, 462, 463, 464, 465, 466, 467,
 ];
+
+/* kerning pairs from the GPOS table of DejaVu Serif 2.37: left glyph, right glyph, adjustment */
+
+pub static KERNING_PAIRS: [i32; 1089 * 3] = [
+    13, 52, -3115, 13, 54, -6357, 13, 55, -4779, 13, 56, -3115, 13, 57, -9600, 13, 189, -9600, 13,
+    324, -3115, 13, 344, -9600, 33, 52, -4779, 33, 54, -4352, 33, 55, -3541, 33, 57, -3541, 33, 70,
+    -1536, 33, 84, -1536, 33, 86, -3541, 33, 87, -3925, 33, 89, -3541, 33, 189, -3541, 33, 221,
+    -3541, 33, 223, -3541, 33, 322, -4779, 33, 323, -1536, 33, 324, -4779, 33, 325, -1536, 33, 340,
+    -3541, 33, 341, -3925, 33, 342, -3541, 33, 343, -3541, 33, 344, -3541, 34, 13, 1621, 34, 35,
+    1621, 34, 39, 1621, 34, 47, 1621, 34, 57, -1536, 34, 167, 1621, 34, 178, 1621, 34, 179, 1621,
+    34, 180, 1621, 34, 181, 1621, 34, 182, 1621, 34, 184, 1621, 34, 189, -1536, 34, 230, 1621, 34,
+    232, 1621, 34, 234, 1621, 34, 236, 1621, 34, 252, 1621, 34, 254, 1621, 34, 256, 1621, 34, 258,
+    1621, 34, 300, 1621, 34, 302, 1621, 34, 304, 1621, 34, 306, 1621, 34, 342, -1536, 34, 344,
+    -1536, 34, 458, 1621, 34, 460, 1621, 35, 12, -3115, 35, 14, -3115, 36, 12, -3115, 36, 13, 1621,
+    36, 14, -3115, 36, 54, -1536, 37, 13, 1621, 38, 12, -13611, 38, 13, -3925, 38, 14, -13611, 38,
+    26, -3115, 38, 27, -3115, 38, 33, -7595, 38, 65, -5931, 38, 69, -4779, 38, 79, -4779, 38, 160,
+    -7595, 38, 161, -7595, 38, 162, -7595, 38, 163, -7595, 38, 164, -7595, 38, 192, -5931, 38, 193,
+    -5931, 38, 194, -5931, 38, 195, -5931, 38, 196, -5931, 38, 197, -5931, 38, 198, -5931, 38, 200,
+    -4779, 38, 201, -4779, 38, 202, -4779, 38, 203, -4779, 38, 210, -4779, 38, 211, -4779, 38, 212,
+    -4779, 38, 213, -4779, 38, 214, -4779, 38, 216, -4779, 38, 224, -7595, 38, 225, -5931, 38, 226,
+    -7595, 38, 227, -5931, 38, 228, -7595, 38, 229, -5931, 38, 243, -4779, 38, 245, -4779, 38, 247,
+    -4779, 38, 249, -4779, 38, 251, -4779, 38, 301, -4779, 38, 303, -4779, 38, 305, -4779, 38, 307,
+    -4779, 38, 451, -5931, 38, 459, -4779, 38, 461, -4779, 39, 12, -3115, 39, 13, 1621, 39, 14,
+    -3115, 39, 57, -1536, 39, 189, -1536, 39, 344, -1536, 42, 12, -5120, 42, 14, -6784, 42, 26,
+    -3541, 42, 27, -3541, 43, 13, -6357, 43, 33, -3541, 43, 35, -2347, 43, 47, -2347, 43, 53,
+    -3115, 43, 55, -3115, 43, 57, -2347, 43, 69, -2347, 43, 79, -2347, 43, 85, -1920, 43, 89,
+    -5589, 43, 160, -3541, 43, 161, -3541, 43, 162, -3541, 43, 163, -3541, 43, 164, -3541, 43, 167,
+    -2347, 43, 178, -2347, 43, 179, -2347, 43, 180, -2347, 43, 181, -2347, 43, 182, -2347, 43, 184,
+    -2347, 43, 185, -3115, 43, 186, -3115, 43, 187, -3115, 43, 188, -3115, 43, 189, -2347, 43, 200,
+    -2347, 43, 201, -2347, 43, 202, -2347, 43, 203, -2347, 43, 210, -2347, 43, 211, -2347, 43, 212,
+    -2347, 43, 213, -2347, 43, 214, -2347, 43, 216, -1536, 43, 217, -1920, 43, 218, -1920, 43, 219,
+    -1920, 43, 220, -1920, 43, 221, -5589, 43, 223, -5589, 43, 230, -2347, 43, 236, -2347, 43, 251,
+    -2347, 43, 306, -2347, 43, 307, -2347, 43, 334, -3115, 43, 335, -1920, 43, 344, -2347, 44, 52,
+    -7125, 44, 53, -4779, 44, 54, -10368, 44, 55, -7595, 44, 57, -5589, 44, 89, -1536, 44, 185,
+    -4779, 44, 186, -4779, 44, 187, -4779, 44, 188, -4779, 44, 189, -5589, 44, 221, -1536, 44, 223,
+    -1536, 44, 324, -7125, 44, 334, -4779, 44, 344, -5589, 46, 12, -5589, 46, 14, -5589, 46, 26,
+    -3115, 46, 27, -3115, 47, 12, -5120, 47, 13, 3200, 47, 14, -5120, 47, 54, -1536, 47, 56, -1536,
+    48, 12, -17707, 48, 13, -4779, 48, 14, -17707, 48, 26, -3115, 48, 27, -3115, 48, 33, -8021, 48,
+    53, -1536, 48, 65, -3925, 48, 69, -3925, 48, 79, -3541, 48, 83, -2347, 48, 160, -8021, 48, 161,
+    -8021, 48, 162, -8021, 48, 163, -8021, 48, 164, -8021, 48, 185, -1536, 48, 186, -1536, 48, 187,
+    -1536, 48, 188, -1536, 48, 192, -3925, 48, 193, -3925, 48, 194, -3925, 48, 195, -3925, 48, 196,
+    -3925, 48, 197, -3925, 48, 198, -3925, 48, 200, -3925, 48, 201, -3925, 48, 202, -3925, 48, 203,
+    -3925, 48, 210, -3541, 48, 211, -3541, 48, 212, -3541, 48, 213, -3541, 48, 214, -3541, 48, 216,
+    -3541, 48, 251, -3925, 48, 307, -3541, 48, 319, -2347, 48, 321, -2347, 48, 334, -1536, 49, 12,
+    -4352, 49, 13, 3200, 49, 14, -4352, 50, 52, -1536, 50, 54, -3115, 50, 55, -1920, 50, 57, -2688,
+    50, 65, 2005, 50, 89, -1536, 50, 189, -2688, 50, 192, 2005, 50, 193, 2005, 50, 194, 2005, 50,
+    195, 2005, 50, 196, 2005, 50, 197, 2005, 50, 198, 2005, 50, 216, 1621, 50, 221, -1536, 50, 223,
+    -1536, 50, 324, -1536, 50, 344, -2688, 51, 12, -3115, 51, 13, 3200, 51, 14, -3115, 51, 51,
+    -1536, 51, 318, -1536, 51, 320, -1536, 52, 12, -12843, 52, 13, -11264, 52, 14, -12843, 52, 26,
+    -3115, 52, 27, -3115, 52, 33, -4779, 52, 52, 1621, 52, 65, -6784, 52, 67, -6784, 52, 69, -6784,
+    52, 79, -6784, 52, 83, -6357, 52, 87, -3115, 52, 160, -4779, 52, 161, -4779, 52, 162, -4779,
+    52, 163, -4779, 52, 164, -4779, 52, 192, -2517, 52, 193, -6784, 52, 194, -2517, 52, 195, -2517,
+    52, 196, -2517, 52, 197, -2517, 52, 198, -6784, 52, 199, -6784, 52, 200, -4224, 52, 201, -6784,
+    52, 202, -4224, 52, 203, -4224, 52, 210, -3371, 52, 211, -6784, 52, 212, -3371, 52, 213, -3371,
+    52, 214, -3371, 52, 216, -6784, 52, 231, -6784, 52, 237, -6784, 52, 251, -6784, 52, 307, -6784,
+    52, 319, -6357, 52, 321, -6357, 52, 324, 1621, 53, 12, -8021, 53, 13, -1536, 53, 14, -8021, 53,
+    26, -3115, 53, 27, -3115, 53, 33, -2688, 53, 42, -2347, 53, 160, -2688, 53, 161, -2688, 53,
+    162, -2688, 53, 163, -2688, 53, 164, -2688, 54, 12, -15275, 54, 13, -8021, 54, 14, -15275, 54,
+    26, -8789, 54, 27, -8789, 54, 33, -5931, 54, 47, -1536, 54, 65, -8021, 54, 69, -8021, 54, 73,
+    -1536, 54, 79, -8021, 54, 85, -5589, 54, 89, -3541, 54, 160, -5931, 54, 161, -5931, 54, 162,
+    -5931, 54, 163, -5931, 54, 164, -5931, 54, 178, -1536, 54, 179, -1536, 54, 180, -1536, 54, 181,
+    -1536, 54, 182, -1536, 54, 184, -1536, 54, 192, -8021, 54, 193, -8021, 54, 194, -8021, 54, 195,
+    -8021, 54, 196, -8021, 54, 197, -8021, 54, 198, -8021, 54, 200, -8021, 54, 201, -8021, 54, 202,
+    -8021, 54, 203, -8021, 54, 210, -8021, 54, 211, -8021, 54, 212, -8021, 54, 213, -8021, 54, 214,
+    -8021, 54, 216, -8021, 54, 217, -5589, 54, 218, -5589, 54, 219, -5589, 54, 220, -5589, 54, 221,
+    -3541, 54, 223, -3541, 54, 251, -8021, 54, 306, -1536, 54, 307, -8021, 54, 335, -5589, 55, 12,
+    -15275, 55, 13, -6357, 55, 14, -15275, 55, 26, -7595, 55, 27, -7595, 55, 33, -4352, 55, 65,
+    -7595, 55, 69, -7125, 55, 73, -1536, 55, 79, -5931, 55, 82, -3925, 55, 85, -3541, 55, 89,
+    -1920, 55, 160, -4352, 55, 161, -4352, 55, 162, -4352, 55, 163, -4352, 55, 164, -4352, 55, 192,
+    -7595, 55, 193, -7595, 55, 194, -7595, 55, 195, -7595, 55, 196, -7595, 55, 197, -7595, 55, 198,
+    -5931, 55, 200, -7125, 55, 201, -7125, 55, 202, -7125, 55, 203, -7125, 55, 210, -5931, 55, 211,
+    -5931, 55, 212, -5931, 55, 213, -5931, 55, 214, -5931, 55, 216, -5931, 55, 217, -3541, 55, 218,
+    -3541, 55, 219, -3541, 55, 220, -3541, 55, 221, -1920, 55, 223, -1920, 55, 251, -7125, 55, 307,
+    -5931, 55, 309, -3925, 55, 313, -3925, 55, 335, -3541, 56, 13, -3115, 56, 33, -3115, 56, 35,
+    -1536, 56, 47, -1536, 56, 160, -3115, 56, 161, -3115, 56, 162, -3115, 56, 163, -3115, 56, 164,
+    -3115, 56, 167, -1536, 56, 178, -1536, 56, 179, -1536, 56, 180, -1536, 56, 181, -1536, 56, 182,
+    -1536, 56, 184, -1536, 56, 230, -1536, 56, 236, -1536, 56, 306, -1536, 57, 12, -11264, 57, 13,
+    -9600, 57, 14, -11264, 57, 26, -10795, 57, 27, -10795, 57, 33, -6784, 57, 35, -1536, 57, 65,
+    -6784, 57, 69, -7595, 57, 73, -1536, 57, 79, -7595, 57, 85, -7595, 57, 160, -6784, 57, 161,
+    -6784, 57, 162, -6784, 57, 163, -6784, 57, 164, -6784, 57, 167, -1536, 57, 192, -6784, 57, 193,
+    -6784, 57, 194, -6784, 57, 195, -6784, 57, 196, -6784, 57, 197, -6784, 57, 198, -8363, 57, 200,
+    -7595, 57, 201, -7595, 57, 202, -7595, 57, 203, -7595, 57, 210, -7595, 57, 211, -7595, 57, 212,
+    -7595, 57, 213, -7595, 57, 214, -7595, 57, 216, -7595, 57, 217, -7595, 57, 218, -7595, 57, 219,
+    -7595, 57, 220, -7595, 57, 230, -1536, 57, 236, -1536, 57, 251, -7595, 57, 307, -9173, 57, 335,
+    -7595, 58, 12, -1536, 58, 14, -1536, 70, 12, -3115, 70, 13, -3115, 70, 14, -3115, 75, 13,
+    -1536, 79, 14, -1536, 82, 12, -9600, 82, 14, -9600, 86, 12, -10368, 86, 14, -10368, 87, 12,
+    -10368, 87, 14, -10368, 88, 13, -1536, 89, 12, -11605, 89, 14, -11605, 160, 52, -4779, 160, 54,
+    -4352, 160, 55, -3541, 160, 57, -3541, 160, 70, -1536, 160, 84, -1536, 160, 86, -3541, 160, 87,
+    -3925, 160, 89, -3541, 160, 189, -3541, 160, 221, -3541, 160, 223, -3541, 160, 322, -4779, 160,
+    323, -1536, 160, 324, -4779, 160, 325, -1536, 160, 340, -3541, 160, 341, -3925, 160, 342,
+    -3541, 160, 343, -3541, 160, 344, -3541, 161, 52, -4779, 161, 54, -4352, 161, 55, -3541, 161,
+    57, -3541, 161, 70, -1536, 161, 84, -1536, 161, 86, -3541, 161, 87, -3925, 161, 89, -3541, 161,
+    189, -3541, 161, 221, -3541, 161, 223, -3541, 161, 322, -4779, 161, 323, -1536, 161, 324,
+    -4779, 161, 325, -1536, 161, 340, -3541, 161, 341, -3925, 161, 342, -3541, 161, 343, -3541,
+    161, 344, -3541, 162, 52, -4779, 162, 54, -4352, 162, 55, -3541, 162, 57, -3541, 162, 70,
+    -1536, 162, 84, -1536, 162, 86, -3541, 162, 87, -3925, 162, 89, -3541, 162, 189, -3541, 162,
+    221, -3541, 162, 223, -3541, 162, 322, -4779, 162, 323, -1536, 162, 324, -4779, 162, 325,
+    -1536, 162, 340, -3541, 162, 341, -3925, 162, 342, -3541, 162, 343, -3541, 162, 344, -3541,
+    163, 52, -4779, 163, 54, -4352, 163, 55, -3541, 163, 57, -3541, 163, 70, -1536, 163, 84, -1536,
+    163, 86, -3541, 163, 87, -3925, 163, 89, -3541, 163, 189, -3541, 163, 221, -3541, 163, 223,
+    -3541, 163, 322, -4779, 163, 323, -1536, 163, 324, -4779, 163, 325, -1536, 163, 340, -3541,
+    163, 341, -3925, 163, 342, -3541, 163, 343, -3541, 163, 344, -3541, 164, 52, -4779, 164, 54,
+    -4352, 164, 55, -3541, 164, 57, -3541, 164, 70, -1536, 164, 84, -1536, 164, 86, -3541, 164, 87,
+    -3925, 164, 89, -3541, 164, 189, -3541, 164, 221, -3541, 164, 223, -3541, 164, 322, -4779, 164,
+    323, -1536, 164, 324, -4779, 164, 325, -1536, 164, 340, -3541, 164, 341, -3925, 164, 342,
+    -3541, 164, 343, -3541, 164, 344, -3541, 166, 13, 1621, 167, 12, -3115, 167, 14, -3115, 168,
+    13, 1621, 169, 13, 1621, 170, 13, 1621, 171, 13, 1621, 176, 12, -3115, 176, 13, 3200, 176, 14,
+    -3115, 176, 33, -1536, 176, 54, -1536, 176, 57, -1536, 176, 160, -1536, 176, 161, -1536, 176,
+    162, -1536, 176, 163, -1536, 176, 164, -1536, 176, 189, -1536, 176, 344, -1536, 177, 12, -5589,
+    177, 14, -5589, 177, 26, -3115, 177, 27, -3115, 178, 12, -5120, 178, 13, 3200, 178, 14, -5120,
+    178, 54, -1536, 178, 56, -1536, 179, 12, -5120, 179, 13, 3200, 179, 14, -5120, 179, 54, -1536,
+    179, 56, -1536, 180, 12, -5120, 180, 13, 3200, 180, 14, -5120, 180, 54, -1536, 180, 56, -1536,
+    181, 12, -5120, 181, 13, 3200, 181, 14, -5120, 181, 54, -1536, 181, 56, -1536, 182, 12, -5120,
+    182, 13, 3200, 182, 14, -5120, 182, 54, -1536, 182, 56, -1536, 184, 12, -5120, 184, 13, 3200,
+    184, 14, -5120, 184, 54, -1536, 184, 56, -1536, 185, 12, -8021, 185, 13, -1536, 185, 14, -8021,
+    185, 26, -3115, 185, 27, -3115, 185, 33, -2688, 185, 42, -2347, 185, 160, -2688, 185, 161,
+    -2688, 185, 162, -2688, 185, 163, -2688, 185, 164, -2688, 186, 12, -8021, 186, 13, -1536, 186,
+    14, -8021, 186, 26, -3115, 186, 27, -3115, 186, 33, -2688, 186, 42, -2347, 186, 160, -2688,
+    186, 161, -2688, 186, 162, -2688, 186, 163, -2688, 186, 164, -2688, 187, 12, -8021, 187, 13,
+    -1536, 187, 14, -8021, 187, 26, -3115, 187, 27, -3115, 187, 33, -2688, 187, 42, -2347, 187,
+    160, -2688, 187, 161, -2688, 187, 162, -2688, 187, 163, -2688, 187, 164, -2688, 188, 12, -8021,
+    188, 13, -1536, 188, 14, -8021, 188, 26, -3115, 188, 27, -3115, 188, 33, -2688, 188, 42, -2347,
+    188, 160, -2688, 188, 161, -2688, 188, 162, -2688, 188, 163, -2688, 188, 164, -2688, 189, 12,
+    -11264, 189, 13, -9600, 189, 14, -11264, 189, 26, -10795, 189, 27, -10795, 189, 33, -6784, 189,
+    35, -1536, 189, 65, -6784, 189, 69, -7595, 189, 73, -1536, 189, 79, -7595, 189, 85, -7595, 189,
+    160, -6784, 189, 161, -6784, 189, 162, -6784, 189, 163, -6784, 189, 164, -6784, 189, 167,
+    -1536, 189, 192, -6784, 189, 193, -6784, 189, 194, -6784, 189, 195, -6784, 189, 196, -6784,
+    189, 197, -6784, 189, 198, -8363, 189, 200, -7595, 189, 201, -7595, 189, 202, -7595, 189, 203,
+    -7595, 189, 210, -7595, 189, 211, -7595, 189, 212, -7595, 189, 213, -7595, 189, 214, -7595,
+    189, 216, -7595, 189, 217, -7595, 189, 218, -7595, 189, 219, -7595, 189, 220, -7595, 189, 230,
+    -1536, 189, 236, -1536, 189, 251, -7595, 189, 307, -9173, 189, 335, -7595, 190, 12, -14464,
+    190, 13, 1621, 190, 14, -14464, 208, 14, -1536, 210, 14, -1536, 211, 14, -1536, 212, 14, -1536,
+    213, 14, -1536, 214, 14, -1536, 216, 14, -1536, 221, 12, -11605, 221, 14, -11605, 222, 12,
+    -1536, 222, 14, -4352, 223, 12, -11605, 223, 14, -11605, 224, 52, -4779, 224, 54, -4352, 224,
+    55, -3541, 224, 57, -3541, 224, 70, -1536, 224, 84, -1536, 224, 86, -3541, 224, 87, -3925, 224,
+    89, -3541, 224, 189, -3541, 224, 221, -3541, 224, 223, -3541, 224, 322, -4779, 224, 323, -1536,
+    224, 324, -4779, 224, 325, -1536, 224, 340, -3541, 224, 341, -3925, 224, 342, -3541, 224, 343,
+    -3541, 224, 344, -3541, 226, 52, -4779, 226, 54, -4352, 226, 55, -3541, 226, 57, -3541, 226,
+    70, -1536, 226, 84, -1536, 226, 86, -3541, 226, 87, -3925, 226, 89, -3541, 226, 189, -3541,
+    226, 221, -3541, 226, 223, -3541, 226, 322, -4779, 226, 323, -1536, 226, 324, -4779, 226, 325,
+    -1536, 226, 340, -3541, 226, 341, -3925, 226, 342, -3541, 226, 343, -3541, 226, 344, -3541,
+    228, 52, -4779, 228, 54, -4352, 228, 55, -3541, 228, 57, -3541, 228, 70, -1536, 228, 84, -1536,
+    228, 86, -3541, 228, 87, -3925, 228, 89, -3541, 228, 189, -3541, 228, 221, -3541, 228, 223,
+    -3541, 228, 322, -4779, 228, 323, -1536, 228, 324, -4779, 228, 325, -1536, 228, 340, -3541,
+    228, 341, -3925, 228, 342, -3541, 228, 343, -3541, 228, 344, -3541, 230, 12, -3115, 230, 14,
+    -3115, 232, 12, -3115, 232, 14, -3115, 234, 12, -3115, 234, 14, -3115, 236, 12, -3115, 236, 14,
+    -3115, 238, 12, -3115, 238, 13, 1621, 238, 14, -3115, 238, 54, -1536, 240, 12, -3115, 240, 13,
+    1621, 240, 14, -3115, 240, 54, -1536, 250, 13, 1621, 254, 12, -3115, 254, 13, 1621, 254, 14,
+    -3115, 254, 57, -1536, 254, 189, -1536, 254, 344, -1536, 281, 52, -7125, 281, 53, -4779, 281,
+    54, -10368, 281, 55, -7595, 281, 57, -5589, 281, 89, -1536, 281, 185, -4779, 281, 186, -4779,
+    281, 187, -4779, 281, 188, -4779, 281, 189, -5589, 281, 221, -1536, 281, 223, -1536, 281, 324,
+    -7125, 281, 334, -4779, 281, 344, -5589, 285, 52, -7125, 285, 53, -4779, 285, 54, -10368, 285,
+    55, -7595, 285, 57, -5589, 285, 89, -1536, 285, 185, -4779, 285, 186, -4779, 285, 187, -4779,
+    285, 188, -4779, 285, 189, -5589, 285, 221, -1536, 285, 223, -1536, 285, 324, -7125, 285, 334,
+    -4779, 285, 344, -5589, 288, 76, -9685, 289, 52, -7125, 289, 53, -1536, 289, 54, -10368, 289,
+    55, -7595, 289, 57, -8789, 289, 89, -1536, 289, 185, -1536, 289, 186, -1536, 289, 187, -1536,
+    289, 188, -1536, 289, 189, -8789, 289, 221, -1536, 289, 223, -1536, 289, 324, -7125, 289, 334,
+    -1536, 289, 344, -8789, 295, 12, -5589, 295, 14, -5589, 295, 26, -3115, 295, 27, -3115, 306,
+    13, 1621, 308, 52, -1536, 308, 54, -3115, 308, 55, -1920, 308, 57, -2688, 308, 65, 2005, 308,
+    89, -1536, 308, 189, -2688, 308, 192, 2005, 308, 193, 2005, 308, 194, 2005, 308, 195, 2005,
+    308, 196, 2005, 308, 197, 2005, 308, 198, 2005, 308, 216, 1621, 308, 221, -1536, 308, 223,
+    -1536, 308, 324, -1536, 308, 344, -2688, 309, 12, -9600, 309, 14, -9600, 312, 52, -1536, 312,
+    54, -3115, 312, 55, -1920, 312, 57, -2688, 312, 65, 2005, 312, 89, -1536, 312, 189, -2688, 312,
+    192, 2005, 312, 193, 2005, 312, 194, 2005, 312, 195, 2005, 312, 196, 2005, 312, 197, 2005, 312,
+    198, 2005, 312, 216, 1621, 312, 221, -1536, 312, 223, -1536, 312, 324, -1536, 312, 344, -2688,
+    313, 12, -9600, 313, 14, -9600, 318, 12, -3115, 318, 13, 3200, 318, 14, -3115, 318, 51, -1536,
+    318, 318, -1536, 318, 320, -1536, 320, 12, -3115, 320, 13, 3200, 320, 14, -3115, 320, 51,
+    -1536, 320, 318, -1536, 320, 320, -1536, 324, 12, -12843, 324, 13, -11264, 324, 14, -12843,
+    324, 26, -3115, 324, 27, -3115, 324, 33, -4779, 324, 52, 1621, 324, 65, -6784, 324, 67, -6784,
+    324, 69, -6784, 324, 79, -6784, 324, 83, -6357, 324, 87, -3115, 324, 160, -4779, 324, 161,
+    -4779, 324, 162, -4779, 324, 163, -4779, 324, 164, -4779, 324, 192, -6784, 324, 193, -6784,
+    324, 194, -6784, 324, 195, -6784, 324, 196, -6784, 324, 197, -6784, 324, 198, -6784, 324, 199,
+    -6784, 324, 200, -6784, 324, 201, -6784, 324, 202, -6784, 324, 203, -6784, 324, 210, -6784,
+    324, 211, -6784, 324, 212, -6784, 324, 213, -6784, 324, 214, -6784, 324, 216, -6784, 324, 231,
+    -6784, 324, 237, -6784, 324, 251, -6784, 324, 307, -6784, 324, 319, -6357, 324, 321, -6357,
+    324, 324, 1621, 334, 12, -8021, 334, 13, -1536, 334, 14, -8021, 334, 26, -3115, 334, 27, -3115,
+    334, 33, -2688, 334, 42, -2347, 334, 160, -2688, 334, 161, -2688, 334, 162, -2688, 334, 163,
+    -2688, 334, 164, -2688, 341, 12, -13099, 341, 14, -11691, 341, 65, 4693, 341, 67, 3627, 341,
+    68, 4139, 341, 69, 3669, 341, 70, 9387, 341, 71, 4139, 341, 73, 9387, 341, 74, 9301, 341, 77,
+    5333, 341, 78, 5333, 341, 79, 3627, 341, 80, 5973, 341, 81, 4139, 341, 82, 5333, 341, 83, 6613,
+    341, 84, 10027, 341, 85, 6144, 341, 86, 8747, 341, 87, 7083, 341, 88, 7424, 341, 89, 8747, 341,
+    90, 7637, 341, 341, 11136, 344, 12, -11264, 344, 13, -9600, 344, 14, -11264, 344, 26, -10795,
+    344, 27, -10795, 344, 33, -6784, 344, 35, -1536, 344, 65, -6784, 344, 69, -7595, 344, 73,
+    -1536, 344, 79, -7595, 344, 85, -7595, 344, 160, -6784, 344, 161, -6784, 344, 162, -6784, 344,
+    163, -6784, 344, 164, -6784, 344, 167, -1536, 344, 192, -6784, 344, 193, -6784, 344, 194,
+    -6784, 344, 195, -6784, 344, 196, -6784, 344, 197, -6784, 344, 198, -8363, 344, 200, -7595,
+    344, 201, -7595, 344, 202, -7595, 344, 203, -7595, 344, 210, -7595, 344, 211, -7595, 344, 212,
+    -7595, 344, 213, -7595, 344, 214, -7595, 344, 216, -7595, 344, 217, -7595, 344, 218, -7595,
+    344, 219, -7595, 344, 220, -7595, 344, 230, -1536, 344, 236, -1536, 344, 251, -7595, 344, 307,
+    -9173, 344, 335, -7595, 349, 12, -1536, 349, 14, -1536,
+];
@@ -14,27 +14,174 @@
 
 //! Representation of OpenVG fonts.
 
+mod bidi;
 mod deja_vu_sans;
 mod deja_vu_sans_mono;
 mod deja_vu_serif;
+mod sfnt;
 
+pub use self::bidi::{is_rtl, visual_order};
+pub(crate) use self::bidi::{run_text, visual_runs};
+
+use super::draw;
 use super::openvg::*;
 
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+use unicode_normalization::UnicodeNormalization;
+
+/// Describes problems encountered when loading fonts.
+#[derive(Debug)]
+pub enum Error {
+    /// The font file couldn't be read.
+    Io(io::Error),
+    /// The font data couldn't be parsed.
+    Parse(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "unable to read font: {}", e),
+            Error::Parse(ref s) => write!(f, "unable to parse font: {}", s),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Io(_) => "unable to read font",
+            Error::Parse(_) => "unable to parse font",
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+/// Specifies what is drawn for characters that a font has no glyph for.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum MissingGlyph {
+    /// Nothing is drawn and the character takes no space (the default).
+    Skip,
+    /// A hollow box is drawn in place of the character.
+    Box,
+}
+
+/// A glyph of a font and where to find it in the font's VGFont.
+#[derive(Copy, Clone, Debug)]
+pub struct Glyph {
+    /// The glyph outline.
+    pub path: VGPath,
+    /// The distance to the next glyph, without kerning.
+    pub advance: i32,
+    /// The VGFont holding the glyph.
+    pub font: VGFont,
+    /// The glyph's index in the VGFont.
+    pub index: VGuint,
+}
+
+/// Provides glyphs and metrics for drawing text: a Font or a FontFamily.
+///
+/// Advances and heights are in units of 1/65536 of the point size.
+pub trait GlyphSource {
+    /// Returns the glyph used to draw a character, if any.
+    fn glyph(&self, c: char) -> Option<Glyph>;
+    /// Returns true if a character has its own glyph rather than a missing glyph.
+    fn has_glyph(&self, c: char) -> bool;
+    /// Returns the adjustment to the advance of left when it is followed by right.
+    fn kerning(&self, left: char, right: char) -> i32;
+    /// Returns the distance from the lowest descender to the highest ascender.
+    fn font_height(&self) -> i32;
+    /// Returns the distance below the baseline that descenders reach (negative).
+    fn descender_height(&self) -> i32;
+}
+
 /// Represents an OpenVG font.
+///
+/// Glyph coordinates and metrics are in units of 1/65536 of the point size. Glyphs are
+/// kept in a VGFont so that text can be drawn with a single `vgDrawGlyphs` call.
 pub struct Font<'a> {
-    pub character_map: &'a [i16],
-    pub glyph_advances: &'a [i32],
+    /// Maps characters to indices in glyphs and glyph_advances.
+    pub character_map: HashMap<char, usize>,
+    pub glyph_advances: Cow<'a, [i32]>,
     pub glyph_count: i32,
     pub descender_height: i32,
     pub font_height: i32,
-    pub glyphs: [VGPath; 500],
+    pub glyphs: Vec<VGPath>,
+    vg_font: VGFont,
+    missing_glyph: MissingGlyph,
+    missing_glyph_box: VGPath,
+    kerning: Kerning,
+}
+
+/// Kerning adjustments, in the same units as glyph advances.
+enum Kerning {
+    None,
+    /// Adjustments between pairs of glyph indices.
+    Pairs(HashMap<(usize, usize), i32>),
+    /// Adjustments read from a font file, with the file's glyph for each glyph index.
+    Table(sfnt::Kerning, Vec<u16>, f32),
 }
 
 impl<'a> Drop for Font<'a> {
     fn drop(&mut self) {
-        for i in 0..self.glyph_count {
-            unsafe { vgDestroyPath(self.glyphs[i as usize]) }
+        for path in self.glyphs.iter() {
+            unsafe { vgDestroyPath(*path) }
+        }
+        if self.missing_glyph_box != VG_INVALID_HANDLE {
+            unsafe { vgDestroyPath(self.missing_glyph_box) }
+        }
+        unsafe { vgDestroyFont(self.vg_font) }
+        draw::forget_text_shadows();
+    }
+}
+
+/// Creates an empty path for glyph data.
+fn glyph_path() -> VGPath {
+    unsafe {
+        vgCreatePath(
+            VG_PATH_FORMAT_STANDARD,
+            VGPathDatatype::VG_PATH_DATATYPE_F,
+            1.0 / 65536.0,
+            0.0,
+            0,
+            0,
+            VGPathCapabilities::VG_PATH_CAPABILITY_ALL as u32,
+        )
+    }
+}
+
+/// Creates a VGFont holding glyph paths, with their advances as escapements. One more
+/// glyph than the paths is reserved for the missing glyph box.
+fn create_vg_font(glyphs: &[VGPath], advances: &[i32]) -> VGFont {
+    unsafe {
+        let font = vgCreateFont(glyphs.len() as VGint + 1);
+        for (i, (&path, &advance)) in glyphs.iter().zip(advances.iter()).enumerate() {
+            set_glyph(font, i as VGuint, path, advance);
         }
+        font
+    }
+}
+
+/// Adds a glyph path to a VGFont.
+fn set_glyph(font: VGFont, index: VGuint, path: VGPath, advance: i32) {
+    let origin: [VGfloat; 2] = [0.0, 0.0];
+    let escapement: [VGfloat; 2] = [advance as VGfloat / 65536.0, 0.0];
+    unsafe {
+        vgSetGlyphToPath(font, index, path, VGboolean::VG_FALSE, &origin, &escapement);
     }
 }
 
@@ -52,19 +199,11 @@ impl<'a> Font<'a> {
         descender_height: i32,
         font_height: i32,
     ) -> Font<'a> {
-        let mut glyphs: [VGPath; 500] = [0; 500];
+        let mut glyphs = Vec::with_capacity(glyph_count as usize);
 
         for i in 0..glyph_count {
             unsafe {
-                let path = vgCreatePath(
-                    VG_PATH_FORMAT_STANDARD,
-                    VGPathDatatype::VG_PATH_DATATYPE_F,
-                    1.0 / 65536.0,
-                    0.0,
-                    0,
-                    0,
-                    VGPathCapabilities::VG_PATH_CAPABILITY_ALL as u32,
-                );
+                let path = glyph_path();
                 let ic = glyph_instruction_counts[i as usize];
                 if ic > 0 {
                     let instructions = glyph_instructions
@@ -74,20 +213,215 @@ impl<'a> Font<'a> {
                         .as_ptr() as *const i8;
                     vgAppendPathData(path, ic, instructions, points);
                 }
-                glyphs[i as usize] = path;
+                glyphs.push(path);
+            }
+        }
+
+        let mut map = HashMap::new();
+        for (c, &index) in character_map.iter().enumerate() {
+            if let Some(c) = char::from_u32(c as u32) {
+                if index >= 0 && index < glyph_count as i16 {
+                    map.insert(c, index as usize);
+                }
             }
         }
 
         Font {
-            character_map: character_map,
-            glyph_advances: glyph_advances,
+            character_map: map,
+            glyph_advances: Cow::Borrowed(glyph_advances),
             glyph_count: glyph_count,
             descender_height: descender_height,
             font_height: font_height,
+            vg_font: create_vg_font(&glyphs, glyph_advances),
+            glyphs: glyphs,
+            missing_glyph: MissingGlyph::Skip,
+            missing_glyph_box: VG_INVALID_HANDLE,
+            kerning: Kerning::None,
+        }
+    }
+
+    /// Adds kerning to a font as triples of left glyph index, right glyph index and
+    /// adjustment to the left glyph's advance.
+    pub fn with_kerning(mut self, pairs: &[i32]) -> Font<'a> {
+        let mut kerning = HashMap::new();
+        for pair in pairs.chunks(3) {
+            if pair.len() == 3 && pair[0] >= 0 && pair[1] >= 0 {
+                kerning.insert((pair[0] as usize, pair[1] as usize), pair[2]);
+            }
+        }
+        self.kerning = Kerning::Pairs(kerning);
+        self
+    }
+
+    /// Creates a Font from TrueType or OpenType data.
+    ///
+    /// Both quadratic (glyf) and cubic (CFF) outlines are supported.
+    pub fn from_ttf_bytes(data: &[u8]) -> Result<Font<'a>, Error> {
+        let file = sfnt::FontFile::parse(data).map_err(Error::Parse)?;
+        // Match the scale of the built-in fonts, which were rendered at 96 dpi.
+        let scale = 65536.0 * 4.0 / 3.0 / file.units_per_em as f32;
+
+        // Only glyphs that are reachable from characters are loaded.
+        let mut indices: HashMap<u16, usize> = HashMap::new();
+        let mut character_map = HashMap::new();
+        let mut glyphs = Vec::new();
+        let mut glyph_advances = Vec::new();
+        let mut glyph_ids = Vec::new();
+        for &(c, glyph) in file.mappings() {
+            let c = match char::from_u32(c) {
+                Some(c) => c,
+                None => continue,
+            };
+            let index = match indices.get(&glyph) {
+                Some(&index) => index,
+                None => {
+                    let outline = file.outline(glyph).map_err(Error::Parse)?;
+                    let points: Vec<VGfloat> = outline.points.iter().map(|p| p * scale).collect();
+                    let path = glyph_path();
+                    if !outline.is_empty() {
+                        unsafe {
+                            vgAppendPathData(
+                                path,
+                                outline.commands.len() as VGint,
+                                outline.commands.as_ptr(),
+                                points.as_ptr() as *const i8,
+                            );
+                        }
+                    }
+                    glyphs.push(path);
+                    glyph_ids.push(glyph);
+                    glyph_advances.push((file.advance(glyph) as f32 * scale) as i32);
+                    let index = glyphs.len() - 1;
+                    indices.insert(glyph, index);
+                    index
+                }
+            };
+            character_map.insert(c, index);
+        }
+
+        Ok(Font {
+            character_map: character_map,
+            vg_font: create_vg_font(&glyphs, &glyph_advances),
+            glyph_advances: Cow::Owned(glyph_advances),
+            glyph_count: glyphs.len() as i32,
+            descender_height: (file.descender as f32 * scale) as i32,
+            font_height: ((file.ascender - file.descender) as f32 * scale) as i32,
             glyphs: glyphs,
+            missing_glyph: MissingGlyph::Skip,
+            missing_glyph_box: VG_INVALID_HANDLE,
+            kerning: Kerning::Table(file.kerning(), glyph_ids, scale),
+        })
+    }
+
+    /// Creates a Font from a TrueType or OpenType file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Font<'a>, Error> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        Font::from_ttf_bytes(&data)
+    }
+
+    /// Returns the index of the glyph for a character, if the font has one.
+    pub fn glyph_index(&self, c: char) -> Option<usize> {
+        self.character_map.get(&c).cloned()
+    }
+
+    /// Returns true if the font has a glyph for a character.
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.character_map.contains_key(&c)
+    }
+
+    /// Returns the VGFont holding the font's glyphs, indexed like glyphs.
+    pub fn vg_font(&self) -> VGFont {
+        self.vg_font
+    }
+
+    /// Returns the glyph used to draw a character, substituting the missing glyph if
+    /// the font has no glyph for it.
+    pub fn glyph(&self, c: char) -> Option<Glyph> {
+        match self.glyph_index(c) {
+            Some(index) => Some(Glyph {
+                path: self.glyphs[index],
+                advance: self.glyph_advances[index],
+                font: self.vg_font,
+                index: index as VGuint,
+            }),
+            None => match self.missing_glyph {
+                MissingGlyph::Skip => None,
+                MissingGlyph::Box => Some(Glyph {
+                    path: self.missing_glyph_box,
+                    advance: self.missing_glyph_advance(),
+                    font: self.vg_font,
+                    index: self.glyphs.len() as VGuint,
+                }),
+            },
         }
     }
 
+    /// Returns the adjustment to the advance of left when it is followed by right.
+    pub fn kerning(&self, left: char, right: char) -> i32 {
+        let (l, r) = match (self.glyph_index(left), self.glyph_index(right)) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return 0,
+        };
+        match self.kerning {
+            Kerning::None => 0,
+            Kerning::Pairs(ref pairs) => pairs.get(&(l, r)).cloned().unwrap_or(0),
+            Kerning::Table(ref table, ref glyph_ids, scale) => {
+                (table.lookup(glyph_ids[l], glyph_ids[r]) as f32 * scale) as i32
+            }
+        }
+    }
+
+    /// Returns what is drawn for characters that the font has no glyph for.
+    pub fn missing_glyph(&self) -> MissingGlyph {
+        self.missing_glyph
+    }
+
+    /// Sets what is drawn for characters that the font has no glyph for.
+    pub fn set_missing_glyph(&mut self, missing_glyph: MissingGlyph) {
+        self.missing_glyph = missing_glyph;
+        if missing_glyph == MissingGlyph::Box && self.missing_glyph_box == VG_INVALID_HANDLE {
+            self.missing_glyph_box = self.create_missing_glyph_box();
+            set_glyph(
+                self.vg_font,
+                self.glyphs.len() as VGuint,
+                self.missing_glyph_box,
+                self.missing_glyph_advance(),
+            );
+        }
+    }
+
+    fn missing_glyph_advance(&self) -> i32 {
+        (self.font_height + self.descender_height) * 2 / 3
+    }
+
+    /// Creates a hollow box as tall as the ascent with a margin on each side.
+    fn create_missing_glyph_box(&self) -> VGPath {
+        let advance = self.missing_glyph_advance() as VGfloat;
+        let height = (self.font_height + self.descender_height) as VGfloat;
+        let margin = advance / 8.0;
+        let line = advance / 12.0;
+        let (x0, x1) = (margin, advance - margin);
+        let (y0, y1) = (0.0, height * 0.9);
+        // The inner rectangle runs the other way so it is a hole under either fill rule.
+        #[cfg_attr(rustfmt, rustfmt_skip)]
+        let points: [VGfloat; 16] = [
+            x0, y0, x1, y0, x1, y1, x0, y1,
+            x0 + line, y0 + line, x0 + line, y1 - line, x1 - line, y1 - line, x1 - line, y0 + line,
+        ];
+        let commands: [u8; 10] = [2, 4, 4, 4, 0, 2, 4, 4, 4, 0];
+        let path = glyph_path();
+        unsafe {
+            vgAppendPathData(
+                path,
+                commands.len() as VGint,
+                commands.as_ptr(),
+                points.as_ptr() as *const i8,
+            );
+        }
+        path
+    }
+
     /// Creates a deja_vu_serif font.
     pub fn serif() -> Font<'a> {
         Font::new(
@@ -102,6 +436,7 @@ impl<'a> Font<'a> {
             deja_vu_serif::DESCENDER_HEIGHT,
             deja_vu_serif::FONT_HEIGHT,
         )
+        .with_kerning(&deja_vu_serif::KERNING_PAIRS)
     }
 
     /// Creates a deja_vu_sans font.
@@ -118,6 +453,7 @@ impl<'a> Font<'a> {
             deja_vu_sans::DESCENDER_HEIGHT,
             deja_vu_sans::FONT_HEIGHT,
         )
+        .with_kerning(&deja_vu_sans::KERNING_PAIRS)
     }
 
     /// Creates a deja_vu_sans_mono font.
@@ -136,3 +472,180 @@ impl<'a> Font<'a> {
         )
     }
 }
+
+impl<'a> GlyphSource for Font<'a> {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        Font::glyph(self, c)
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        Font::has_glyph(self, c)
+    }
+
+    fn kerning(&self, left: char, right: char) -> i32 {
+        Font::kerning(self, left, right)
+    }
+
+    fn font_height(&self) -> i32 {
+        self.font_height
+    }
+
+    fn descender_height(&self) -> i32 {
+        self.descender_height
+    }
+}
+
+/// A glyph placed along a line of text by `shape()`.
+#[derive(Copy, Clone, Debug)]
+pub struct PlacedGlyph {
+    /// The character the glyph draws.
+    pub c: char,
+    /// The glyph drawn for the character.
+    pub glyph: Glyph,
+    /// The offset of the glyph from the start of the line.
+    pub x: i32,
+    /// The distance to the next glyph, including kerning.
+    pub advance: i32,
+}
+
+/// Converts text into glyphs placed along a line, in units of 1/65536 of the point size.
+///
+/// Characters are composed into precomposed forms that the font has glyphs for, or
+/// decomposed into base characters and combining marks when it only has those, so that
+/// both forms of a character like "é" draw the same. Right-to-left text is put in
+/// visual order with `visual_order()`, and adjacent glyphs are kerned in that order.
+pub fn shape<F: GlyphSource + ?Sized>(s: &str, f: &F) -> Vec<PlacedGlyph> {
+    shape_visual(&visual_order(s), f)
+}
+
+/// Converts text that is already in visual order into glyphs, as `shape()` does but
+/// without reordering it.
+pub(crate) fn shape_visual<F: GlyphSource + ?Sized>(s: &str, f: &F) -> Vec<PlacedGlyph> {
+    let composed: String = s.nfc().collect();
+    let mut characters = Vec::new();
+    for c in composed.chars() {
+        if f.has_glyph(c) {
+            characters.push(c);
+            continue;
+        }
+        let mut parts = Vec::new();
+        decompose_canonical(c, |d| parts.push(d));
+        if parts.len() > 1 && parts.iter().all(|&d| f.has_glyph(d)) {
+            characters.extend(parts);
+        } else {
+            characters.push(c);
+        }
+    }
+
+    let mut glyphs: Vec<PlacedGlyph> = Vec::with_capacity(characters.len());
+    let mut x = 0;
+    for (i, &c) in characters.iter().enumerate() {
+        let glyph = match f.glyph(c) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let advance = glyph.advance;
+        if is_combining_mark(c) && advance != 0 {
+            // Marks are usually drawn to the left of the pen with no advance, but some
+            // fonts give them a width; right-align those with the previous glyph.
+            if let Some(previous) = glyphs.last().cloned() {
+                glyphs.push(PlacedGlyph {
+                    c: c,
+                    glyph: glyph,
+                    x: previous.x + previous.advance - advance,
+                    advance: 0,
+                });
+                continue;
+            }
+        }
+        let kerning = match characters.get(i + 1) {
+            Some(&next) => f.kerning(c, next),
+            None => 0,
+        };
+        glyphs.push(PlacedGlyph {
+            c: c,
+            glyph: glyph,
+            x: x,
+            advance: advance + kerning,
+        });
+        x += advance + kerning;
+    }
+    glyphs
+}
+
+/// A list of fonts searched in order for each character.
+///
+/// Characters that none of the fonts have are drawn as the first font's missing glyph.
+/// Heights are the largest of the fonts so that mixed text measures consistently.
+pub struct FontFamily<'a> {
+    fonts: Vec<&'a Font<'a>>,
+}
+
+impl<'a> FontFamily<'a> {
+    /// Creates a new FontFamily with a primary font.
+    pub fn new(primary: &'a Font<'a>) -> FontFamily<'a> {
+        FontFamily {
+            fonts: vec![primary],
+        }
+    }
+
+    /// Adds a font to search when the fonts before it have no glyph for a character.
+    pub fn fallback(mut self, font: &'a Font<'a>) -> FontFamily<'a> {
+        self.fonts.push(font);
+        self
+    }
+
+    /// Returns the fonts in search order.
+    pub fn fonts(&self) -> &[&'a Font<'a>] {
+        &self.fonts
+    }
+
+    /// Returns the first font with a glyph for a character.
+    pub fn font_for(&self, c: char) -> Option<&'a Font<'a>> {
+        for font in self.fonts.iter() {
+            if font.has_glyph(c) {
+                return Some(*font);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> GlyphSource for FontFamily<'a> {
+    fn glyph(&self, c: char) -> Option<Glyph> {
+        match self.font_for(c) {
+            Some(font) => font.glyph(c),
+            None => self.fonts[0].glyph(c),
+        }
+    }
+
+    fn has_glyph(&self, c: char) -> bool {
+        self.font_for(c).is_some()
+    }
+
+    /// Kerns characters drawn with the same font.
+    fn kerning(&self, left: char, right: char) -> i32 {
+        match (self.font_for(left), self.font_for(right)) {
+            (Some(l), Some(r)) if l as *const Font == r as *const Font => l.kerning(left, right),
+            _ => 0,
+        }
+    }
+
+    fn font_height(&self) -> i32 {
+        let ascent = self
+            .fonts
+            .iter()
+            .map(|f| f.font_height + f.descender_height)
+            .max()
+            .unwrap_or(0);
+        ascent - self.descender_height()
+    }
+
+    fn descender_height(&self) -> i32 {
+        self.fonts
+            .iter()
+            .map(|f| f.descender_height)
+            .min()
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,1465 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of TrueType and OpenType font files.
+//!
+//! This module only depends on the standard library so that tools can share it.
+
+#![allow(dead_code)]
+
+use std::collections::{HashMap, HashSet};
+
+/// Path commands, numbered like the absolute OpenVG path segment commands.
+pub const CLOSE_PATH: u8 = 0;
+pub const MOVE_TO: u8 = 2;
+pub const LINE_TO: u8 = 4;
+pub const QUAD_TO: u8 = 10;
+pub const CUBIC_TO: u8 = 12;
+
+/// Composite glyphs and subroutines can't nest deeper than this.
+const MAX_DEPTH: usize = 10;
+
+/// A glyph outline in font units.
+#[derive(Clone, Debug, Default)]
+pub struct Outline {
+    /// Path commands.
+    pub commands: Vec<u8>,
+    /// Coordinates used by the commands, as x, y pairs.
+    pub points: Vec<f32>,
+    open: bool,
+}
+
+impl Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.commands.push(MOVE_TO);
+        self.points.extend_from_slice(&[x, y]);
+        self.open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.commands.push(LINE_TO);
+        self.points.extend_from_slice(&[x, y]);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.commands.push(QUAD_TO);
+        self.points.extend_from_slice(&[x1, y1, x, y]);
+    }
+
+    fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.commands.push(CUBIC_TO);
+        self.points.extend_from_slice(&[x1, y1, x2, y2, x, y]);
+    }
+
+    fn close(&mut self) {
+        if self.open {
+            self.commands.push(CLOSE_PATH);
+            self.open = false;
+        }
+    }
+
+    /// Appends another outline mapped through the matrix [a b c d e f].
+    fn append(&mut self, other: &Outline, m: &[f32; 6]) {
+        self.close();
+        self.commands.extend_from_slice(&other.commands);
+        for p in other.points.chunks(2) {
+            self.points.push(m[0] * p[0] + m[2] * p[1] + m[4]);
+            self.points.push(m[1] * p[0] + m[3] * p[1] + m[5]);
+        }
+    }
+
+    /// Returns true if the outline draws nothing.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+fn truncated() -> String {
+    "font data is truncated".to_string()
+}
+
+fn read_u8(data: &[u8], offset: usize) -> Result<u8, String> {
+    data.get(offset).cloned().ok_or_else(truncated)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    if offset + 2 > data.len() {
+        return Err(truncated());
+    }
+    Ok((data[offset] as u16) << 8 | data[offset + 1] as u16)
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Result<i16, String> {
+    read_u16(data, offset).map(|v| v as i16)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    Ok((read_u16(data, offset)? as u32) << 16 | read_u16(data, offset + 2)? as u32)
+}
+
+/// Reads an unsigned integer of 1 to 4 bytes.
+fn read_offset(data: &[u8], offset: usize, size: usize) -> Result<usize, String> {
+    let mut v = 0;
+    for i in 0..size {
+        v = v << 8 | read_u8(data, offset + i)? as usize;
+    }
+    Ok(v)
+}
+
+fn slice(data: &[u8], start: usize, end: usize) -> Result<&[u8], String> {
+    if start > end || end > data.len() {
+        return Err(truncated());
+    }
+    Ok(&data[start..end])
+}
+
+/// Where glyph outlines are stored.
+enum Outlines<'a> {
+    TrueType {
+        loca: &'a [u8],
+        glyf: &'a [u8],
+        long_offsets: bool,
+    },
+    Cff(Cff<'a>),
+}
+
+/// A parsed TrueType or OpenType font.
+pub struct FontFile<'a> {
+    /// The number of font units in an em.
+    pub units_per_em: u16,
+    /// Distance from the baseline to the top of the tallest glyphs, in font units.
+    pub ascender: i16,
+    /// Distance from the baseline to the bottom of the lowest glyphs (negative).
+    pub descender: i16,
+    /// Extra space between lines, in font units.
+    pub line_gap: i16,
+    /// The number of glyphs in the font.
+    pub glyph_count: u16,
+    /// The family name, if the font has one.
+    pub family_name: Option<String>,
+    /// The style name, if the font has one.
+    pub style_name: Option<String>,
+    hmtx: &'a [u8],
+    metric_count: u16,
+    mappings: Vec<(u32, u16)>,
+    outlines: Outlines<'a>,
+    tables: Vec<([u8; 4], &'a [u8])>,
+}
+
+impl<'a> FontFile<'a> {
+    /// Parses the first font in a TrueType, OpenType or collection file.
+    pub fn parse(data: &'a [u8]) -> Result<FontFile<'a>, String> {
+        let mut start = 0;
+        if data.starts_with(b"ttcf") {
+            start = read_u32(data, 12)? as usize;
+        }
+        let version = read_u32(data, start)?;
+        if version != 0x00010000 && version != 0x4F54544F && version != 0x74727565 {
+            return Err("not a TrueType or OpenType font".to_string());
+        }
+        let table_count = read_u16(data, start + 4)? as usize;
+        let mut tables = Vec::new();
+        for i in 0..table_count {
+            let record = start + 12 + i * 16;
+            let tag = slice(data, record, record + 4)?;
+            let offset = read_u32(data, record + 8)? as usize;
+            let length = read_u32(data, record + 12)? as usize;
+            tables.push((
+                [tag[0], tag[1], tag[2], tag[3]],
+                slice(data, offset, offset + length)?,
+            ));
+        }
+
+        let mut file = FontFile {
+            units_per_em: 0,
+            ascender: 0,
+            descender: 0,
+            line_gap: 0,
+            glyph_count: 0,
+            family_name: None,
+            style_name: None,
+            hmtx: &[],
+            metric_count: 0,
+            mappings: Vec::new(),
+            outlines: Outlines::TrueType {
+                loca: &[],
+                glyf: &[],
+                long_offsets: false,
+            },
+            tables: tables,
+        };
+
+        let head = file.required_table(b"head")?;
+        file.units_per_em = read_u16(head, 18)?;
+        if file.units_per_em == 0 {
+            return Err("font has no units per em".to_string());
+        }
+        let long_offsets = read_i16(head, 50)? != 0;
+
+        let hhea = file.required_table(b"hhea")?;
+        file.ascender = read_i16(hhea, 4)?;
+        file.descender = read_i16(hhea, 6)?;
+        file.line_gap = read_i16(hhea, 8)?;
+        file.metric_count = read_u16(hhea, 34)?;
+        file.hmtx = file.required_table(b"hmtx")?;
+        file.glyph_count = read_u16(file.required_table(b"maxp")?, 4)?;
+
+        file.mappings = parse_cmap(file.required_table(b"cmap")?, file.glyph_count)?;
+        if let Some(name) = file.table(b"name") {
+            file.family_name = parse_name(name, 1);
+            file.style_name = parse_name(name, 2);
+        }
+
+        file.outlines = if let Some(cff) = file.table(b"CFF ") {
+            Outlines::Cff(Cff::parse(cff)?)
+        } else {
+            Outlines::TrueType {
+                loca: file.required_table(b"loca")?,
+                glyf: file.required_table(b"glyf")?,
+                long_offsets: long_offsets,
+            }
+        };
+        Ok(file)
+    }
+
+    /// Returns the contents of a table.
+    pub fn table(&self, tag: &[u8; 4]) -> Option<&'a [u8]> {
+        for &(ref t, data) in self.tables.iter() {
+            if t == tag {
+                return Some(data);
+            }
+        }
+        None
+    }
+
+    fn required_table(&self, tag: &[u8; 4]) -> Result<&'a [u8], String> {
+        match self.table(tag) {
+            Some(data) => Ok(data),
+            None => Err(format!(
+                "font has no {} table",
+                String::from_utf8_lossy(tag).trim()
+            )),
+        }
+    }
+
+    /// Returns the character to glyph mappings, sorted by character.
+    pub fn mappings(&self) -> &[(u32, u16)] {
+        &self.mappings
+    }
+
+    /// Returns the glyph for a character.
+    pub fn glyph_index(&self, c: u32) -> Option<u16> {
+        match self.mappings.binary_search_by_key(&c, |&(c, _)| c) {
+            Ok(i) => Some(self.mappings[i].1),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns the advance width of a glyph in font units.
+    pub fn advance(&self, glyph: u16) -> u16 {
+        if self.metric_count == 0 {
+            return 0;
+        }
+        let i = (glyph as usize).min(self.metric_count as usize - 1);
+        read_u16(self.hmtx, i * 4).unwrap_or(0)
+    }
+
+    /// Returns the horizontal kerning from the GPOS table, or the kern table if the
+    /// font has no GPOS kerning.
+    pub fn kerning(&self) -> Kerning {
+        let mut kerning = Kerning::default();
+        if let Some(gpos) = self.table(b"GPOS") {
+            if parse_gpos(gpos, &mut kerning).is_err() {
+                kerning = Kerning::default();
+            }
+        }
+        if kerning.is_empty() {
+            if let Some(kern) = self.table(b"kern") {
+                if parse_kern(kern, &mut kerning).is_err() {
+                    kerning = Kerning::default();
+                }
+            }
+        }
+        kerning
+    }
+
+    /// Returns the outline of a glyph in font units.
+    pub fn outline(&self, glyph: u16) -> Result<Outline, String> {
+        if glyph >= self.glyph_count {
+            return Err(format!("glyph {} is out of range", glyph));
+        }
+        let mut outline = Outline::default();
+        match self.outlines {
+            Outlines::TrueType {
+                loca,
+                glyf,
+                long_offsets,
+            } => glyf_outline(loca, glyf, long_offsets, glyph, 0, &mut outline)?,
+            Outlines::Cff(ref cff) => cff.outline(glyph, &mut outline)?,
+        }
+        outline.close();
+        Ok(outline)
+    }
+}
+
+//
+// Character maps
+//
+
+fn parse_cmap(cmap: &[u8], glyph_count: u16) -> Result<Vec<(u32, u16)>, String> {
+    // Prefer full Unicode tables, then BMP tables, then symbol and Macintosh tables.
+    let mut best = None;
+    let mut best_score = 0;
+    let count = read_u16(cmap, 2)? as usize;
+    for i in 0..count {
+        let record = 4 + i * 8;
+        let platform = read_u16(cmap, record)?;
+        let encoding = read_u16(cmap, record + 2)?;
+        let offset = read_u32(cmap, record + 4)? as usize;
+        let format = read_u16(cmap, offset)?;
+        let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+        let score = match format {
+            12 if unicode => 5,
+            4 if unicode => 4,
+            4 if platform == 3 && encoding == 0 => 3,
+            6 if unicode => 2,
+            0 => 1,
+            _ => 0,
+        };
+        if score > best_score {
+            best = Some(offset);
+            best_score = score;
+        }
+    }
+    let offset = match best {
+        Some(offset) => offset,
+        None => return Err("font has no supported character map".to_string()),
+    };
+
+    let mut mappings = Vec::new();
+    match read_u16(cmap, offset)? {
+        0 => {
+            for c in 0..256 {
+                mappings.push((c as u32, read_u8(cmap, offset + 6 + c)? as u16));
+            }
+        }
+        4 => {
+            let segments = read_u16(cmap, offset + 6)? as usize / 2;
+            let ends = offset + 14;
+            let starts = ends + segments * 2 + 2;
+            let deltas = starts + segments * 2;
+            let range_offsets = deltas + segments * 2;
+            for s in 0..segments {
+                let end = read_u16(cmap, ends + s * 2)? as u32;
+                let start = read_u16(cmap, starts + s * 2)? as u32;
+                let delta = read_u16(cmap, deltas + s * 2)? as u32;
+                let range_offset = read_u16(cmap, range_offsets + s * 2)? as usize;
+                for c in start..(end + 1) {
+                    if c == 0xFFFF {
+                        break;
+                    }
+                    let glyph = if range_offset == 0 {
+                        (c + delta) & 0xFFFF
+                    } else {
+                        let address =
+                            range_offsets + s * 2 + range_offset + (c - start) as usize * 2;
+                        match read_u16(cmap, address)? as u32 {
+                            0 => 0,
+                            g => (g + delta) & 0xFFFF,
+                        }
+                    };
+                    mappings.push((c, glyph as u16));
+                }
+            }
+        }
+        6 => {
+            let first = read_u16(cmap, offset + 6)? as u32;
+            let count = read_u16(cmap, offset + 8)? as usize;
+            for i in 0..count {
+                mappings.push((first + i as u32, read_u16(cmap, offset + 10 + i * 2)?));
+            }
+        }
+        _ => {
+            let groups = read_u32(cmap, offset + 12)? as usize;
+            for i in 0..groups {
+                let group = offset + 16 + i * 12;
+                let start = read_u32(cmap, group)?;
+                let end = read_u32(cmap, group + 4)?.min(0x10FFFF);
+                let glyph = read_u32(cmap, group + 8)?;
+                for c in start..(end + 1) {
+                    let g = glyph + (c - start);
+                    if g >= glyph_count as u32 {
+                        break;
+                    }
+                    mappings.push((c, g as u16));
+                }
+            }
+        }
+    }
+    mappings.retain(|&(_, g)| g != 0 && g < glyph_count);
+    mappings.sort();
+    mappings.dedup_by_key(|m| m.0);
+    Ok(mappings)
+}
+
+/// Reads a name from the naming table, preferring Windows Unicode names.
+fn parse_name(name: &[u8], id: u16) -> Option<String> {
+    let count = read_u16(name, 2).ok()? as usize;
+    let storage = read_u16(name, 4).ok()? as usize;
+    let mut found = None;
+    for i in 0..count {
+        let record = 6 + i * 12;
+        let platform = read_u16(name, record).ok()?;
+        if read_u16(name, record + 6).ok()? != id {
+            continue;
+        }
+        let length = read_u16(name, record + 8).ok()? as usize;
+        let offset = storage + read_u16(name, record + 10).ok()? as usize;
+        let bytes = slice(name, offset, offset + length).ok()?;
+        if platform == 0 || platform == 3 {
+            let units: Vec<u16> = bytes
+                .chunks(2)
+                .filter(|c| c.len() == 2)
+                .map(|c| (c[0] as u16) << 8 | c[1] as u16)
+                .collect();
+            return String::from_utf16(&units).ok();
+        } else if platform == 1 && found.is_none() {
+            found = Some(bytes.iter().map(|&b| b as char).collect());
+        }
+    }
+    found
+}
+
+//
+// Kerning
+//
+
+/// Kerning adjustments between pairs of glyphs, in font units.
+#[derive(Clone, Debug, Default)]
+pub struct Kerning {
+    /// Each lookup is a list of subtables; the first that applies to a pair is used.
+    lookups: Vec<Vec<PairSubtable>>,
+}
+
+/// A subtable of pair adjustments.
+#[derive(Clone, Debug)]
+enum PairSubtable {
+    /// Adjustments for individual pairs.
+    Pairs(HashMap<(u16, u16), i16>),
+    /// Adjustments between classes of glyphs.
+    Classes {
+        coverage: HashSet<u16>,
+        first_classes: HashMap<u16, u16>,
+        second_classes: HashMap<u16, u16>,
+        second_class_count: usize,
+        values: Vec<i16>,
+    },
+}
+
+impl PairSubtable {
+    fn lookup(&self, left: u16, right: u16) -> Option<i16> {
+        match *self {
+            PairSubtable::Pairs(ref pairs) => pairs.get(&(left, right)).cloned(),
+            PairSubtable::Classes {
+                ref coverage,
+                ref first_classes,
+                ref second_classes,
+                second_class_count,
+                ref values,
+            } => {
+                if !coverage.contains(&left) {
+                    return None;
+                }
+                let first = first_classes.get(&left).cloned().unwrap_or(0) as usize;
+                let second = second_classes.get(&right).cloned().unwrap_or(0) as usize;
+                values.get(first * second_class_count + second).cloned()
+            }
+        }
+    }
+}
+
+impl Kerning {
+    /// Returns true if no pairs are kerned.
+    pub fn is_empty(&self) -> bool {
+        self.lookups.iter().all(|l| l.is_empty())
+    }
+
+    /// Returns the adjustment to the advance of left when it is followed by right.
+    pub fn lookup(&self, left: u16, right: u16) -> i16 {
+        let mut total = 0;
+        for subtables in self.lookups.iter() {
+            for subtable in subtables.iter() {
+                if let Some(value) = subtable.lookup(left, right) {
+                    total += value;
+                    break;
+                }
+            }
+        }
+        total
+    }
+}
+
+fn parse_kern(kern: &[u8], kerning: &mut Kerning) -> Result<(), String> {
+    // Only the Microsoft version of the table, with format 0 subtables, is read.
+    if read_u16(kern, 0)? != 0 {
+        return Ok(());
+    }
+    let count = read_u16(kern, 2)? as usize;
+    let mut offset = 4;
+    for _ in 0..count {
+        let length = read_u16(kern, offset + 2)? as usize;
+        let coverage = read_u16(kern, offset + 4)?;
+        // Horizontal, not minimum or cross-stream, format 0.
+        if coverage & 0xFF07 == 0x0001 {
+            let pair_count = read_u16(kern, offset + 6)? as usize;
+            let mut pairs = HashMap::new();
+            for i in 0..pair_count {
+                let pair = offset + 14 + i * 6;
+                pairs.insert(
+                    (read_u16(kern, pair)?, read_u16(kern, pair + 2)?),
+                    read_i16(kern, pair + 4)?,
+                );
+            }
+            kerning.lookups.push(vec![PairSubtable::Pairs(pairs)]);
+        }
+        offset += length.max(6);
+    }
+    Ok(())
+}
+
+/// Returns the glyphs listed in a coverage table, in coverage index order.
+fn parse_coverage(data: &[u8], offset: usize) -> Result<Vec<u16>, String> {
+    let mut glyphs = Vec::new();
+    match read_u16(data, offset)? {
+        1 => {
+            let count = read_u16(data, offset + 2)? as usize;
+            for i in 0..count {
+                glyphs.push(read_u16(data, offset + 4 + i * 2)?);
+            }
+        }
+        2 => {
+            let count = read_u16(data, offset + 2)? as usize;
+            for i in 0..count {
+                let range = offset + 4 + i * 6;
+                let start = read_u16(data, range)?;
+                let end = read_u16(data, range + 2)?;
+                for g in start as u32..end as u32 + 1 {
+                    glyphs.push(g as u16);
+                }
+            }
+        }
+        _ => return Err("unsupported coverage format".to_string()),
+    }
+    Ok(glyphs)
+}
+
+/// Returns the non-zero classes assigned by a class definition table.
+fn parse_class_def(data: &[u8], offset: usize) -> Result<HashMap<u16, u16>, String> {
+    let mut classes = HashMap::new();
+    match read_u16(data, offset)? {
+        1 => {
+            let start = read_u16(data, offset + 2)? as u32;
+            let count = read_u16(data, offset + 4)? as usize;
+            for i in 0..count {
+                let class = read_u16(data, offset + 6 + i * 2)?;
+                if class != 0 {
+                    classes.insert((start + i as u32) as u16, class);
+                }
+            }
+        }
+        2 => {
+            let count = read_u16(data, offset + 2)? as usize;
+            for i in 0..count {
+                let range = offset + 4 + i * 6;
+                let start = read_u16(data, range)?;
+                let end = read_u16(data, range + 2)?;
+                let class = read_u16(data, range + 4)?;
+                for g in start as u32..end as u32 + 1 {
+                    classes.insert(g as u16, class);
+                }
+            }
+        }
+        _ => return Err("unsupported class definition format".to_string()),
+    }
+    Ok(classes)
+}
+
+/// Returns the size of a value record and the offset of its x advance, if it has one.
+fn value_record(format: u16) -> (usize, Option<usize>) {
+    let size = (format & 0xFF).count_ones() as usize * 2;
+    if format & 4 != 0 {
+        (size, Some((format & 3).count_ones() as usize * 2))
+    } else {
+        (size, None)
+    }
+}
+
+fn parse_pair_subtable(data: &[u8], offset: usize) -> Result<Option<PairSubtable>, String> {
+    let format = read_u16(data, offset)?;
+    let coverage = parse_coverage(data, offset + read_u16(data, offset + 2)? as usize)?;
+    let (size1, advance) = value_record(read_u16(data, offset + 4)?);
+    let (size2, _) = value_record(read_u16(data, offset + 6)?);
+    let advance = match advance {
+        Some(advance) => advance,
+        None => return Ok(None),
+    };
+    match format {
+        1 => {
+            let mut pairs = HashMap::new();
+            let set_count = read_u16(data, offset + 8)? as usize;
+            for (i, &first) in coverage.iter().enumerate().take(set_count) {
+                let set = offset + read_u16(data, offset + 10 + i * 2)? as usize;
+                let count = read_u16(data, set)? as usize;
+                for j in 0..count {
+                    let record = set + 2 + j * (2 + size1 + size2);
+                    let second = read_u16(data, record)?;
+                    pairs.insert((first, second), read_i16(data, record + 2 + advance)?);
+                }
+            }
+            Ok(Some(PairSubtable::Pairs(pairs)))
+        }
+        2 => {
+            let first_classes =
+                parse_class_def(data, offset + read_u16(data, offset + 8)? as usize)?;
+            let second_classes =
+                parse_class_def(data, offset + read_u16(data, offset + 10)? as usize)?;
+            let first_count = read_u16(data, offset + 12)? as usize;
+            let second_count = read_u16(data, offset + 14)? as usize;
+            let mut values = Vec::with_capacity(first_count * second_count);
+            for i in 0..first_count * second_count {
+                let record = offset + 16 + i * (size1 + size2);
+                values.push(read_i16(data, record + advance)?);
+            }
+            Ok(Some(PairSubtable::Classes {
+                coverage: coverage.into_iter().collect(),
+                first_classes: first_classes,
+                second_classes: second_classes,
+                second_class_count: second_count,
+                values: values,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_gpos(gpos: &[u8], kerning: &mut Kerning) -> Result<(), String> {
+    // Use the lookups of every kern feature, whatever script or language it is for.
+    let features = read_u16(gpos, 6)? as usize;
+    let lookups = read_u16(gpos, 8)? as usize;
+    let mut indices = Vec::new();
+    for i in 0..read_u16(gpos, features)? as usize {
+        let record = features + 2 + i * 6;
+        if slice(gpos, record, record + 4)? != b"kern" {
+            continue;
+        }
+        let feature = features + read_u16(gpos, record + 4)? as usize;
+        for j in 0..read_u16(gpos, feature + 2)? as usize {
+            indices.push(read_u16(gpos, feature + 4 + j * 2)? as usize);
+        }
+    }
+    indices.sort();
+    indices.dedup();
+
+    for index in indices {
+        let lookup = lookups + read_u16(gpos, lookups + 2 + index * 2)? as usize;
+        let kind = read_u16(gpos, lookup)?;
+        let mut subtables = Vec::new();
+        for i in 0..read_u16(gpos, lookup + 4)? as usize {
+            let mut subtable = lookup + read_u16(gpos, lookup + 6 + i * 2)? as usize;
+            let mut subtable_kind = kind;
+            if kind == 9 {
+                // Extension subtables point to the real subtable with a 32-bit offset.
+                subtable_kind = read_u16(gpos, subtable + 2)?;
+                subtable += read_u32(gpos, subtable + 4)? as usize;
+            }
+            if subtable_kind == 2 {
+                if let Some(pairs) = parse_pair_subtable(gpos, subtable)? {
+                    subtables.push(pairs);
+                }
+            }
+        }
+        if !subtables.is_empty() {
+            kerning.lookups.push(subtables);
+        }
+    }
+    Ok(())
+}
+
+//
+// TrueType outlines
+//
+
+fn glyf_outline(
+    loca: &[u8],
+    glyf: &[u8],
+    long_offsets: bool,
+    glyph: u16,
+    depth: usize,
+    outline: &mut Outline,
+) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("composite glyphs are nested too deeply".to_string());
+    }
+    let i = glyph as usize;
+    let (start, end) = if long_offsets {
+        (
+            read_u32(loca, i * 4)? as usize,
+            read_u32(loca, i * 4 + 4)? as usize,
+        )
+    } else {
+        (
+            read_u16(loca, i * 2)? as usize * 2,
+            read_u16(loca, i * 2 + 2)? as usize * 2,
+        )
+    };
+    if end <= start {
+        // Glyphs like the space have no outline.
+        return Ok(());
+    }
+    let data = slice(glyf, start, end)?;
+    let contours = read_i16(data, 0)?;
+    if contours >= 0 {
+        simple_glyph(data, contours as usize, outline)
+    } else {
+        composite_glyph(data, loca, glyf, long_offsets, depth, outline)
+    }
+}
+
+fn simple_glyph(data: &[u8], contours: usize, outline: &mut Outline) -> Result<(), String> {
+    let mut ends = Vec::with_capacity(contours);
+    for i in 0..contours {
+        ends.push(read_u16(data, 10 + i * 2)? as usize);
+    }
+    let point_count = match ends.last() {
+        Some(&last) => last + 1,
+        None => return Ok(()),
+    };
+    let instruction_length = read_u16(data, 10 + contours * 2)? as usize;
+    let mut offset = 12 + contours * 2 + instruction_length;
+
+    let mut flags = Vec::with_capacity(point_count);
+    while flags.len() < point_count {
+        let flag = read_u8(data, offset)?;
+        offset += 1;
+        flags.push(flag);
+        if flag & 8 != 0 {
+            let repeat = read_u8(data, offset)?;
+            offset += 1;
+            for _ in 0..repeat {
+                flags.push(flag);
+            }
+        }
+    }
+    flags.truncate(point_count);
+
+    // Coordinates are deltas, short ones carrying their sign in the flags.
+    let mut xs = Vec::with_capacity(point_count);
+    let mut v: i32 = 0;
+    for &flag in flags.iter() {
+        if flag & 2 != 0 {
+            let d = read_u8(data, offset)? as i32;
+            offset += 1;
+            v += if flag & 16 != 0 { d } else { -d };
+        } else if flag & 16 == 0 {
+            v += read_i16(data, offset)? as i32;
+            offset += 2;
+        }
+        xs.push(v as f32);
+    }
+    let mut ys = Vec::with_capacity(point_count);
+    v = 0;
+    for &flag in flags.iter() {
+        if flag & 4 != 0 {
+            let d = read_u8(data, offset)? as i32;
+            offset += 1;
+            v += if flag & 32 != 0 { d } else { -d };
+        } else if flag & 32 == 0 {
+            v += read_i16(data, offset)? as i32;
+            offset += 2;
+        }
+        ys.push(v as f32);
+    }
+
+    let mut first = 0;
+    for &end in ends.iter() {
+        if end < first || end >= point_count {
+            return Err("glyph contours are malformed".to_string());
+        }
+        let points: Vec<(f32, f32, bool)> = (first..end + 1)
+            .map(|i| (xs[i], ys[i], flags[i] & 1 != 0))
+            .collect();
+        contour(&points, outline);
+        first = end + 1;
+    }
+    Ok(())
+}
+
+/// Appends a contour of quadratic B-spline points, implying on-curve points midway
+/// between consecutive off-curve points.
+fn contour(points: &[(f32, f32, bool)], outline: &mut Outline) {
+    let n = points.len();
+    if n == 0 {
+        return;
+    }
+    let mid = |a: (f32, f32, bool), b: (f32, f32, bool)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    let first = points[0];
+    let last = points[n - 1];
+    let (start, rest) = if first.2 {
+        ((first.0, first.1), &points[1..])
+    } else if last.2 {
+        ((last.0, last.1), &points[..n - 1])
+    } else {
+        (mid(first, last), points)
+    };
+    outline.move_to(start.0, start.1);
+    let mut control: Option<(f32, f32, bool)> = None;
+    for &p in rest.iter() {
+        if p.2 {
+            match control {
+                Some(c) => outline.quad_to(c.0, c.1, p.0, p.1),
+                None => outline.line_to(p.0, p.1),
+            }
+            control = None;
+        } else {
+            if let Some(c) = control {
+                let m = mid(c, p);
+                outline.quad_to(c.0, c.1, m.0, m.1);
+            }
+            control = Some(p);
+        }
+    }
+    if let Some(c) = control {
+        outline.quad_to(c.0, c.1, start.0, start.1);
+    }
+    outline.close();
+}
+
+fn f2dot14(data: &[u8], offset: usize) -> Result<f32, String> {
+    Ok(read_i16(data, offset)? as f32 / 16384.0)
+}
+
+fn composite_glyph(
+    data: &[u8],
+    loca: &[u8],
+    glyf: &[u8],
+    long_offsets: bool,
+    depth: usize,
+    outline: &mut Outline,
+) -> Result<(), String> {
+    let mut offset = 10;
+    loop {
+        let flags = read_u16(data, offset)?;
+        let glyph = read_u16(data, offset + 2)?;
+        offset += 4;
+        let (dx, dy) = if flags & 1 != 0 {
+            let args = (
+                read_i16(data, offset)? as f32,
+                read_i16(data, offset + 2)? as f32,
+            );
+            offset += 4;
+            args
+        } else {
+            let args = (
+                read_u8(data, offset)? as i8 as f32,
+                read_u8(data, offset + 1)? as i8 as f32,
+            );
+            offset += 2;
+            args
+        };
+        let mut m = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+        if flags & 2 != 0 {
+            // Otherwise the arguments match points, which we don't support.
+            m[4] = dx;
+            m[5] = dy;
+        }
+        if flags & 8 != 0 {
+            let s = f2dot14(data, offset)?;
+            m[0] = s;
+            m[3] = s;
+            offset += 2;
+        } else if flags & 0x40 != 0 {
+            m[0] = f2dot14(data, offset)?;
+            m[3] = f2dot14(data, offset + 2)?;
+            offset += 4;
+        } else if flags & 0x80 != 0 {
+            m[0] = f2dot14(data, offset)?;
+            m[1] = f2dot14(data, offset + 2)?;
+            m[2] = f2dot14(data, offset + 4)?;
+            m[3] = f2dot14(data, offset + 6)?;
+            offset += 8;
+        }
+        let mut component = Outline::default();
+        glyf_outline(loca, glyf, long_offsets, glyph, depth + 1, &mut component)?;
+        component.close();
+        outline.append(&component, &m);
+        if flags & 0x20 == 0 {
+            return Ok(());
+        }
+    }
+}
+
+//
+// CFF outlines
+//
+
+/// An INDEX structure: a counted list of byte strings.
+#[derive(Clone, Copy)]
+struct Index<'a> {
+    data: &'a [u8],
+    count: usize,
+    offset_size: usize,
+    /// The end of the index within the data it was read from.
+    end: usize,
+}
+
+impl<'a> Index<'a> {
+    fn empty() -> Index<'a> {
+        Index {
+            data: &[],
+            count: 0,
+            offset_size: 1,
+            end: 0,
+        }
+    }
+
+    fn parse(data: &'a [u8], offset: usize) -> Result<Index<'a>, String> {
+        let count = read_u16(data, offset)? as usize;
+        if count == 0 {
+            return Ok(Index {
+                data: &[],
+                count: 0,
+                offset_size: 1,
+                end: offset + 2,
+            });
+        }
+        let offset_size = read_u8(data, offset + 2)? as usize;
+        let offsets = offset + 3;
+        let base = offsets + (count + 1) * offset_size - 1;
+        let last = read_offset(data, offsets + count * offset_size, offset_size)?;
+        let index = Index {
+            data: slice(data, offset, base + last)?,
+            count: count,
+            offset_size: offset_size,
+            end: base + last,
+        };
+        Ok(index)
+    }
+
+    fn get(&self, i: usize) -> Result<&'a [u8], String> {
+        if i >= self.count {
+            return Err("CFF index is out of range".to_string());
+        }
+        let offsets = 3;
+        let base = offsets + (self.count + 1) * self.offset_size - 1;
+        let start = read_offset(self.data, offsets + i * self.offset_size, self.offset_size)?;
+        let end = read_offset(
+            self.data,
+            offsets + (i + 1) * self.offset_size,
+            self.offset_size,
+        )?;
+        slice(self.data, base + start, base + end)
+    }
+}
+
+/// Parses a DICT into operator and operand pairs. Escaped operators are 1200 + code.
+fn parse_dict(data: &[u8]) -> Result<Vec<(u16, Vec<f64>)>, String> {
+    let mut entries = Vec::new();
+    let mut operands = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        let b0 = data[i];
+        i += 1;
+        match b0 {
+            0..=21 => {
+                let op = if b0 == 12 {
+                    i += 1;
+                    1200 + read_u8(data, i - 1)? as u16
+                } else {
+                    b0 as u16
+                };
+                entries.push((op, operands));
+                operands = Vec::new();
+            }
+            28 => {
+                operands.push(read_i16(data, i)? as f64);
+                i += 2;
+            }
+            29 => {
+                operands.push(read_u32(data, i)? as i32 as f64);
+                i += 4;
+            }
+            30 => {
+                // Real numbers are packed as nibbles ending with 0xf.
+                let mut s = String::new();
+                loop {
+                    let b = read_u8(data, i)?;
+                    i += 1;
+                    let mut done = false;
+                    for &nibble in [b >> 4, b & 15].iter() {
+                        match nibble {
+                            0..=9 => s.push((b'0' + nibble) as char),
+                            0xa => s.push('.'),
+                            0xb => s.push('E'),
+                            0xc => s.push_str("E-"),
+                            0xe => s.push('-'),
+                            0xf => done = true,
+                            _ => {}
+                        }
+                        if done {
+                            break;
+                        }
+                    }
+                    if done {
+                        break;
+                    }
+                }
+                operands.push(s.parse().unwrap_or(0.0));
+            }
+            32..=246 => operands.push(b0 as f64 - 139.0),
+            247..=250 => {
+                operands.push((b0 as f64 - 247.0) * 256.0 + read_u8(data, i)? as f64 + 108.0);
+                i += 1;
+            }
+            251..=254 => {
+                operands.push(-(b0 as f64 - 251.0) * 256.0 - read_u8(data, i)? as f64 - 108.0);
+                i += 1;
+            }
+            _ => return Err("CFF dictionary is malformed".to_string()),
+        }
+    }
+    Ok(entries)
+}
+
+fn dict_operands(dict: &[(u16, Vec<f64>)], op: u16) -> Option<&[f64]> {
+    for &(o, ref operands) in dict.iter() {
+        if o == op {
+            return Some(operands);
+        }
+    }
+    None
+}
+
+/// Finds the local subroutines of a Private DICT given as [size, offset].
+fn private_subrs<'a>(cff: &'a [u8], private: Option<&[f64]>) -> Result<Index<'a>, String> {
+    let private = match private {
+        Some(p) if p.len() == 2 => p,
+        _ => return Ok(Index::empty()),
+    };
+    let size = private[0] as usize;
+    let offset = private[1] as usize;
+    let dict = parse_dict(slice(cff, offset, offset + size)?)?;
+    match dict_operands(&dict, 19) {
+        Some(subrs) if subrs.len() == 1 => Index::parse(cff, offset + subrs[0] as usize),
+        _ => Ok(Index::empty()),
+    }
+}
+
+/// The Compact Font Format table of an OpenType font.
+struct Cff<'a> {
+    data: &'a [u8],
+    char_strings: Index<'a>,
+    global_subrs: Index<'a>,
+    /// Local subroutines for each font DICT.
+    local_subrs: Vec<Index<'a>>,
+    /// For CID-keyed fonts, the FDSelect table mapping glyphs to font DICTs.
+    fd_select: Option<usize>,
+}
+
+impl<'a> Cff<'a> {
+    fn parse(data: &'a [u8]) -> Result<Cff<'a>, String> {
+        let header_size = read_u8(data, 2)? as usize;
+        let names = Index::parse(data, header_size)?;
+        let top_dicts = Index::parse(data, names.end)?;
+        let strings = Index::parse(data, top_dicts.end)?;
+        let global_subrs = Index::parse(data, strings.end)?;
+        let top = parse_dict(top_dicts.get(0)?)?;
+
+        let char_strings = match dict_operands(&top, 17) {
+            Some(v) if v.len() == 1 => Index::parse(data, v[0] as usize)?,
+            _ => return Err("CFF font has no charstrings".to_string()),
+        };
+
+        let mut local_subrs = Vec::new();
+        let mut fd_select = None;
+        match (dict_operands(&top, 1236), dict_operands(&top, 1237)) {
+            (Some(fd_array), Some(select)) if fd_array.len() == 1 && select.len() == 1 => {
+                let fd_array = Index::parse(data, fd_array[0] as usize)?;
+                for i in 0..fd_array.count {
+                    let fd = parse_dict(fd_array.get(i)?)?;
+                    local_subrs.push(private_subrs(data, dict_operands(&fd, 18))?);
+                }
+                fd_select = Some(select[0] as usize);
+            }
+            _ => local_subrs.push(private_subrs(data, dict_operands(&top, 18))?),
+        }
+
+        Ok(Cff {
+            data: data,
+            char_strings: char_strings,
+            global_subrs: global_subrs,
+            local_subrs: local_subrs,
+            fd_select: fd_select,
+        })
+    }
+
+    /// Returns the index of the font DICT used by a glyph.
+    fn font_dict(&self, glyph: u16) -> Result<usize, String> {
+        let offset = match self.fd_select {
+            Some(offset) => offset,
+            None => return Ok(0),
+        };
+        match read_u8(self.data, offset)? {
+            0 => Ok(read_u8(self.data, offset + 1 + glyph as usize)? as usize),
+            3 => {
+                let ranges = read_u16(self.data, offset + 1)? as usize;
+                for i in 0..ranges {
+                    let range = offset + 3 + i * 3;
+                    let first = read_u16(self.data, range)?;
+                    let next = read_u16(self.data, range + 3)?;
+                    if glyph >= first && glyph < next {
+                        return Ok(read_u8(self.data, range + 2)? as usize);
+                    }
+                }
+                Err("glyph has no font dictionary".to_string())
+            }
+            _ => Err("unsupported CFF FDSelect format".to_string()),
+        }
+    }
+
+    fn outline(&self, glyph: u16, outline: &mut Outline) -> Result<(), String> {
+        let char_string = self.char_strings.get(glyph as usize)?;
+        let local_subrs = match self.local_subrs.get(self.font_dict(glyph)?) {
+            Some(subrs) => *subrs,
+            None => Index::empty(),
+        };
+        let mut interpreter = CharString {
+            global_subrs: self.global_subrs,
+            local_subrs: local_subrs,
+            stack: Vec::new(),
+            x: 0.0,
+            y: 0.0,
+            stems: 0,
+            width_seen: false,
+            outline: outline,
+        };
+        interpreter.run(char_string, 0)?;
+        Ok(())
+    }
+}
+
+/// Returns the bias added to subroutine numbers.
+fn subr_bias(subrs: &Index) -> i32 {
+    if subrs.count < 1240 {
+        107
+    } else if subrs.count < 33900 {
+        1131
+    } else {
+        32768
+    }
+}
+
+/// Interprets Type 2 charstrings.
+struct CharString<'a, 'b> {
+    global_subrs: Index<'a>,
+    local_subrs: Index<'a>,
+    stack: Vec<f32>,
+    x: f32,
+    y: f32,
+    stems: usize,
+    width_seen: bool,
+    outline: &'b mut Outline,
+}
+
+impl<'a, 'b> CharString<'a, 'b> {
+    /// Drops the advance width that may precede the first stack-clearing operator.
+    fn width(&mut self, has_width: bool) {
+        if !self.width_seen && has_width && !self.stack.is_empty() {
+            self.stack.remove(0);
+        }
+        self.width_seen = true;
+    }
+
+    fn line(&mut self, dx: f32, dy: f32) {
+        self.x += dx;
+        self.y += dy;
+        self.outline.line_to(self.x, self.y);
+    }
+
+    fn curve(&mut self, dx1: f32, dy1: f32, dx2: f32, dy2: f32, dx3: f32, dy3: f32) {
+        let x1 = self.x + dx1;
+        let y1 = self.y + dy1;
+        let x2 = x1 + dx2;
+        let y2 = y1 + dy2;
+        self.x = x2 + dx3;
+        self.y = y2 + dy3;
+        self.outline.cubic_to(x1, y1, x2, y2, self.x, self.y);
+    }
+
+    fn move_to(&mut self, dx: f32, dy: f32) {
+        self.x += dx;
+        self.y += dy;
+        self.outline.move_to(self.x, self.y);
+    }
+
+    /// Runs a charstring, returning true when endchar is reached.
+    fn run(&mut self, code: &[u8], depth: usize) -> Result<bool, String> {
+        if depth > MAX_DEPTH {
+            return Err("CFF subroutines are nested too deeply".to_string());
+        }
+        let mut i = 0;
+        while i < code.len() {
+            let b0 = code[i];
+            i += 1;
+            match b0 {
+                1 | 3 | 18 | 23 => {
+                    // hstem, vstem, hstemhm, vstemhm
+                    let odd = self.stack.len() % 2 == 1;
+                    self.width(odd);
+                    self.stems += self.stack.len() / 2;
+                    self.stack.clear();
+                }
+                19 | 20 => {
+                    // hintmask and cntrmask may follow implied vstem hints.
+                    let odd = self.stack.len() % 2 == 1;
+                    self.width(odd);
+                    self.stems += self.stack.len() / 2;
+                    self.stack.clear();
+                    i += (self.stems + 7) / 8;
+                }
+                21 => {
+                    let has_width = self.stack.len() > 2;
+                    self.width(has_width);
+                    let (dx, dy) = (self.arg(0)?, self.arg(1)?);
+                    self.move_to(dx, dy);
+                    self.stack.clear();
+                }
+                22 => {
+                    let has_width = self.stack.len() > 1;
+                    self.width(has_width);
+                    let dx = self.arg(0)?;
+                    self.move_to(dx, 0.0);
+                    self.stack.clear();
+                }
+                4 => {
+                    let has_width = self.stack.len() > 1;
+                    self.width(has_width);
+                    let dy = self.arg(0)?;
+                    self.move_to(0.0, dy);
+                    self.stack.clear();
+                }
+                5 => {
+                    let mut j = 0;
+                    while j + 2 <= self.stack.len() {
+                        let (dx, dy) = (self.stack[j], self.stack[j + 1]);
+                        self.line(dx, dy);
+                        j += 2;
+                    }
+                    self.stack.clear();
+                }
+                6 | 7 => {
+                    let mut horizontal = b0 == 6;
+                    for j in 0..self.stack.len() {
+                        let d = self.stack[j];
+                        if horizontal {
+                            self.line(d, 0.0);
+                        } else {
+                            self.line(0.0, d);
+                        }
+                        horizontal = !horizontal;
+                    }
+                    self.stack.clear();
+                }
+                8 => {
+                    let mut j = 0;
+                    while j + 6 <= self.stack.len() {
+                        let s = self.stack[j..j + 6].to_vec();
+                        self.curve(s[0], s[1], s[2], s[3], s[4], s[5]);
+                        j += 6;
+                    }
+                    self.stack.clear();
+                }
+                24 => {
+                    let mut j = 0;
+                    while j + 8 <= self.stack.len() {
+                        let s = self.stack[j..j + 6].to_vec();
+                        self.curve(s[0], s[1], s[2], s[3], s[4], s[5]);
+                        j += 6;
+                    }
+                    let (dx, dy) = (self.arg(j)?, self.arg(j + 1)?);
+                    self.line(dx, dy);
+                    self.stack.clear();
+                }
+                25 => {
+                    let mut j = 0;
+                    while j + 8 <= self.stack.len() {
+                        let (dx, dy) = (self.stack[j], self.stack[j + 1]);
+                        self.line(dx, dy);
+                        j += 2;
+                    }
+                    if j + 6 <= self.stack.len() {
+                        let s = self.stack[j..j + 6].to_vec();
+                        self.curve(s[0], s[1], s[2], s[3], s[4], s[5]);
+                    }
+                    self.stack.clear();
+                }
+                26 | 27 => {
+                    // vvcurveto and hhcurveto, with an optional first cross-axis delta.
+                    let mut j = 0;
+                    let mut d1 = 0.0;
+                    if self.stack.len() % 2 == 1 {
+                        d1 = self.stack[0];
+                        j = 1;
+                    }
+                    while j + 4 <= self.stack.len() {
+                        let s = self.stack[j..j + 4].to_vec();
+                        if b0 == 26 {
+                            self.curve(d1, s[0], s[1], s[2], 0.0, s[3]);
+                        } else {
+                            self.curve(s[0], d1, s[1], s[2], s[3], 0.0);
+                        }
+                        d1 = 0.0;
+                        j += 4;
+                    }
+                    self.stack.clear();
+                }
+                30 | 31 => {
+                    // vhcurveto and hvcurveto alternate between starting vertically and
+                    // horizontally; the last curve may end with an extra delta.
+                    let mut horizontal = b0 == 31;
+                    let mut j = 0;
+                    let n = self.stack.len();
+                    while j + 4 <= n {
+                        let s = self.stack[j..j + 4].to_vec();
+                        let last = if n - j == 5 { self.stack[j + 4] } else { 0.0 };
+                        if horizontal {
+                            self.curve(s[0], 0.0, s[1], s[2], last, s[3]);
+                        } else {
+                            self.curve(0.0, s[0], s[1], s[2], s[3], last);
+                        }
+                        horizontal = !horizontal;
+                        j += 4;
+                    }
+                    self.stack.clear();
+                }
+                10 | 29 => {
+                    let subrs = if b0 == 10 {
+                        self.local_subrs
+                    } else {
+                        self.global_subrs
+                    };
+                    let n = match self.stack.pop() {
+                        Some(n) => n as i32 + subr_bias(&subrs),
+                        None => return Err("CFF stack underflow".to_string()),
+                    };
+                    if n < 0 {
+                        return Err("CFF subroutine is out of range".to_string());
+                    }
+                    let subr = subrs.get(n as usize)?;
+                    if self.run(subr, depth + 1)? {
+                        return Ok(true);
+                    }
+                }
+                11 => return Ok(false),
+                14 => {
+                    let has_width = self.stack.len() == 1 || self.stack.len() == 5;
+                    self.width(has_width);
+                    self.outline.close();
+                    self.stack.clear();
+                    return Ok(true);
+                }
+                12 => {
+                    let b1 = read_u8(code, i)?;
+                    i += 1;
+                    self.flex(b1)?;
+                    self.stack.clear();
+                }
+                28 => {
+                    self.stack.push(read_i16(code, i)? as f32);
+                    i += 2;
+                }
+                32..=246 => self.stack.push(b0 as f32 - 139.0),
+                247..=250 => {
+                    let b1 = read_u8(code, i)? as f32;
+                    self.stack.push((b0 as f32 - 247.0) * 256.0 + b1 + 108.0);
+                    i += 1;
+                }
+                251..=254 => {
+                    let b1 = read_u8(code, i)? as f32;
+                    self.stack.push(-(b0 as f32 - 251.0) * 256.0 - b1 - 108.0);
+                    i += 1;
+                }
+                255 => {
+                    self.stack.push(read_u32(code, i)? as i32 as f32 / 65536.0);
+                    i += 4;
+                }
+                _ => self.stack.clear(),
+            }
+            if self.stack.len() > 513 {
+                return Err("CFF stack overflow".to_string());
+            }
+        }
+        Ok(false)
+    }
+
+    fn arg(&self, i: usize) -> Result<f32, String> {
+        match self.stack.get(i) {
+            Some(&v) => Ok(v),
+            None => Err("CFF stack underflow".to_string()),
+        }
+    }
+
+    /// Draws the flex operators as pairs of curves; other escaped operators are ignored.
+    fn flex(&mut self, op: u8) -> Result<(), String> {
+        let s = self.stack.clone();
+        let need = match op {
+            34 => 7,
+            35 => 13,
+            36 => 9,
+            37 => 11,
+            _ => return Ok(()),
+        };
+        if s.len() < need {
+            return Err("CFF stack underflow".to_string());
+        }
+        match op {
+            34 => {
+                self.curve(s[0], 0.0, s[1], s[2], s[3], 0.0);
+                self.curve(s[4], 0.0, s[5], -s[2], s[6], 0.0);
+            }
+            35 => {
+                self.curve(s[0], s[1], s[2], s[3], s[4], s[5]);
+                self.curve(s[6], s[7], s[8], s[9], s[10], s[11]);
+            }
+            36 => {
+                self.curve(s[0], s[1], s[2], s[3], s[4], 0.0);
+                self.curve(s[5], 0.0, s[6], s[7], s[8], -(s[1] + s[3] + s[7]));
+            }
+            _ => {
+                let (x0, y0) = (self.x, self.y);
+                self.curve(s[0], s[1], s[2], s[3], s[4], s[5]);
+                let dx = s[0] + s[2] + s[4] + s[6] + s[8];
+                let dy = s[1] + s[3] + s[5] + s[7] + s[9];
+                let (x1, y1) = (self.x + s[6] + s[8], self.y + s[7] + s[9]);
+                let (last_x, last_y) = if dx.abs() > dy.abs() {
+                    (s[10], y0 - y1)
+                } else {
+                    (x0 - x1, s[10])
+                };
+                self.curve(s[6], s[7], s[8], s[9], last_x, last_y);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,525 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Representation of OpenVG images and decoding of image files.
+
+use gif;
+use jpeg_decoder;
+use png;
+
+use openvg::*;
+
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Describes problems encountered when loading or creating images.
+#[derive(Debug)]
+pub enum Error {
+    /// The image data couldn't be read.
+    Io(io::Error),
+    /// The image data couldn't be decoded.
+    Decode(String),
+    /// The image data couldn't be encoded.
+    Encode(String),
+    /// The image data isn't PNG, JPEG or GIF.
+    UnknownFormat,
+    /// OpenVG couldn't create an image of the requested size and format.
+    Create,
+    /// A filter's kernel or lookup tables are the wrong size.
+    InvalidFilter,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Io(ref e) => write!(f, "unable to read image: {}", e),
+            Error::Decode(ref s) => write!(f, "unable to decode image: {}", s),
+            Error::Encode(ref s) => write!(f, "unable to encode image: {}", s),
+            Error::UnknownFormat => write!(f, "unknown image format"),
+            Error::Create => write!(f, "unable to create image"),
+            Error::InvalidFilter => write!(f, "invalid filter"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Io(_) => "unable to read image",
+            Error::Decode(_) => "unable to decode image",
+            Error::Encode(_) => "unable to encode image",
+            Error::UnknownFormat => "unknown image format",
+            Error::Create => "unable to create image",
+            Error::InvalidFilter => "invalid filter",
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        Error::Io(e)
+    }
+}
+
+impl From<png::DecodingError> for Error {
+    fn from(e: png::DecodingError) -> Error {
+        Error::Decode(format!("{}", e))
+    }
+}
+
+impl From<png::EncodingError> for Error {
+    fn from(e: png::EncodingError) -> Error {
+        Error::Encode(format!("{}", e))
+    }
+}
+
+impl From<jpeg_decoder::Error> for Error {
+    fn from(e: jpeg_decoder::Error) -> Error {
+        Error::Decode(format!("{}", e))
+    }
+}
+
+impl From<gif::DecodingError> for Error {
+    fn from(e: gif::DecodingError) -> Error {
+        Error::Decode(format!("{}", e))
+    }
+}
+
+/// Describes how an image stores its pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// Opaque color.
+    Rgbx,
+    /// Color with alpha.
+    Rgba,
+    /// Color with alpha premultiplied into the color channels.
+    RgbaPremultiplied,
+    /// Opaque grayscale.
+    Luminance,
+    /// Alpha only.
+    Alpha,
+}
+
+impl Format {
+    /// Returns the OpenVG format used to store images in this format.
+    pub fn vg_format(&self) -> VGImageFormat {
+        match *self {
+            Format::Rgbx => VGImageFormat::VG_sRGBX_8888,
+            Format::Rgba => VGImageFormat::VG_sRGBA_8888,
+            Format::RgbaPremultiplied => VGImageFormat::VG_sRGBA_8888_PRE,
+            Format::Luminance => VGImageFormat::VG_sL_8,
+            Format::Alpha => VGImageFormat::VG_A_8,
+        }
+    }
+}
+
+/// Returns the OpenVG format of bitmap pixels, which are stored as R, G, B, A bytes.
+pub fn bitmap_format() -> VGImageFormat {
+    if cfg!(target_endian = "little") {
+        VGImageFormat::VG_sABGR_8888
+    } else {
+        VGImageFormat::VG_sRGBA_8888
+    }
+}
+
+/// Represents decoded pixels in memory.
+///
+/// Pixels are stored as R, G, B, A bytes (not premultiplied), starting with the top row.
+#[derive(Clone, Debug)]
+pub struct Bitmap {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    /// Creates a new transparent Bitmap.
+    pub fn new(width: u32, height: u32) -> Bitmap {
+        Bitmap {
+            width: width,
+            height: height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    /// Decodes PNG, JPEG or GIF data, detecting the format from its signature.
+    pub fn decode(data: &[u8]) -> Result<Bitmap, Error> {
+        if data.starts_with(b"\x89PNG") {
+            Bitmap::decode_png(data)
+        } else if data.starts_with(b"\xFF\xD8") {
+            Bitmap::decode_jpeg(data)
+        } else if data.starts_with(b"GIF8") {
+            Bitmap::decode_gif(data)
+        } else {
+            Err(Error::UnknownFormat)
+        }
+    }
+
+    /// Decodes PNG data.
+    pub fn decode_png(data: &[u8]) -> Result<Bitmap, Error> {
+        // The default transformations expand palettes, transparency and low bit depths
+        // and reduce 16-bit samples to 8 bits.
+        let decoder = png::Decoder::new(data);
+        let (info, mut reader) = decoder.read_info()?;
+        let mut buffer = vec![0; info.buffer_size()];
+        reader.next_frame(&mut buffer)?;
+        let mut bitmap = Bitmap::new(info.width, info.height);
+        for y in 0..info.height as usize {
+            let row = &buffer[y * info.line_size..];
+            for x in 0..info.width as usize {
+                let rgba = match info.color_type {
+                    png::ColorType::Grayscale => [row[x], row[x], row[x], 255],
+                    png::ColorType::GrayscaleAlpha => {
+                        [row[2 * x], row[2 * x], row[2 * x], row[2 * x + 1]]
+                    }
+                    png::ColorType::RGB => [row[3 * x], row[3 * x + 1], row[3 * x + 2], 255],
+                    png::ColorType::RGBA => {
+                        [row[4 * x], row[4 * x + 1], row[4 * x + 2], row[4 * x + 3]]
+                    }
+                    png::ColorType::Indexed => {
+                        return Err(Error::Decode("unexpanded palette".to_string()))
+                    }
+                };
+                bitmap.set_pixel(x as u32, y as u32, rgba);
+            }
+        }
+        Ok(bitmap)
+    }
+
+    /// Decodes JPEG data.
+    pub fn decode_jpeg(data: &[u8]) -> Result<Bitmap, Error> {
+        let mut decoder = jpeg_decoder::Decoder::new(data);
+        let buffer = decoder.decode()?;
+        let info = match decoder.info() {
+            Some(info) => info,
+            None => return Err(Error::Decode("missing JPEG header".to_string())),
+        };
+        let mut bitmap = Bitmap::new(info.width as u32, info.height as u32);
+        let n = (info.width as usize) * (info.height as usize);
+        for i in 0..n {
+            let rgba = match info.pixel_format {
+                jpeg_decoder::PixelFormat::L8 => [buffer[i], buffer[i], buffer[i], 255],
+                jpeg_decoder::PixelFormat::RGB24 => {
+                    [buffer[3 * i], buffer[3 * i + 1], buffer[3 * i + 2], 255]
+                }
+                jpeg_decoder::PixelFormat::CMYK32 => {
+                    // Adobe applications write inverted CMYK values.
+                    let k = buffer[4 * i + 3] as u32;
+                    [
+                        (buffer[4 * i] as u32 * k / 255) as u8,
+                        (buffer[4 * i + 1] as u32 * k / 255) as u8,
+                        (buffer[4 * i + 2] as u32 * k / 255) as u8,
+                        255,
+                    ]
+                }
+            };
+            bitmap.pixels[4 * i..4 * i + 4].copy_from_slice(&rgba);
+        }
+        Ok(bitmap)
+    }
+
+    /// Decodes the first frame of GIF data.
+    pub fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
+        use gif::SetParameter;
+        let mut decoder = gif::Decoder::new(data);
+        decoder.set(gif::ColorOutput::RGBA);
+        let mut reader = decoder.read_info()?;
+        let mut bitmap = Bitmap::new(reader.width() as u32, reader.height() as u32);
+        match reader.read_next_frame()? {
+            Some(frame) => bitmap.draw_rgba(
+                frame.left as u32,
+                frame.top as u32,
+                frame.width as u32,
+                frame.height as u32,
+                &frame.buffer,
+            ),
+            None => return Err(Error::Decode("GIF has no frames".to_string())),
+        }
+        Ok(bitmap)
+    }
+
+    /// Decodes every frame of an animated GIF.
+    ///
+    /// Each frame is returned fully composited along with its delay in milliseconds.
+    pub fn decode_gif_frames(data: &[u8]) -> Result<Vec<(Bitmap, u32)>, Error> {
+        use gif::SetParameter;
+        let mut decoder = gif::Decoder::new(data);
+        decoder.set(gif::ColorOutput::RGBA);
+        let mut reader = decoder.read_info()?;
+        let mut canvas = Bitmap::new(reader.width() as u32, reader.height() as u32);
+        let mut frames = Vec::new();
+        while let Some(frame) = reader.read_next_frame()? {
+            let x = frame.left as u32;
+            let y = frame.top as u32;
+            let w = frame.width as u32;
+            let h = frame.height as u32;
+            let previous = canvas.pixels.clone();
+            canvas.draw_rgba(x, y, w, h, &frame.buffer);
+            frames.push((
+                Bitmap {
+                    width: canvas.width,
+                    height: canvas.height,
+                    pixels: canvas.pixels.clone(),
+                },
+                frame.delay as u32 * 10,
+            ));
+            match frame.dispose {
+                gif::DisposalMethod::Background => canvas.clear_rect(x, y, w, h),
+                gif::DisposalMethod::Previous => canvas.pixels = previous,
+                _ => {}
+            }
+        }
+        if frames.is_empty() {
+            return Err(Error::Decode("GIF has no frames".to_string()));
+        }
+        Ok(frames)
+    }
+
+    /// Encodes the bitmap as PNG data.
+    pub fn encode_png(&self) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        self.write_png(&mut data)?;
+        Ok(data)
+    }
+
+    /// Saves the bitmap as a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let f = File::create(path)?;
+        self.write_png(BufWriter::new(f))
+    }
+
+    fn write_png<W: io::Write>(&self, w: W) -> Result<(), Error> {
+        use png::HasParameters;
+        let mut encoder = png::Encoder::new(w, self.width, self.height);
+        encoder.set(png::ColorType::RGBA).set(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// Returns the R, G, B, A values of the pixel at x, y, counting from the top left.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// Sets the R, G, B, A values of the pixel at x, y, counting from the top left.
+    pub fn set_pixel(&mut self, x: u32, y: u32, rgba: [u8; 4]) {
+        let i = ((y * self.width + x) * 4) as usize;
+        self.pixels[i..i + 4].copy_from_slice(&rgba);
+    }
+
+    /// Returns true if any pixel is not fully opaque.
+    pub fn has_alpha(&self) -> bool {
+        self.pixels.chunks(4).any(|p| p[3] != 255)
+    }
+
+    /// Makes a block of pixels transparent.
+    fn clear_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        for j in y..(y + h).min(self.height) {
+            for i in x..(x + w).min(self.width) {
+                self.set_pixel(i, j, [0, 0, 0, 0]);
+            }
+        }
+    }
+
+    /// Copies a block of RGBA pixels with its top left corner at x, y, skipping
+    /// transparent pixels and anything outside the bitmap.
+    fn draw_rgba(&mut self, x: u32, y: u32, w: u32, h: u32, rgba: &[u8]) {
+        for j in 0..h {
+            for i in 0..w {
+                if x + i >= self.width || y + j >= self.height {
+                    continue;
+                }
+                let k = ((j * w + i) * 4) as usize;
+                if rgba[k + 3] != 0 {
+                    self.set_pixel(
+                        x + i,
+                        y + j,
+                        [rgba[k], rgba[k + 1], rgba[k + 2], rgba[k + 3]],
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Owns an OpenVG image handle, which is destroyed when the last owner is dropped.
+///
+/// Paints that draw an image keep a reference so that the handle stays valid while
+/// the paint can still be applied.
+pub(crate) struct ImageHandle(VGImage);
+
+impl Drop for ImageHandle {
+    fn drop(&mut self) {
+        unsafe { vgDestroyImage(self.0) }
+    }
+}
+
+impl ImageHandle {
+    /// Returns the OpenVG handle.
+    pub fn get(&self) -> VGImage {
+        self.0
+    }
+}
+
+/// Represents an OpenVG image.
+pub struct Image {
+    handle: Arc<ImageHandle>,
+    width: u32,
+    height: u32,
+    format: Format,
+}
+
+impl Image {
+    /// Creates a new transparent Image.
+    pub fn new(width: u32, height: u32, format: Format) -> Result<Image, Error> {
+        let handle = unsafe {
+            vgCreateImage(
+                format.vg_format(),
+                width as VGint,
+                height as VGint,
+                VGImageQuality::VG_IMAGE_QUALITY_BETTER as u32,
+            )
+        };
+        if handle == VG_INVALID_HANDLE {
+            return Err(Error::Create);
+        }
+        unsafe {
+            vgClearImage(handle, 0, 0, width as VGint, height as VGint);
+        }
+        Ok(Image {
+            handle: Arc::new(ImageHandle(handle)),
+            width: width,
+            height: height,
+            format: format,
+        })
+    }
+
+    /// Creates an Image from a Bitmap.
+    pub fn from_bitmap(bitmap: &Bitmap, format: Format) -> Result<Image, Error> {
+        let image = Image::new(bitmap.width, bitmap.height, format)?;
+        image.upload(bitmap);
+        Ok(image)
+    }
+
+    /// Creates an Image from PNG, JPEG or GIF data.
+    ///
+    /// Images with transparent pixels are stored with alpha.
+    pub fn from_bytes(data: &[u8]) -> Result<Image, Error> {
+        let bitmap = Bitmap::decode(data)?;
+        let format = if bitmap.has_alpha() {
+            Format::Rgba
+        } else {
+            Format::Rgbx
+        };
+        Image::from_bitmap(&bitmap, format)
+    }
+
+    /// Creates an Image from a PNG, JPEG or GIF file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Image, Error> {
+        let mut f = File::open(path)?;
+        let mut data = Vec::new();
+        f.read_to_end(&mut data)?;
+        Image::from_bytes(&data)
+    }
+
+    /// Returns the OpenVG handle of the image.
+    pub fn handle(&self) -> VGImage {
+        self.handle.get()
+    }
+
+    /// Returns an owner of the OpenVG handle that keeps it valid after the image is
+    /// dropped.
+    pub(crate) fn shared_handle(&self) -> Arc<ImageHandle> {
+        self.handle.clone()
+    }
+
+    /// Returns the width of the image in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the image in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the pixel format of the image.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Replaces the pixels of the image with those of a Bitmap of the same size.
+    pub fn upload(&self, bitmap: &Bitmap) {
+        let w = self.width.min(bitmap.width) as VGint;
+        let h = self.height.min(bitmap.height) as VGint;
+        if w == 0 || h == 0 {
+            return;
+        }
+        // OpenVG images start with the bottom row, so pass the last row with a negative stride.
+        let stride = (bitmap.width * 4) as VGint;
+        let last = (bitmap.height as usize - 1) * stride as usize;
+        unsafe {
+            vgImageSubData(
+                self.handle(),
+                bitmap.pixels[last..].as_ptr() as *const VGbyte,
+                -stride,
+                bitmap_format(),
+                0,
+                0,
+                w,
+                h,
+            );
+        }
+    }
+
+    /// Saves the image as a PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        self.to_bitmap().save_png(path)
+    }
+
+    /// Reads the pixels of the image into a Bitmap.
+    pub fn to_bitmap(&self) -> Bitmap {
+        let mut bitmap = Bitmap::new(self.width, self.height);
+        if self.width == 0 || self.height == 0 {
+            return bitmap;
+        }
+        let stride = (self.width * 4) as VGint;
+        let last = (self.height as usize - 1) * stride as usize;
+        unsafe {
+            vgGetImageSubData(
+                self.handle(),
+                bitmap.pixels[last..].as_mut_ptr() as *const VGbyte,
+                -stride,
+                bitmap_format(),
+                0,
+                0,
+                self.width as VGint,
+                self.height as VGint,
+            );
+        }
+        bitmap
+    }
+}
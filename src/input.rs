@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use event;
+use key;
 use Application;
 
 use std::sync::Arc;
@@ -109,6 +110,9 @@ impl Listener {
                 self.touches[self.slot].began = true;
             }
         } else {
+            if c == key::F12 && v == 1 {
+                ::request_screenshot();
+            }
             let k = event::Key::new(c, v as u8);
             let ev = event::Event::new_key(k);
             self.send(&ev, &app);
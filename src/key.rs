@@ -12,13 +12,174 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub const SPACE: u16 = 57;
+//! Key codes delivered in `event::Key`, which follow the Linux input event codes.
 
+pub const ESC: u16 = 1;
+pub const KEY_1: u16 = 2;
+pub const KEY_2: u16 = 3;
+pub const KEY_3: u16 = 4;
+pub const KEY_4: u16 = 5;
+pub const KEY_5: u16 = 6;
+pub const KEY_6: u16 = 7;
+pub const KEY_7: u16 = 8;
+pub const KEY_8: u16 = 9;
+pub const KEY_9: u16 = 10;
+pub const KEY_0: u16 = 11;
+pub const MINUS: u16 = 12;
+pub const EQUAL: u16 = 13;
+pub const BACKSPACE: u16 = 14;
+pub const TAB: u16 = 15;
+pub const Q: u16 = 16;
+pub const W: u16 = 17;
+pub const E: u16 = 18;
+pub const R: u16 = 19;
+pub const T: u16 = 20;
+pub const Y: u16 = 21;
+pub const U: u16 = 22;
+pub const I: u16 = 23;
+pub const O: u16 = 24;
+pub const P: u16 = 25;
+pub const LEFTBRACE: u16 = 26;
+pub const RIGHTBRACE: u16 = 27;
+pub const ENTER: u16 = 28;
+pub const LEFTCTRL: u16 = 29;
+pub const A: u16 = 30;
+pub const S: u16 = 31;
+pub const D: u16 = 32;
+pub const F: u16 = 33;
+pub const G: u16 = 34;
+pub const H: u16 = 35;
+pub const J: u16 = 36;
+pub const K: u16 = 37;
+pub const L: u16 = 38;
+pub const SEMICOLON: u16 = 39;
+pub const APOSTROPHE: u16 = 40;
+pub const GRAVE: u16 = 41;
+pub const LEFTSHIFT: u16 = 42;
+pub const BACKSLASH: u16 = 43;
+pub const Z: u16 = 44;
+pub const X: u16 = 45;
+pub const C: u16 = 46;
+pub const V: u16 = 47;
+pub const B: u16 = 48;
+pub const N: u16 = 49;
+pub const M: u16 = 50;
+pub const COMMA: u16 = 51;
+pub const DOT: u16 = 52;
+pub const SLASH: u16 = 53;
+pub const RIGHTSHIFT: u16 = 54;
+pub const LEFTALT: u16 = 56;
+pub const SPACE: u16 = 57;
+pub const CAPSLOCK: u16 = 58;
+pub const F1: u16 = 59;
+pub const F2: u16 = 60;
+pub const F3: u16 = 61;
+pub const F4: u16 = 62;
+pub const F5: u16 = 63;
+pub const F6: u16 = 64;
+pub const F7: u16 = 65;
+pub const F8: u16 = 66;
+pub const F9: u16 = 67;
+pub const F10: u16 = 68;
+pub const F11: u16 = 87;
+pub const F12: u16 = 88;
+pub const RIGHTCTRL: u16 = 97;
+pub const RIGHTALT: u16 = 100;
+pub const HOME: u16 = 102;
 pub const UP: u16 = 103;
-
-pub const RIGHT: u16 = 106;
+pub const PAGEUP: u16 = 104;
 pub const LEFT: u16 = 105;
-
+pub const RIGHT: u16 = 106;
+pub const END: u16 = 107;
 pub const DOWN: u16 = 108;
+pub const PAGEDOWN: u16 = 109;
+pub const INSERT: u16 = 110;
+pub const DELETE: u16 = 111;
+pub const LEFTMETA: u16 = 125;
+pub const RIGHTMETA: u16 = 126;
 
-pub const A: u16 = 30;
+/// Key events that report a key being released.
+pub const RELEASED: u8 = 0;
+/// Key events that report a key being pressed.
+pub const PRESSED: u8 = 1;
+/// Key events that report a key repeating while it is held down.
+pub const REPEATED: u8 = 2;
+
+/// Returns true if a key code is one of the shift keys.
+pub fn is_shift(key: u16) -> bool {
+    key == LEFTSHIFT || key == RIGHTSHIFT
+}
+
+/// Returns true if a key code is one of the control or command keys.
+pub fn is_control(key: u16) -> bool {
+    key == LEFTCTRL || key == RIGHTCTRL || key == LEFTMETA || key == RIGHTMETA
+}
+
+/// Returns the character typed by a key on a US keyboard, if any.
+pub fn to_char(key: u16, shift: bool) -> Option<char> {
+    let (plain, shifted) = match key {
+        KEY_1 => ('1', '!'),
+        KEY_2 => ('2', '@'),
+        KEY_3 => ('3', '#'),
+        KEY_4 => ('4', '$'),
+        KEY_5 => ('5', '%'),
+        KEY_6 => ('6', '^'),
+        KEY_7 => ('7', '&'),
+        KEY_8 => ('8', '*'),
+        KEY_9 => ('9', '('),
+        KEY_0 => ('0', ')'),
+        MINUS => ('-', '_'),
+        EQUAL => ('=', '+'),
+        Q => ('q', 'Q'),
+        W => ('w', 'W'),
+        E => ('e', 'E'),
+        R => ('r', 'R'),
+        T => ('t', 'T'),
+        Y => ('y', 'Y'),
+        U => ('u', 'U'),
+        I => ('i', 'I'),
+        O => ('o', 'O'),
+        P => ('p', 'P'),
+        LEFTBRACE => ('[', '{'),
+        RIGHTBRACE => (']', '}'),
+        A => ('a', 'A'),
+        S => ('s', 'S'),
+        D => ('d', 'D'),
+        F => ('f', 'F'),
+        G => ('g', 'G'),
+        H => ('h', 'H'),
+        J => ('j', 'J'),
+        K => ('k', 'K'),
+        L => ('l', 'L'),
+        SEMICOLON => (';', ':'),
+        APOSTROPHE => ('\'', '"'),
+        GRAVE => ('`', '~'),
+        BACKSLASH => ('\\', '|'),
+        Z => ('z', 'Z'),
+        X => ('x', 'X'),
+        C => ('c', 'C'),
+        V => ('v', 'V'),
+        B => ('b', 'B'),
+        N => ('n', 'N'),
+        M => ('m', 'M'),
+        COMMA => (',', '<'),
+        DOT => ('.', '>'),
+        SLASH => ('/', '?'),
+        SPACE => (' ', ' '),
+        _ => return None,
+    };
+    Some(if shift { shifted } else { plain })
+}
+
+/// Returns the key that types a character, and whether shift must be held for it.
+pub fn from_char(c: char) -> Option<(u16, bool)> {
+    for key in 0..256 {
+        if to_char(key, false) == Some(c) {
+            return Some((key, false));
+        }
+        if to_char(key, true) == Some(c) {
+            return Some((key, true));
+        }
+    }
+    None
+}
@@ -0,0 +1,649 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An on-screen keyboard for touchscreens without a physical keyboard.
+//!
+//! The keyboard turns touches into the key events a physical keyboard sends, so an
+//! application passes every event through `Keyboard::handle()` and then handles the
+//! events it returns as usual:
+//!
+//! ```ignore
+//! fn handle(&mut self, ev: &event::Event) {
+//!     for ev in self.keyboard.handle(ev) {
+//!         self.field.handle(&ev);
+//!     }
+//! }
+//! ```
+//!
+//! Characters that need shift are sent between shift key events. Characters with no
+//! key code on a US keyboard, such as accented letters chosen by holding a key down,
+//! are sent as `Event::Text`.
+
+use draw;
+use event::{Event, Key, Touch};
+use font::GlyphSource;
+use key;
+use openvg::VGfloat;
+use text_field::InputMode;
+
+use std::time::Duration;
+
+/// How long a key is held before its accents are offered or it starts to repeat.
+const LONG_PRESS: u64 = 500;
+
+/// The time between repeats of a held backspace key.
+const REPEAT_INTERVAL: u64 = 80;
+
+/// How quickly shift is tapped twice to turn on caps lock.
+const DOUBLE_TAP: u64 = 400;
+
+/// The arrangement of keys shown by a keyboard.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Layout {
+    /// Letters in QWERTY order.
+    Qwerty,
+    /// A keypad for entering numbers.
+    Numeric,
+    /// Digits and punctuation.
+    Symbols,
+}
+
+impl Layout {
+    /// Returns the layout best suited for entering text in a mode.
+    pub fn for_input_mode(mode: InputMode) -> Layout {
+        match mode {
+            InputMode::Numeric => Layout::Numeric,
+            InputMode::Text | InputMode::Password => Layout::Qwerty,
+        }
+    }
+}
+
+/// What a key does when it is tapped.
+#[derive(Copy, Clone, PartialEq)]
+enum Action {
+    Char(char),
+    Shift,
+    Backspace,
+    Enter,
+    Space,
+    Switch(Layout),
+}
+
+/// A key and its width in units of the layout's narrowest key.
+#[derive(Copy, Clone)]
+struct Cap {
+    action: Action,
+    width: VGfloat,
+}
+
+fn cap(action: Action, width: VGfloat) -> Cap {
+    Cap {
+        action: action,
+        width: width,
+    }
+}
+
+/// Returns a row of single-width character keys.
+fn chars(s: &str) -> Vec<Cap> {
+    s.chars().map(|c| cap(Action::Char(c), 1.0)).collect()
+}
+
+/// Joins the pieces of a row.
+fn row(parts: Vec<Vec<Cap>>) -> Vec<Cap> {
+    parts
+        .into_iter()
+        .flat_map(|part| part.into_iter())
+        .collect()
+}
+
+/// Returns the keys of a layout, from the top row down.
+fn rows(layout: Layout) -> Vec<Vec<Cap>> {
+    match layout {
+        Layout::Qwerty => vec![
+            chars("qwertyuiop"),
+            chars("asdfghjkl"),
+            row(vec![
+                vec![cap(Action::Shift, 1.5)],
+                chars("zxcvbnm"),
+                vec![cap(Action::Backspace, 1.5)],
+            ]),
+            row(vec![
+                vec![cap(Action::Switch(Layout::Symbols), 1.5)],
+                chars(","),
+                vec![cap(Action::Space, 5.0)],
+                chars("."),
+                vec![cap(Action::Enter, 1.5)],
+            ]),
+        ],
+        Layout::Symbols => vec![
+            chars("1234567890"),
+            chars("@#$%&*-+()"),
+            row(vec![chars("!\"':;/?_"), vec![cap(Action::Backspace, 2.0)]]),
+            row(vec![
+                vec![cap(Action::Switch(Layout::Qwerty), 1.5)],
+                chars(","),
+                vec![cap(Action::Space, 5.0)],
+                chars("."),
+                vec![cap(Action::Enter, 1.5)],
+            ]),
+        ],
+        Layout::Numeric => vec![
+            chars("123-"),
+            chars("456."),
+            row(vec![chars("789"), vec![cap(Action::Backspace, 1.0)]]),
+            row(vec![
+                vec![cap(Action::Switch(Layout::Qwerty), 1.0)],
+                vec![cap(Action::Char('0'), 2.0)],
+                vec![cap(Action::Enter, 1.0)],
+            ]),
+        ],
+    }
+}
+
+/// Returns the variants of a character offered when its key is held down.
+///
+/// Variants are limited to the characters that the built-in fonts have glyphs for.
+fn accents(c: char) -> &'static str {
+    match c {
+        'a' => "àáâäãåāæ",
+        'c' => "çćč",
+        'e' => "èéêëēęě",
+        'i' => "ìíîïī",
+        'l' => "ł",
+        'n' => "ñńň",
+        'o' => "òóôöõøōœ",
+        'r' => "ř",
+        's' => "śšß",
+        'u' => "ùúûüūů",
+        'y' => "ýÿ",
+        'z' => "žźż",
+        '!' => "¡",
+        '?' => "¿",
+        '$' => "£¥¢",
+        '"' => "«»",
+        _ => "",
+    }
+}
+
+/// Returns the uppercase form of a character if it is a single character.
+fn upper(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    d.as_secs() * 1000 + (d.subsec_nanos() / 1_000_000) as u64
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Shift {
+    Off,
+    /// Shifts the next character only.
+    Once,
+    /// Caps lock.
+    Locked,
+}
+
+/// A touch on the keyboard.
+struct Press {
+    slot: usize,
+    /// The row and column of the key under the touch, if any.
+    key: Option<(usize, usize)>,
+    start: u64,
+    last_repeat: u64,
+    /// The accents offered for the key once it has been held down.
+    accents: Vec<char>,
+    /// The index of the accent under the touch.
+    accent: Option<usize>,
+}
+
+/// A keyboard drawn in a rectangle at the bottom of the screen.
+///
+/// Keys are drawn with the keyboard's own colors and labelled with its font, and
+/// pressed keys are highlighted until the touch ends. Tapping shift shifts the next
+/// letter and tapping it twice quickly turns on caps lock. Holding a letter offers its
+/// accents above the key, one of which is chosen by sliding to it and lifting.
+pub struct Keyboard<'a> {
+    font: &'a GlyphSource,
+    frame: [VGfloat; 4],
+    layout: Layout,
+    visible: bool,
+    shift: Shift,
+    last_shift_tap: Option<u64>,
+    presses: Vec<Press>,
+    now: u64,
+}
+
+impl<'a> Keyboard<'a> {
+    /// Creates a QWERTY Keyboard in the rectangle at x, y with size w, h.
+    pub fn new(
+        font: &'a GlyphSource,
+        x: VGfloat,
+        y: VGfloat,
+        w: VGfloat,
+        h: VGfloat,
+    ) -> Keyboard<'a> {
+        Keyboard {
+            font: font,
+            frame: [x, y, w, h],
+            layout: Layout::Qwerty,
+            visible: true,
+            shift: Shift::Off,
+            last_shift_tap: None,
+            presses: Vec::new(),
+            now: 0,
+        }
+    }
+
+    /// Moves the keyboard to the rectangle at x, y with size w, h.
+    pub fn set_frame(&mut self, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+        self.frame = [x, y, w, h];
+    }
+
+    /// Returns the layout being shown.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Shows another layout.
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+        self.presses.clear();
+    }
+
+    /// Returns true if the keyboard is drawn and handles touches.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Sets whether the keyboard is drawn and handles touches.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.presses.clear();
+    }
+
+    /// Returns true if the next letter is typed in uppercase.
+    pub fn is_shifted(&self) -> bool {
+        self.shift != Shift::Off
+    }
+
+    /// Returns true if caps lock is on.
+    pub fn is_caps_locked(&self) -> bool {
+        self.shift == Shift::Locked
+    }
+
+    /// Handles an event and returns the events the application should handle in its
+    /// place.
+    ///
+    /// Touches on the keyboard become key and text events. All other events are
+    /// returned unchanged.
+    pub fn handle(&mut self, ev: &Event) -> Vec<Event> {
+        let mut events = Vec::new();
+        match *ev {
+            Event::Touch(t, d) => {
+                self.now = millis(d);
+                if self.handle_touch(t, &mut events) {
+                    return events;
+                }
+            }
+            Event::Tick(d) => {
+                self.now = millis(d);
+                self.repeat(&mut events);
+            }
+            Event::Key(_, d) | Event::Text(_, d) => self.now = millis(d),
+        }
+        events.push(ev.clone());
+        events
+    }
+
+    fn handle_touch(&mut self, t: Touch, events: &mut Vec<Event>) -> bool {
+        if !self.visible {
+            return false;
+        }
+        // Touches are measured down from the top of the screen.
+        let (_, height) = draw::canvas_size();
+        let x = t.x as VGfloat;
+        let y = height as VGfloat - t.y as VGfloat;
+        let index = self.presses.iter().position(|p| p.slot == t.slot);
+        match (t.kind, index) {
+            (1, _) => {
+                let f = self.frame;
+                if x < f[0] || x > f[0] + f[2] || y < f[1] || y > f[1] + f[3] {
+                    return false;
+                }
+                if let Some(i) = index {
+                    self.presses.remove(i);
+                }
+                self.press(t.slot, x, y, events);
+            }
+            (2, Some(i)) => self.slide(i, x, y),
+            (3, Some(i)) => {
+                let press = self.presses.remove(i);
+                self.release(press, events);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn press(&mut self, slot: usize, x: VGfloat, y: VGfloat, events: &mut Vec<Event>) {
+        let key = self.key_at(x, y);
+        if let Some((r, c)) = key {
+            match rows(self.layout)[r][c].action {
+                Action::Shift => self.tap_shift(),
+                Action::Backspace => send_key(key::BACKSPACE, key::PRESSED, events),
+                _ => {}
+            }
+        }
+        self.presses.push(Press {
+            slot: slot,
+            key: key,
+            start: self.now,
+            last_repeat: self.now,
+            accents: Vec::new(),
+            accent: None,
+        });
+    }
+
+    fn slide(&mut self, i: usize, x: VGfloat, y: VGfloat) {
+        if !self.presses[i].accents.is_empty() {
+            let accent = self.accent_at(&self.presses[i], x);
+            self.presses[i].accent = accent;
+            return;
+        }
+        let actions = rows(self.layout);
+        let held = self.presses[i].key.map(|(r, c)| actions[r][c].action);
+        // Backspace and shift act when pressed, so they stay pressed until released.
+        if held == Some(Action::Backspace) || held == Some(Action::Shift) {
+            return;
+        }
+        let key = self.key_at(x, y);
+        let press = &mut self.presses[i];
+        if key != press.key {
+            press.key = key;
+            press.start = self.now;
+        }
+    }
+
+    fn release(&mut self, press: Press, events: &mut Vec<Event>) {
+        let (r, c) = match press.key {
+            Some(key) => key,
+            None => return,
+        };
+        if !press.accents.is_empty() {
+            if let Some(i) = press.accent {
+                self.type_char(press.accents[i], events);
+            }
+            return;
+        }
+        match rows(self.layout)[r][c].action {
+            Action::Char(c) => {
+                let c = if self.is_shifted() { upper(c) } else { c };
+                self.type_char(c, events);
+            }
+            Action::Space => self.tap_key(key::SPACE, events),
+            Action::Enter => self.tap_key(key::ENTER, events),
+            Action::Backspace => send_key(key::BACKSPACE, key::RELEASED, events),
+            Action::Switch(layout) => {
+                // Other touches hold keys of the old layout, so they are cancelled.
+                self.cancel_presses(events);
+                self.layout = layout;
+            }
+            Action::Shift => {}
+        }
+    }
+
+    /// Forgets all touches, releasing any backspace key they hold.
+    fn cancel_presses(&mut self, events: &mut Vec<Event>) {
+        let actions = rows(self.layout);
+        for press in self.presses.drain(..) {
+            if let Some((r, c)) = press.key {
+                if actions[r][c].action == Action::Backspace {
+                    send_key(key::BACKSPACE, key::RELEASED, events);
+                }
+            }
+        }
+    }
+
+    /// Offers accents for keys held long enough and repeats held backspace keys.
+    fn repeat(&mut self, events: &mut Vec<Event>) {
+        let actions = rows(self.layout);
+        let shifted = self.is_shifted();
+        let now = self.now;
+        for i in 0..self.presses.len() {
+            let (r, c) = match self.presses[i].key {
+                Some(key) => key,
+                None => continue,
+            };
+            if now.saturating_sub(self.presses[i].start) < LONG_PRESS {
+                continue;
+            }
+            match actions[r][c].action {
+                Action::Backspace => {
+                    let press = &mut self.presses[i];
+                    if now.saturating_sub(press.last_repeat) >= REPEAT_INTERVAL {
+                        press.last_repeat = now;
+                        send_key(key::BACKSPACE, key::REPEATED, events);
+                    }
+                }
+                Action::Char(ch) if self.presses[i].accents.is_empty() => {
+                    let press = &mut self.presses[i];
+                    press.accents = accents(ch)
+                        .chars()
+                        .map(|a| if shifted { upper(a) } else { a })
+                        .collect();
+                    if !press.accents.is_empty() {
+                        press.accent = Some(0);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn tap_shift(&mut self) {
+        let double_tap = match self.last_shift_tap {
+            Some(t) => self.now.saturating_sub(t) < DOUBLE_TAP,
+            None => false,
+        };
+        self.shift = match self.shift {
+            Shift::Off => Shift::Once,
+            Shift::Once if double_tap => Shift::Locked,
+            Shift::Once | Shift::Locked => Shift::Off,
+        };
+        self.last_shift_tap = Some(self.now);
+    }
+
+    /// Sends a character as the key events that type it, or as text if no key does.
+    fn type_char(&mut self, c: char, events: &mut Vec<Event>) {
+        match key::from_char(c) {
+            Some((code, true)) => {
+                send_key(key::LEFTSHIFT, key::PRESSED, events);
+                self.tap_key(code, events);
+                send_key(key::LEFTSHIFT, key::RELEASED, events);
+            }
+            Some((code, false)) => self.tap_key(code, events),
+            None => events.push(Event::new_text(&c.to_string())),
+        }
+        if self.shift == Shift::Once {
+            self.shift = Shift::Off;
+        }
+    }
+
+    fn tap_key(&self, code: u16, events: &mut Vec<Event>) {
+        send_key(code, key::PRESSED, events);
+        send_key(code, key::RELEASED, events);
+    }
+
+    /// Returns the size of the layout's rows, in key widths, and of its keys.
+    fn key_size(&self) -> (VGfloat, VGfloat) {
+        let rows = rows(self.layout);
+        let units = rows
+            .iter()
+            .map(|row| row.iter().map(|cap| cap.width).sum())
+            .fold(0.0, VGfloat::max);
+        (self.frame[2] / units, self.frame[3] / rows.len() as VGfloat)
+    }
+
+    /// Returns the rectangle of every key, by row and column.
+    fn key_frames(&self) -> Vec<Vec<[VGfloat; 4]>> {
+        let (unit, height) = self.key_size();
+        let f = self.frame;
+        let mut frames = Vec::new();
+        for (r, row) in rows(self.layout).iter().enumerate() {
+            let width: VGfloat = row.iter().map(|cap| cap.width).sum();
+            let mut x = f[0] + (f[2] - width * unit) / 2.0;
+            let y = f[1] + f[3] - (r + 1) as VGfloat * height;
+            let mut row_frames = Vec::new();
+            for cap in row {
+                row_frames.push([x, y, cap.width * unit, height]);
+                x += cap.width * unit;
+            }
+            frames.push(row_frames);
+        }
+        frames
+    }
+
+    fn key_at(&self, x: VGfloat, y: VGfloat) -> Option<(usize, usize)> {
+        for (r, row) in self.key_frames().iter().enumerate() {
+            for (c, k) in row.iter().enumerate() {
+                if x >= k[0] && x < k[0] + k[2] && y >= k[1] && y < k[1] + k[3] {
+                    return Some((r, c));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the rectangle of the accents offered for a press, one key wide per
+    /// accent and centered over the key where it fits.
+    fn accent_frame(&self, press: &Press) -> [VGfloat; 4] {
+        let (unit, height) = self.key_size();
+        let k = match press.key {
+            Some((r, c)) => self.key_frames()[r][c],
+            None => return [0.0, 0.0, 0.0, 0.0],
+        };
+        let w = unit * press.accents.len() as VGfloat;
+        let f = self.frame;
+        let x = (k[0] + k[2] / 2.0 - w / 2.0).min(f[0] + f[2] - w).max(f[0]);
+        [x, k[1] + k[3], w, height]
+    }
+
+    fn accent_at(&self, press: &Press, x: VGfloat) -> Option<usize> {
+        let a = self.accent_frame(press);
+        if x < a[0] || x >= a[0] + a[2] {
+            return None;
+        }
+        let i = ((x - a[0]) / (a[2] / press.accents.len() as VGfloat)) as usize;
+        Some(i.min(press.accents.len() - 1))
+    }
+
+    fn label(&self, action: Action) -> String {
+        match action {
+            Action::Char(c) if self.is_shifted() => upper(c).to_string(),
+            Action::Char(c) => c.to_string(),
+            Action::Shift => "shift".to_string(),
+            Action::Backspace => "del".to_string(),
+            Action::Enter => "enter".to_string(),
+            Action::Space => String::new(),
+            Action::Switch(Layout::Qwerty) => "ABC".to_string(),
+            Action::Switch(Layout::Symbols) => "?123".to_string(),
+            Action::Switch(Layout::Numeric) => "123".to_string(),
+        }
+    }
+
+    /// Draws the keyboard and any accents being offered.
+    pub fn draw(&self) {
+        if !self.visible {
+            return;
+        }
+        let _state = draw::SavedState::new();
+        let f = self.frame;
+        let (unit, height) = self.key_size();
+        let gap = (unit.min(height) * 0.08).max(1.0);
+        let radius = gap * 2.0;
+        let pointsize = height * 0.4;
+        let rows = rows(self.layout);
+
+        draw::stroke_width(0.0);
+        draw::fill(32, 32, 36, 1.0);
+        draw::rect(f[0], f[1], f[2], f[3]);
+
+        for (r, row) in self.key_frames().iter().enumerate() {
+            for (c, k) in row.iter().enumerate() {
+                let action = rows[r][c].action;
+                let pressed = self.presses.iter().any(|p| p.key == Some((r, c)));
+                let lit = match action {
+                    Action::Shift => self.is_shifted(),
+                    _ => false,
+                };
+                match action {
+                    _ if pressed || lit => draw::fill(150, 150, 160, 1.0),
+                    Action::Char(_) | Action::Space => draw::fill(88, 88, 96, 1.0),
+                    _ => draw::fill(60, 60, 66, 1.0),
+                }
+                let (x, y, w, h) = (k[0] + gap, k[1] + gap, k[2] - 2.0 * gap, k[3] - 2.0 * gap);
+                draw::round_rect(x, y, w, h, radius, radius);
+                draw::fill(255, 255, 255, 1.0);
+                let label = self.label(action);
+                let baseline = y
+                    + (h - draw::text_height(self.font, pointsize)) / 2.0
+                    + draw::text_depth(self.font, pointsize);
+                draw::text_mid(x + w / 2.0, baseline, &label, self.font, pointsize);
+                if action == Action::Shift && self.is_caps_locked() {
+                    draw::rect(x + w / 4.0, y + gap, w / 2.0, gap);
+                }
+            }
+        }
+
+        for press in self.presses.iter().filter(|p| !p.accents.is_empty()) {
+            let a = self.accent_frame(press);
+            draw::fill(60, 60, 66, 1.0);
+            draw::round_rect(a[0], a[1], a[2], a[3], radius, radius);
+            let w = a[2] / press.accents.len() as VGfloat;
+            for (i, accent) in press.accents.iter().enumerate() {
+                let x = a[0] + i as VGfloat * w;
+                if press.accent == Some(i) {
+                    draw::fill(150, 150, 160, 1.0);
+                    draw::round_rect(
+                        x + gap,
+                        a[1] + gap,
+                        w - 2.0 * gap,
+                        a[3] - 2.0 * gap,
+                        radius,
+                        radius,
+                    );
+                }
+                draw::fill(255, 255, 255, 1.0);
+                let baseline = a[1]
+                    + (a[3] - draw::text_height(self.font, pointsize)) / 2.0
+                    + draw::text_depth(self.font, pointsize);
+                draw::text_mid(
+                    x + w / 2.0,
+                    baseline,
+                    &accent.to_string(),
+                    self.font,
+                    pointsize,
+                );
+            }
+        }
+    }
+}
+
+fn send_key(code: u16, kind: u8, events: &mut Vec<Event>) {
+    events.push(Event::new_key(Key::new(code, kind)));
+}
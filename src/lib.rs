@@ -17,19 +17,35 @@
 #[macro_use]
 extern crate lazy_static;
 
+extern crate gif;
+extern crate jpeg_decoder;
 extern crate libc;
+extern crate png;
+extern crate serde_json;
+extern crate unicode_bidi;
+extern crate unicode_normalization;
 
 pub mod draw;
 pub mod egl;
 pub mod event;
+pub mod filter;
 pub mod font;
+pub mod image;
 pub mod key;
+pub mod keyboard;
+pub mod paragraph;
+pub mod rich_text;
+pub mod sprite;
+pub mod text_field;
+pub mod transform;
 
 pub mod input;
 pub mod openvg;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::{thread, time};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, thread, time};
 
 use libc::timeval;
 use std::fs::File;
@@ -65,6 +81,11 @@ pub trait Application {
 pub fn main<T: Application + 'static>(application: T) -> i64 {
     println!("Running on {}", platform());
     unsafe {
+        SCREENSHOT_REQUESTED.store(false, Ordering::SeqCst);
+        libc::signal(
+            libc::SIGUSR1,
+            screenshot_signal_handler as extern "C" fn(c_int) as libc::sighandler_t,
+        );
         APPLICATION = Some(Arc::new(Mutex::new(application)));
         INPUT_LISTENER = Some(input::Listener::new());
         if cfg!(target_os = "macos") {
@@ -124,6 +145,39 @@ extern "C" fn draw_handler(width: u32, height: u32) -> () {
             None => {}
         }
     }
+    if SCREENSHOT_REQUESTED.swap(false, Ordering::SeqCst) {
+        save_screenshot(width, height);
+    }
+}
+
+// A plain static, since the signal handler can't safely initialize a lazy_static.
+static SCREENSHOT_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Asks for the next frame to be saved as a PNG file.
+///
+/// Screenshots are written to the directory named by WEEKIT_SCREENSHOT_DIR, or to the
+/// current directory. Sending SIGUSR1 to the application or pressing F12 also asks for
+/// a screenshot.
+pub fn request_screenshot() {
+    SCREENSHOT_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+extern "C" fn screenshot_signal_handler(_signal: c_int) {
+    request_screenshot();
+}
+
+fn save_screenshot(width: u32, height: u32) {
+    // Milliseconds keep screenshots taken within the same second apart.
+    let (seconds, millis) = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs(), d.subsec_nanos() / 1_000_000),
+        Err(_) => (0, 0),
+    };
+    let dir = env::var("WEEKIT_SCREENSHOT_DIR").unwrap_or(".".to_string());
+    let path = format!("{}/weekit-{}-{:03}.png", dir, seconds, millis);
+    match draw::read_pixels(0, 0, width, height).save_png(&path) {
+        Ok(()) => println!("saved screenshot to {}", path),
+        Err(e) => println!("unable to save screenshot to {}: {}", path, e),
+    }
 }
 
 extern "C" fn tick_handler() -> () {
@@ -162,7 +216,13 @@ struct InputEvent {
 
 fn handle_inputs(filename: &'static str) {
     thread::spawn(move || {
-        let mut f = File::open(filename).expect(&("unable to open ".to_owned() + filename));
+        let mut f = match File::open(filename) {
+            Ok(f) => f,
+            Err(e) => {
+                println!("unable to open {}: {}", filename, e);
+                return;
+            }
+        };
         unsafe {
             get_input_details(f.as_raw_fd());
         }
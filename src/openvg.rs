@@ -562,7 +562,7 @@ extern "C" {
         height: VGint,
     );
     pub fn vgRenderToMask(path: VGPath, paintModes: VGbitfield, operation: VGMaskOperation);
-    pub fn vgCreateMaskLayer(path: VGPath, height: VGint) -> VGMaskLayer;
+    pub fn vgCreateMaskLayer(width: VGint, height: VGint) -> VGMaskLayer;
     pub fn vgDestroyMaskLayer(maskLayer: VGMaskLayer);
     pub fn vgFillMaskLayer(
         maskLayer: VGMaskLayer,
@@ -764,7 +764,7 @@ extern "C" {
     pub fn vgDrawGlyphs(
         font: VGFont,
         glyphCount: VGint,
-        glyphIndices: *const VGfloat,
+        glyphIndices: *const VGuint,
         adjustments_x: *const VGfloat,
         adjustments_y: *const VGfloat,
         paintModes: VGbitfield,
@@ -795,7 +795,7 @@ extern "C" {
         kernelY: *const VGshort,
         scale: VGfloat,
         bias: VGfloat,
-        tilingMode: VGfloat,
+        tilingMode: VGTilingMode,
     );
     pub fn vgGaussianBlur(
         dst: VGImage,
@@ -807,10 +807,10 @@ extern "C" {
     pub fn vgLookup(
         dst: VGImage,
         src: VGImage,
-        redLUT: *const VGuint,
-        greenLUT: *const VGuint,
-        blueLUT: *const VGuint,
-        alphaLUT: *const VGuint,
+        redLUT: *const VGubyte,
+        greenLUT: *const VGubyte,
+        blueLUT: *const VGubyte,
+        alphaLUT: *const VGubyte,
         outputLinear: VGboolean,
         outputPremultiplied: VGboolean,
     );
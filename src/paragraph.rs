@@ -0,0 +1,458 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Layout of multi-line text.
+//!
+//! Paragraphs can be laid out from plain strings or from AttributedStrings, whose
+//! spans keep their styles as lines are broken.
+
+use draw;
+use font::{is_rtl, GlyphSource};
+use openvg::VGfloat;
+use rich_text::{AttributedString, Span, TextStyle};
+
+use std::collections::VecDeque;
+use std::mem;
+
+/// Specifies how lines are placed between the edges of a paragraph.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Align {
+    /// Aligns lines with the start of their paragraph: the left edge, or the right edge
+    /// for right-to-left paragraphs.
+    Start,
+    /// Aligns lines with the end of their paragraph.
+    End,
+    Left,
+    Center,
+    Right,
+    /// Stretches the spaces between words so that lines fill the width. The last line of
+    /// each paragraph is aligned with its start.
+    Justify,
+}
+
+/// Returns the byte offsets at which a word may be broken with a hyphen.
+pub type Hyphenator = Box<Fn(&str) -> Vec<usize>>;
+
+/// Describes how text is laid out.
+pub struct Layout {
+    width: VGfloat,
+    line_spacing: VGfloat,
+    align: Align,
+    max_lines: Option<usize>,
+    ellipsis: String,
+    hyphenator: Option<Hyphenator>,
+}
+
+impl Layout {
+    /// Creates a new Layout that wraps lines at width.
+    pub fn new(width: VGfloat) -> Layout {
+        Layout {
+            width: width,
+            line_spacing: 1.0,
+            align: Align::Start,
+            max_lines: None,
+            ellipsis: "\u{2026}".to_string(),
+            hyphenator: None,
+        }
+    }
+
+    /// Sets the distance between baselines as a multiple of the height of each line.
+    pub fn line_spacing(mut self, line_spacing: VGfloat) -> Layout {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// Sets the alignment of lines. The default is `Align::Start`.
+    pub fn align(mut self, align: Align) -> Layout {
+        self.align = align;
+        self
+    }
+
+    /// Limits the number of lines. Text that doesn't fit is cut off and the last line
+    /// ends with the ellipsis.
+    pub fn max_lines(mut self, max_lines: usize) -> Layout {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Sets the string that marks truncated text. The default is "…".
+    pub fn ellipsis(mut self, ellipsis: &str) -> Layout {
+        self.ellipsis = ellipsis.to_string();
+        self
+    }
+
+    /// Sets a function that finds hyphenation points in words that don't fit on a line.
+    ///
+    /// Without one, only words too long for a line by themselves are broken.
+    pub fn hyphenator(mut self, hyphenator: Hyphenator) -> Layout {
+        self.hyphenator = Some(hyphenator);
+        self
+    }
+}
+
+/// A line of a laid-out paragraph.
+#[derive(Clone)]
+pub struct Line<'a> {
+    /// The text of the line.
+    pub text: AttributedString<'a>,
+    /// The offset of the line from the left edge of the paragraph.
+    pub x: VGfloat,
+    /// The distance from the top of the paragraph down to the line's baseline.
+    pub baseline: VGfloat,
+    /// The width of the text.
+    pub width: VGfloat,
+    /// Extra space added to each space between words when justifying.
+    pub word_gap: VGfloat,
+    /// True if the line is from a right-to-left paragraph.
+    pub rtl: bool,
+}
+
+/// Text broken into lines by a Layout.
+pub struct Paragraph<'a> {
+    /// The lines, from top to bottom.
+    pub lines: Vec<Line<'a>>,
+    /// The width of the widest line.
+    pub width: VGfloat,
+    /// The height of all of the lines.
+    pub height: VGfloat,
+    /// True if text was cut off to fit the maximum number of lines.
+    pub truncated: bool,
+}
+
+/// Text from one span of an AttributedString.
+#[derive(Clone)]
+struct Piece {
+    span: usize,
+    text: String,
+}
+
+/// Appends text to pieces, extending the last piece if it is from the same span.
+fn push_text(pieces: &mut Vec<Piece>, span: usize, text: &str) {
+    if let Some(last) = pieces.last_mut() {
+        if last.span == span {
+            last.text.push_str(text);
+            return;
+        }
+    }
+    pieces.push(Piece {
+        span: span,
+        text: text.to_string(),
+    });
+}
+
+/// Returns the text of pieces without their spans.
+fn plain_text(pieces: &[Piece]) -> String {
+    pieces.iter().map(|piece| piece.text.as_str()).collect()
+}
+
+/// Splits pieces at a byte offset into their combined text.
+fn split_pieces(pieces: &[Piece], at: usize) -> (Vec<Piece>, Vec<Piece>) {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut offset = 0;
+    for piece in pieces.iter() {
+        let end = offset + piece.text.len();
+        if end <= at {
+            head.push(piece.clone());
+        } else if offset >= at {
+            tail.push(piece.clone());
+        } else {
+            push_text(&mut head, piece.span, &piece.text[..at - offset]);
+            push_text(&mut tail, piece.span, &piece.text[at - offset..]);
+        }
+        offset = end;
+    }
+    (head, tail)
+}
+
+/// Removes the last character from pieces, dropping pieces that become empty.
+fn pop_char(pieces: &mut Vec<Piece>) {
+    while let Some(mut last) = pieces.pop() {
+        if last.text.pop().is_some() {
+            if !last.text.is_empty() {
+                pieces.push(last);
+            }
+            return;
+        }
+    }
+}
+
+/// Breaks text into lines, keeping the pieces of each line, whether it ends a paragraph
+/// and whether its paragraph is right to left.
+struct LineBreaker<'a, 'b> {
+    spans: &'b [Span<'a>],
+    layout: &'b Layout,
+    lines: Vec<(Vec<Piece>, bool, bool)>,
+}
+
+impl<'a, 'b> LineBreaker<'a, 'b> {
+    fn attributed(&self, pieces: &[Piece]) -> AttributedString<'a> {
+        let mut s = AttributedString::new();
+        for piece in pieces.iter() {
+            s.push(&piece.text, &self.spans[piece.span].style);
+        }
+        s
+    }
+
+    fn fits(&self, pieces: &[Piece]) -> bool {
+        self.attributed(pieces).width() <= self.layout.width
+    }
+
+    fn is_full(&self) -> bool {
+        match self.layout.max_lines {
+            Some(max_lines) => self.lines.len() > max_lines,
+            None => false,
+        }
+    }
+
+    /// Returns a line followed by a word, separated by a space in the line's last style.
+    fn join(&self, line: &[Piece], word: &[Piece]) -> Vec<Piece> {
+        let mut joined = line.to_vec();
+        if let Some(span) = line.last().map(|piece| piece.span) {
+            push_text(&mut joined, span, " ");
+        }
+        for piece in word.iter() {
+            push_text(&mut joined, piece.span, &piece.text);
+        }
+        joined
+    }
+
+    /// Splits a word at the last hyphenation point that lets it finish the line.
+    fn hyphenate(&self, line: &[Piece], word: &[Piece]) -> Option<(Vec<Piece>, Vec<Piece>)> {
+        let hyphenator = match self.layout.hyphenator {
+            Some(ref hyphenator) => hyphenator,
+            None => return None,
+        };
+        let text = plain_text(word);
+        let mut points = hyphenator(&text);
+        points.sort();
+        for &i in points.iter().rev() {
+            if i == 0 || i >= text.len() || !text.is_char_boundary(i) {
+                continue;
+            }
+            let (mut head, tail) = split_pieces(word, i);
+            let span = head[head.len() - 1].span;
+            push_text(&mut head, span, "-");
+            let head = self.join(line, &head);
+            if self.fits(&head) {
+                return Some((head, tail));
+            }
+        }
+        None
+    }
+
+    /// Splits a word that is too long for a line at the last character that fits.
+    fn break_word(&self, word: &[Piece]) -> (Vec<Piece>, Vec<Piece>) {
+        let text = plain_text(word);
+        let mut end = 0;
+        for (i, c) in text.char_indices() {
+            let next = i + c.len_utf8();
+            if end > 0 && !self.fits(&split_pieces(word, next).0) {
+                break;
+            }
+            end = next;
+        }
+        split_pieces(word, end)
+    }
+
+    /// Breaks the words of a paragraph into lines. Empty paragraphs get an empty line in
+    /// the style of span so that they take up space.
+    fn break_paragraph(&mut self, words: Vec<Vec<Piece>>, span: usize) {
+        let text: Vec<String> = words.iter().map(|word| plain_text(word)).collect();
+        let rtl = is_rtl(&text.join(" "));
+        let mut words: VecDeque<Vec<Piece>> = words.into_iter().collect();
+        let mut line = Vec::new();
+        while let Some(word) = words.pop_front() {
+            if self.is_full() {
+                return;
+            }
+            let candidate = self.join(&line, &word);
+            if self.fits(&candidate) {
+                line = candidate;
+            } else if let Some((head, tail)) = self.hyphenate(&line, &word) {
+                self.lines.push((head, false, rtl));
+                line.clear();
+                words.push_front(tail);
+            } else if line.is_empty() {
+                let (head, tail) = self.break_word(&word);
+                self.lines.push((head, false, rtl));
+                if !tail.is_empty() {
+                    words.push_front(tail);
+                }
+            } else {
+                self.lines.push((line, false, rtl));
+                line = Vec::new();
+                words.push_front(word);
+            }
+        }
+        if line.is_empty() {
+            line.push(Piece {
+                span: span,
+                text: String::new(),
+            });
+        }
+        self.lines.push((line, true, rtl));
+    }
+
+    fn break_text(&mut self) {
+        let mut words = Vec::new();
+        let mut word = Vec::new();
+        let mut last_span = 0;
+        let mut buffer = [0; 4];
+        for (i, span) in self.spans.iter().enumerate() {
+            last_span = i;
+            for c in span.text.chars() {
+                if c.is_whitespace() {
+                    if !word.is_empty() {
+                        words.push(word);
+                        word = Vec::new();
+                    }
+                    if c == '\n' {
+                        if self.is_full() {
+                            return;
+                        }
+                        self.break_paragraph(words, i);
+                        words = Vec::new();
+                    }
+                } else {
+                    push_text(&mut word, i, c.encode_utf8(&mut buffer));
+                }
+            }
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+        if !self.is_full() {
+            self.break_paragraph(words, last_span);
+        }
+    }
+}
+
+impl<'a> Paragraph<'a> {
+    /// Lays out text with a font and size without drawing it.
+    ///
+    /// Line breaks in the text start new paragraphs.
+    pub fn new<F: GlyphSource>(
+        s: &str,
+        f: &'a F,
+        pointsize: VGfloat,
+        layout: &Layout,
+    ) -> Paragraph<'a> {
+        let mut text = AttributedString::new();
+        text.push(s, &TextStyle::new(f, pointsize));
+        Paragraph::from_attributed(&text, layout)
+    }
+
+    /// Lays out rich text without drawing it.
+    ///
+    /// Lines are as tall as their tallest spans, and line spacing scales each line's
+    /// height.
+    pub fn from_attributed(s: &AttributedString<'a>, layout: &Layout) -> Paragraph<'a> {
+        if s.spans.is_empty() {
+            return Paragraph {
+                lines: Vec::new(),
+                width: 0.0,
+                height: 0.0,
+                truncated: false,
+            };
+        }
+        let mut breaker = LineBreaker {
+            spans: &s.spans,
+            layout: layout,
+            lines: Vec::new(),
+        };
+        breaker.break_text();
+        let mut lines = mem::replace(&mut breaker.lines, Vec::new());
+
+        let mut truncated = false;
+        if let Some(max_lines) = layout.max_lines {
+            if lines.len() > max_lines {
+                truncated = true;
+                lines.truncate(max_lines);
+                if let Some(last) = lines.last_mut() {
+                    let span = last.0.last().map(|piece| piece.span).unwrap_or(0);
+                    let mut pieces = last.0.clone();
+                    loop {
+                        while plain_text(&pieces).ends_with(' ') {
+                            pop_char(&mut pieces);
+                        }
+                        let mut candidate = pieces.clone();
+                        push_text(&mut candidate, span, &layout.ellipsis);
+                        if pieces.is_empty() || breaker.fits(&candidate) {
+                            last.0 = candidate;
+                            break;
+                        }
+                        pop_char(&mut pieces);
+                    }
+                    last.1 = true;
+                }
+            }
+        }
+
+        let mut paragraph = Paragraph {
+            lines: Vec::new(),
+            width: 0.0,
+            height: 0.0,
+            truncated: truncated,
+        };
+        for (pieces, last, rtl) in lines.into_iter() {
+            let text = breaker.attributed(&pieces);
+            let width = text.width();
+            let spare = layout.width - width;
+            let (start, end) = if rtl { (spare, 0.0) } else { (0.0, spare) };
+            let (x, word_gap) = match layout.align {
+                Align::Start => (start, 0.0),
+                Align::End => (end, 0.0),
+                Align::Left => (0.0, 0.0),
+                Align::Center => (spare / 2.0, 0.0),
+                Align::Right => (spare, 0.0),
+                Align::Justify => {
+                    let gaps = plain_text(&pieces).matches(' ').count();
+                    if last || gaps == 0 || spare <= 0.0 {
+                        (start, 0.0)
+                    } else {
+                        (0.0, spare / gaps as VGfloat)
+                    }
+                }
+            };
+            let width = if word_gap > 0.0 { layout.width } else { width };
+            let ascent = text.ascent();
+            let line_height = (ascent + text.descent()) * layout.line_spacing;
+            paragraph.width = paragraph.width.max(width);
+            paragraph.lines.push(Line {
+                text: text,
+                x: x,
+                baseline: paragraph.height + ascent,
+                width: width,
+                word_gap: word_gap,
+                rtl: rtl,
+            });
+            paragraph.height += line_height;
+        }
+        paragraph
+    }
+
+    /// Draws the paragraph with its top left corner at x, y.
+    pub fn draw(&self, x: VGfloat, y: VGfloat) {
+        for line in self.lines.iter() {
+            draw::rich_text_spaced(
+                x + line.x,
+                y - line.baseline,
+                &line.text,
+                line.word_gap,
+                Some(line.rtl),
+            );
+        }
+    }
+}
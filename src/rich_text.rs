@@ -0,0 +1,148 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text made of spans with their own fonts, sizes, colors and decorations.
+
+use draw;
+use font::GlyphSource;
+use openvg::VGfloat;
+
+/// The appearance of a span of text.
+#[derive(Clone)]
+pub struct TextStyle<'a> {
+    /// The Font or FontFamily used to draw the span.
+    pub font: &'a GlyphSource,
+    pub pointsize: VGfloat,
+    /// The fill color, or None to draw with the current fill paint.
+    pub color: Option<[VGfloat; 4]>,
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// How far the span is raised above the baseline, or lowered if negative.
+    pub baseline_shift: VGfloat,
+}
+
+impl<'a> TextStyle<'a> {
+    /// Creates a plain style with a font and size.
+    pub fn new(font: &'a GlyphSource, pointsize: VGfloat) -> TextStyle<'a> {
+        TextStyle {
+            font: font,
+            pointsize: pointsize,
+            color: None,
+            underline: false,
+            strikethrough: false,
+            baseline_shift: 0.0,
+        }
+    }
+
+    /// Sets the fill color, usually made with `draw::rgb()` or `draw::rgba()`.
+    pub fn color(mut self, color: [VGfloat; 4]) -> TextStyle<'a> {
+        self.color = Some(color);
+        self
+    }
+
+    /// Draws a line under the span.
+    pub fn underline(mut self) -> TextStyle<'a> {
+        self.underline = true;
+        self
+    }
+
+    /// Draws a line through the span.
+    pub fn strikethrough(mut self) -> TextStyle<'a> {
+        self.strikethrough = true;
+        self
+    }
+
+    /// Raises the span by shift, or lowers it if shift is negative.
+    pub fn baseline_shift(mut self, shift: VGfloat) -> TextStyle<'a> {
+        self.baseline_shift = shift;
+        self
+    }
+
+    /// Returns how far the style's text extends above the baseline.
+    pub fn ascent(&self) -> VGfloat {
+        draw::text_height(self.font, self.pointsize) - draw::text_depth(self.font, self.pointsize)
+            + self.baseline_shift
+    }
+
+    /// Returns how far the style's text extends below the baseline.
+    pub fn descent(&self) -> VGfloat {
+        draw::text_depth(self.font, self.pointsize) - self.baseline_shift
+    }
+}
+
+/// A run of text drawn in one style.
+#[derive(Clone)]
+pub struct Span<'a> {
+    pub text: String,
+    pub style: TextStyle<'a>,
+}
+
+/// A string made of styled spans.
+///
+/// Each span is shaped separately, so text is not kerned across span boundaries and
+/// right-to-left text is only reordered within a span.
+#[derive(Clone)]
+pub struct AttributedString<'a> {
+    pub spans: Vec<Span<'a>>,
+}
+
+impl<'a> AttributedString<'a> {
+    /// Creates an empty AttributedString.
+    pub fn new() -> AttributedString<'a> {
+        AttributedString { spans: Vec::new() }
+    }
+
+    /// Appends text drawn in a style.
+    pub fn push(&mut self, text: &str, style: &TextStyle<'a>) -> &mut AttributedString<'a> {
+        self.spans.push(Span {
+            text: text.to_string(),
+            style: style.clone(),
+        });
+        self
+    }
+
+    /// Returns the text of all of the spans.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Returns true if none of the spans have text.
+    pub fn is_empty(&self) -> bool {
+        self.spans.iter().all(|span| span.text.is_empty())
+    }
+
+    /// Returns the width of the string.
+    pub fn width(&self) -> VGfloat {
+        self.spans
+            .iter()
+            .map(|span| draw::text_width(&span.text, span.style.font, span.style.pointsize))
+            .sum()
+    }
+
+    /// Returns how far the tallest span extends above the baseline.
+    pub fn ascent(&self) -> VGfloat {
+        self.spans
+            .iter()
+            .map(|span| span.style.ascent())
+            .fold(0.0, VGfloat::max)
+    }
+
+    /// Returns how far the deepest span extends below the baseline.
+    pub fn descent(&self) -> VGfloat {
+        self.spans
+            .iter()
+            .map(|span| span.style.descent())
+            .fold(0.0, VGfloat::max)
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Frame sequences loaded from sprite sheets and animated GIFs.
+
+use draw;
+use image::{Bitmap, Error, Format, Image};
+use openvg::{VGfloat, VGint};
+use serde_json;
+
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::Read;
+use std::iter::Peekable;
+use std::path::Path;
+use std::time::Duration;
+
+/// GIF frames with no delay are shown for this long, as browsers do.
+const DEFAULT_GIF_DELAY: u64 = 100;
+
+/// Specifies what happens when playback reaches the last frame.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Playback {
+    /// Starts again from the first frame.
+    Loop,
+    /// Plays backwards to the first frame, then forwards again.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// A region of one of a sprite's images shown for a duration.
+struct Frame {
+    image: usize,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    duration: u64,
+}
+
+/// An animated sequence of frames.
+///
+/// Playback is driven by the times passed to `tick()`, usually the elapsed time carried
+/// by `Event::Tick`.
+pub struct Sprite {
+    images: Vec<Image>,
+    frames: Vec<Frame>,
+    playback: Playback,
+    start: Option<u64>,
+    now: u64,
+}
+
+fn millis(d: Duration) -> u64 {
+    d.as_secs() * 1000 + (d.subsec_nanos() / 1_000_000) as u64
+}
+
+/// Compares frame names with runs of digits ordered by their value, so that "frame2"
+/// comes before "frame10".
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+    loop {
+        let (x, y) = match (a.peek(), b.peek()) {
+            (Some(&x), Some(&y)) => (x, y),
+            (x, y) => return x.is_some().cmp(&y.is_some()),
+        };
+        let order = if x.is_ascii_digit() && y.is_ascii_digit() {
+            let m = number(&mut a);
+            let n = number(&mut b);
+            m.len().cmp(&n.len()).then_with(|| m.cmp(&n))
+        } else {
+            a.next();
+            b.next();
+            x.cmp(&y)
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+/// Takes a run of digits, dropping leading zeros.
+fn number<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> String {
+    let mut digits = String::new();
+    loop {
+        let c = match chars.peek() {
+            Some(&c) if c.is_ascii_digit() => c,
+            _ => break,
+        };
+        if !digits.is_empty() || c != '0' {
+            digits.push(c);
+        }
+        chars.next();
+    }
+    digits
+}
+
+impl Sprite {
+    fn new(images: Vec<Image>, frames: Vec<Frame>) -> Sprite {
+        Sprite {
+            images: images,
+            frames: frames,
+            playback: Playback::Loop,
+            start: None,
+            now: 0,
+        }
+    }
+
+    /// Creates a Sprite from a sheet of equally sized frames.
+    ///
+    /// Frames are read left to right, top to bottom.
+    pub fn from_grid(image: Image, columns: u32, rows: u32, frame_duration: Duration) -> Sprite {
+        let mut frames = Vec::new();
+        if columns > 0 && rows > 0 {
+            let w = image.width() / columns;
+            let h = image.height() / rows;
+            for row in 0..rows {
+                for column in 0..columns {
+                    frames.push(Frame {
+                        image: 0,
+                        x: column * w,
+                        y: row * h,
+                        w: w,
+                        h: h,
+                        duration: millis(frame_duration),
+                    });
+                }
+            }
+        }
+        Sprite::new(vec![image], frames)
+    }
+
+    /// Creates a Sprite from a sheet and a JSON atlas describing its frames.
+    ///
+    /// The atlas uses the TexturePacker layout exported by most sprite tools: "frames" is
+    /// either an array of entries or an object keyed by frame name. Named frames are
+    /// played in name order, with numbers compared by value so that "frame2" comes
+    /// before "frame10". Each entry has a "frame" rectangle with "x", "y", "w" and "h"
+    /// measured from the top left of the sheet, and may have a "duration" in milliseconds.
+    pub fn from_atlas(image: Image, json: &str, frame_duration: Duration) -> Result<Sprite, Error> {
+        let atlas: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| Error::Decode(e.to_string()))?;
+        let entries: Vec<&serde_json::Value> = match atlas.get("frames") {
+            Some(&serde_json::Value::Array(ref entries)) => entries.iter().collect(),
+            Some(&serde_json::Value::Object(ref entries)) => {
+                let mut named: Vec<(&String, &serde_json::Value)> = entries.iter().collect();
+                named.sort_by(|a, b| natural_cmp(a.0, b.0));
+                named.into_iter().map(|(_, entry)| entry).collect()
+            }
+            _ => return Err(Error::Decode("atlas has no frames".to_string())),
+        };
+        let mut frames = Vec::new();
+        for entry in entries {
+            let rect = match entry.get("frame") {
+                Some(rect) => rect,
+                None => return Err(Error::Decode("atlas frame has no rectangle".to_string())),
+            };
+            let field = |name: &str| match rect.get(name).and_then(|v| v.as_u64()) {
+                Some(v) => Ok(v as u32),
+                None => Err(Error::Decode(format!("atlas frame is missing {}", name))),
+            };
+            let duration = match entry.get("duration").and_then(|v| v.as_u64()) {
+                Some(duration) => duration,
+                None => millis(frame_duration),
+            };
+            frames.push(Frame {
+                image: 0,
+                x: field("x")?,
+                y: field("y")?,
+                w: field("w")?,
+                h: field("h")?,
+                duration: duration,
+            });
+        }
+        Ok(Sprite::new(vec![image], frames))
+    }
+
+    /// Creates a Sprite from a sheet image file and a JSON atlas file.
+    pub fn from_atlas_file<P: AsRef<Path>, Q: AsRef<Path>>(
+        image_path: P,
+        atlas_path: Q,
+        frame_duration: Duration,
+    ) -> Result<Sprite, Error> {
+        let image = Image::from_file(image_path)?;
+        let mut json = String::new();
+        File::open(atlas_path)?.read_to_string(&mut json)?;
+        Sprite::from_atlas(image, &json, frame_duration)
+    }
+
+    /// Creates a Sprite from the frames of an animated GIF.
+    pub fn from_gif(data: &[u8]) -> Result<Sprite, Error> {
+        let mut images = Vec::new();
+        let mut frames = Vec::new();
+        for (bitmap, delay) in Bitmap::decode_gif_frames(data)? {
+            frames.push(Frame {
+                image: images.len(),
+                x: 0,
+                y: 0,
+                w: bitmap.width,
+                h: bitmap.height,
+                duration: if delay == 0 {
+                    DEFAULT_GIF_DELAY
+                } else {
+                    delay as u64
+                },
+            });
+            images.push(Image::from_bitmap(&bitmap, Format::Rgba)?);
+        }
+        Ok(Sprite::new(images, frames))
+    }
+
+    /// Creates a Sprite from an animated GIF file.
+    pub fn from_gif_file<P: AsRef<Path>>(path: P) -> Result<Sprite, Error> {
+        let mut data = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        Sprite::from_gif(&data)
+    }
+
+    /// Returns the number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the size of the current frame in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        match self.frames.get(self.frame()) {
+            Some(frame) => (frame.w, frame.h),
+            None => (0, 0),
+        }
+    }
+
+    /// Returns the playback mode.
+    pub fn playback(&self) -> Playback {
+        self.playback
+    }
+
+    /// Sets the playback mode and restarts from the first frame.
+    pub fn set_playback(&mut self, playback: Playback) {
+        self.playback = playback;
+        self.restart();
+    }
+
+    /// Restarts from the first frame at the next tick.
+    pub fn restart(&mut self) {
+        self.start = None;
+    }
+
+    /// Advances the animation to time now.
+    pub fn tick(&mut self, now: Duration) {
+        let now = millis(now);
+        if self.start.is_none() {
+            self.start = Some(now);
+        }
+        self.now = now;
+    }
+
+    /// Returns true if a Once animation has reached its last frame.
+    pub fn is_finished(&self) -> bool {
+        self.playback == Playback::Once && self.elapsed() >= self.total(0, self.frames.len())
+    }
+
+    fn elapsed(&self) -> u64 {
+        match self.start {
+            Some(start) if self.now > start => self.now - start,
+            _ => 0,
+        }
+    }
+
+    /// Returns the total duration of frames first..last.
+    fn total(&self, first: usize, last: usize) -> u64 {
+        self.frames[first..last]
+            .iter()
+            .fold(0, |sum, frame| sum + frame.duration)
+    }
+
+    /// Returns the index of the current frame.
+    pub fn frame(&self) -> usize {
+        let n = self.frames.len();
+        if n < 2 {
+            return 0;
+        }
+        let forward = self.total(0, n);
+        if forward == 0 {
+            return 0;
+        }
+        let mut t = self.elapsed();
+        match self.playback {
+            Playback::Once => {
+                if t >= forward {
+                    return n - 1;
+                }
+            }
+            Playback::Loop => t = t % forward,
+            Playback::PingPong => {
+                // The way back skips the end frames so that they aren't shown twice.
+                let backward = self.total(1, n - 1);
+                t = t % (forward + backward);
+                if t >= forward {
+                    t -= forward;
+                    for i in (1..n - 1).rev() {
+                        if t < self.frames[i].duration {
+                            return i;
+                        }
+                        t -= self.frames[i].duration;
+                    }
+                    return 0;
+                }
+            }
+        }
+        for i in 0..n {
+            if t < self.frames[i].duration {
+                return i;
+            }
+            t -= self.frames[i].duration;
+        }
+        n - 1
+    }
+
+    /// Draws the current frame stretched into the specified rectangle.
+    pub fn draw(&self, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+        let frame = self.frame();
+        self.draw_frame(frame, x, y, w, h);
+    }
+
+    /// Draws a frame stretched into the specified rectangle.
+    pub fn draw_frame(&self, index: usize, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+        let frame = match self.frames.get(index) {
+            Some(frame) => frame,
+            None => return,
+        };
+        let image = &self.images[frame.image];
+        // Frames are measured from the top of the sheet, images from the bottom.
+        let sy = image.height() as VGint - (frame.y + frame.h) as VGint;
+        draw::image_crop(
+            image,
+            frame.x as VGint,
+            sy,
+            frame.w as VGint,
+            frame.h as VGint,
+            x,
+            y,
+            w,
+            h,
+        );
+    }
+}
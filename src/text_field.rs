@@ -0,0 +1,859 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Editable text and a single-line text input field.
+
+use draw;
+use event::{Event, Key, Touch};
+use font::GlyphSource;
+use key;
+use openvg::{VGfloat, VGint};
+use rich_text::{AttributedString, TextStyle};
+
+use std::mem;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long the caret stays shown, and then hidden, while blinking.
+const BLINK_INTERVAL: u64 = 500;
+
+/// The character drawn for each character of a password.
+const PASSWORD_MASK: char = '\u{2022}';
+
+lazy_static! {
+    /// The clipboard shared by all editable text.
+    static ref CLIPBOARD: Mutex<String> = Mutex::new(String::new());
+}
+
+/// Restricts what can be entered into editable text.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InputMode {
+    /// Any text.
+    Text,
+    /// Any text, drawn masked. Masked text can't be cut or copied.
+    Password,
+    /// Digits, with an optional leading minus sign and one decimal point.
+    Numeric,
+}
+
+/// The state restored by undo and redo.
+#[derive(Clone)]
+struct Snapshot {
+    text: String,
+    caret: usize,
+    anchor: usize,
+}
+
+/// The kind of the last change, used to undo runs of typing in one step.
+#[derive(Copy, Clone, PartialEq)]
+enum Change {
+    None,
+    Typing,
+    Other,
+}
+
+/// Text being edited, with a caret, a selection, composed input and undo history.
+///
+/// Positions are byte offsets into the text that fall on character boundaries. The
+/// selection runs between the anchor and the caret.
+pub struct EditableText {
+    text: String,
+    caret: usize,
+    anchor: usize,
+    composition: Option<String>,
+    mode: InputMode,
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    last_change: Change,
+}
+
+impl EditableText {
+    /// Creates empty editable text.
+    pub fn new(mode: InputMode) -> EditableText {
+        EditableText {
+            text: String::new(),
+            caret: 0,
+            anchor: 0,
+            composition: None,
+            mode: mode,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last_change: Change::None,
+        }
+    }
+
+    /// Returns the text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text, placing the caret at its end and clearing the undo history.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.caret = self.text.len();
+        self.anchor = self.caret;
+        self.composition = None;
+        self.undo.clear();
+        self.redo.clear();
+        self.last_change = Change::None;
+    }
+
+    /// Returns the input mode.
+    pub fn mode(&self) -> InputMode {
+        self.mode
+    }
+
+    /// Sets the input mode. Existing text is kept as it is.
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.mode = mode;
+    }
+
+    /// Returns the position of the caret.
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Returns the start and end of the selection, or None if nothing is selected.
+    pub fn selection(&self) -> Option<(usize, usize)> {
+        if self.caret == self.anchor {
+            None
+        } else {
+            Some((self.caret.min(self.anchor), self.caret.max(self.anchor)))
+        }
+    }
+
+    /// Returns the selected text.
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some((start, end)) => &self.text[start..end],
+            None => "",
+        }
+    }
+
+    /// Returns the text being composed, which is shown at the caret but isn't part of
+    /// the text until it is committed.
+    pub fn composition(&self) -> Option<&str> {
+        self.composition.as_ref().map(|s| s.as_str())
+    }
+
+    /// Moves the caret to pos, extending the selection if extend is true.
+    pub fn set_caret(&mut self, pos: usize, extend: bool) {
+        let mut pos = pos.min(self.text.len());
+        while !self.text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        self.caret = pos;
+        if !extend {
+            self.anchor = pos;
+        }
+        self.last_change = Change::None;
+    }
+
+    /// Moves the caret back one character, or to the start of the selection.
+    pub fn move_left(&mut self, extend: bool) {
+        let pos = match self.selection() {
+            Some((start, _)) if !extend => start,
+            _ => self.previous(self.caret),
+        };
+        self.set_caret(pos, extend);
+    }
+
+    /// Moves the caret forward one character, or to the end of the selection.
+    pub fn move_right(&mut self, extend: bool) {
+        let pos = match self.selection() {
+            Some((_, end)) if !extend => end,
+            _ => self.next(self.caret),
+        };
+        self.set_caret(pos, extend);
+    }
+
+    /// Moves the caret to the start of the text.
+    pub fn move_home(&mut self, extend: bool) {
+        self.set_caret(0, extend);
+    }
+
+    /// Moves the caret to the end of the text.
+    pub fn move_end(&mut self, extend: bool) {
+        let end = self.text.len();
+        self.set_caret(end, extend);
+    }
+
+    /// Selects all of the text.
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.text.len();
+        self.last_change = Change::None;
+    }
+
+    fn previous(&self, pos: usize) -> usize {
+        match self.text[..pos].char_indices().last() {
+            Some((i, _)) => i,
+            None => 0,
+        }
+    }
+
+    fn next(&self, pos: usize) -> usize {
+        match self.text[pos..].chars().next() {
+            Some(c) => pos + c.len_utf8(),
+            None => pos,
+        }
+    }
+
+    /// Records the state before a change so that it can be undone.
+    fn record(&mut self, change: Change) {
+        if change != Change::Typing || self.last_change != Change::Typing {
+            self.undo.push(Snapshot {
+                text: self.text.clone(),
+                caret: self.caret,
+                anchor: self.anchor,
+            });
+        }
+        self.redo.clear();
+        self.last_change = change;
+    }
+
+    /// Replaces the selection with s, dropping characters the input mode doesn't allow.
+    fn replace_selection(&mut self, s: &str, change: Change) -> bool {
+        let (start, end) = self.selection().unwrap_or((self.caret, self.caret));
+        let mut inserted = String::new();
+        for c in s.chars() {
+            if self.allows(c, &inserted, start, end) {
+                inserted.push(c);
+            }
+        }
+        // Only deleting, with an empty s, removes a selection without replacing it.
+        if inserted.is_empty() && (start == end || !s.is_empty()) {
+            return false;
+        }
+        self.record(change);
+        self.text = format!("{}{}{}", &self.text[..start], inserted, &self.text[end..]);
+        self.caret = start + inserted.len();
+        self.anchor = self.caret;
+        true
+    }
+
+    /// Returns true if c may follow inserted, which replaces start..end.
+    fn allows(&self, c: char, inserted: &str, start: usize, end: usize) -> bool {
+        if c.is_control() {
+            return false;
+        }
+        if self.mode != InputMode::Numeric {
+            return true;
+        }
+        let before = &self.text[..start];
+        let after = &self.text[end..];
+        match c {
+            '0'..='9' => !(before.is_empty() && inserted.is_empty() && after.starts_with('-')),
+            '-' => before.is_empty() && inserted.is_empty() && !after.starts_with('-'),
+            '.' => {
+                !before.contains('.')
+                    && !inserted.contains('.')
+                    && !after.contains('.')
+                    && !(before.is_empty() && inserted.is_empty() && after.starts_with('-'))
+            }
+            _ => false,
+        }
+    }
+
+    /// Types text at the caret, replacing the selection.
+    ///
+    /// Consecutive typing is undone in one step.
+    pub fn insert(&mut self, s: &str) -> bool {
+        self.replace_selection(s, Change::Typing)
+    }
+
+    /// Deletes the selection or the character before the caret.
+    pub fn backspace(&mut self) -> bool {
+        if self.selection().is_none() {
+            if self.caret == 0 {
+                return false;
+            }
+            self.anchor = self.previous(self.caret);
+        }
+        self.replace_selection("", Change::Other)
+    }
+
+    /// Deletes the selection or the character after the caret.
+    pub fn delete(&mut self) -> bool {
+        if self.selection().is_none() {
+            if self.caret == self.text.len() {
+                return false;
+            }
+            self.anchor = self.next(self.caret);
+        }
+        self.replace_selection("", Change::Other)
+    }
+
+    /// Copies the selection to the clipboard.
+    pub fn copy(&self) -> bool {
+        if self.mode == InputMode::Password || self.selection().is_none() {
+            return false;
+        }
+        *CLIPBOARD.lock().unwrap() = self.selected_text().to_string();
+        true
+    }
+
+    /// Moves the selection to the clipboard.
+    pub fn cut(&mut self) -> bool {
+        self.copy() && self.replace_selection("", Change::Other)
+    }
+
+    /// Replaces the selection with the clipboard.
+    pub fn paste(&mut self) -> bool {
+        let text = CLIPBOARD.lock().unwrap().clone();
+        !text.is_empty() && self.replace_selection(&text, Change::Other)
+    }
+
+    /// Undoes the last change.
+    pub fn undo(&mut self) -> bool {
+        match self.undo.pop() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.redo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redoes the last undone change.
+    pub fn redo(&mut self) -> bool {
+        match self.redo.pop() {
+            Some(snapshot) => {
+                let current = self.restore(snapshot);
+                self.undo.push(current);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replaces the state with a snapshot and returns the state it replaced.
+    fn restore(&mut self, snapshot: Snapshot) -> Snapshot {
+        self.composition = None;
+        self.last_change = Change::None;
+        Snapshot {
+            text: mem::replace(&mut self.text, snapshot.text),
+            caret: mem::replace(&mut self.caret, snapshot.caret),
+            anchor: mem::replace(&mut self.anchor, snapshot.anchor),
+        }
+    }
+
+    /// Sets the text being composed, as an input method does while a character is built
+    /// from several keys. The selection is deleted when composition starts.
+    pub fn set_composition(&mut self, s: &str) {
+        if self.composition.is_none() && self.selection().is_some() {
+            self.replace_selection("", Change::Other);
+        }
+        self.composition = Some(s.to_string());
+    }
+
+    /// Inserts the text being composed at the caret.
+    pub fn commit_composition(&mut self) -> bool {
+        match self.composition.take() {
+            Some(s) => self.insert(&s),
+            None => false,
+        }
+    }
+
+    /// Discards the text being composed.
+    pub fn cancel_composition(&mut self) -> bool {
+        self.composition.take().is_some()
+    }
+
+    /// Returns text as it is drawn, masked if it is a password.
+    fn display(&self, s: &str) -> String {
+        match self.mode {
+            InputMode::Password => s.chars().map(|_| PASSWORD_MASK).collect(),
+            _ => s.to_string(),
+        }
+    }
+}
+
+fn millis(d: Duration) -> u64 {
+    d.as_secs() * 1000 + (d.subsec_nanos() / 1_000_000) as u64
+}
+
+/// A single-line text input drawn in a rectangle.
+///
+/// The field edits its text in response to key and touch events once it has been
+/// touched. Text and the caret are drawn with the current fill paint, and the caret
+/// blinks with the times carried by `Event::Tick`.
+pub struct TextField<'a> {
+    text: EditableText,
+    font: &'a GlyphSource,
+    pointsize: VGfloat,
+    frame: [VGfloat; 4],
+    padding: VGfloat,
+    selection_color: (u32, u32, u32, VGfloat),
+    focused: bool,
+    shift: bool,
+    control: bool,
+    dragging: Option<usize>,
+    scroll: VGfloat,
+    now: u64,
+    blink_start: u64,
+}
+
+impl<'a> TextField<'a> {
+    /// Creates an empty TextField in the rectangle at x, y with size w, h.
+    pub fn new(
+        font: &'a GlyphSource,
+        pointsize: VGfloat,
+        x: VGfloat,
+        y: VGfloat,
+        w: VGfloat,
+        h: VGfloat,
+    ) -> TextField<'a> {
+        TextField {
+            text: EditableText::new(InputMode::Text),
+            font: font,
+            pointsize: pointsize,
+            frame: [x, y, w, h],
+            padding: pointsize / 4.0,
+            selection_color: (128, 160, 255, 0.5),
+            focused: false,
+            shift: false,
+            control: false,
+            dragging: None,
+            scroll: 0.0,
+            now: 0,
+            blink_start: 0,
+        }
+    }
+
+    /// Returns the text being edited.
+    pub fn text(&self) -> &EditableText {
+        &self.text
+    }
+
+    /// Returns the text being edited for changes.
+    pub fn text_mut(&mut self) -> &mut EditableText {
+        &mut self.text
+    }
+
+    /// Moves the field to the rectangle at x, y with size w, h.
+    pub fn set_frame(&mut self, x: VGfloat, y: VGfloat, w: VGfloat, h: VGfloat) {
+        self.frame = [x, y, w, h];
+        self.scroll_to_caret();
+    }
+
+    /// Sets the color drawn behind selected text.
+    pub fn set_selection_color(&mut self, r: u32, g: u32, b: u32, a: VGfloat) {
+        self.selection_color = (r, g, b, a);
+    }
+
+    /// Returns true if key events edit the field.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Sets whether key events edit the field.
+    pub fn set_focused(&mut self, focused: bool) {
+        if !focused {
+            self.text.commit_composition();
+            self.dragging = None;
+        }
+        self.focused = focused;
+        self.blink_start = self.now;
+    }
+
+    /// Updates the field for an event and returns true if the event was used.
+    pub fn handle(&mut self, ev: &Event) -> bool {
+        match *ev {
+            Event::Key(k, _) => self.handle_key(k),
+            Event::Touch(t, _) => self.handle_touch(t),
+            Event::Text(ref s, _) => {
+                if !self.focused {
+                    return false;
+                }
+                self.text.commit_composition();
+                self.text.insert(s);
+                self.changed();
+                true
+            }
+            Event::Tick(t) => {
+                self.now = millis(t);
+                false
+            }
+        }
+    }
+
+    fn handle_key(&mut self, k: Key) -> bool {
+        if key::is_shift(k.key) {
+            self.shift = k.kind != key::RELEASED;
+            return false;
+        }
+        if key::is_control(k.key) {
+            self.control = k.kind != key::RELEASED;
+            return false;
+        }
+        if !self.focused || k.kind == key::RELEASED {
+            return false;
+        }
+        let shift = self.shift;
+        let text = &mut self.text;
+        match k.key {
+            key::LEFT => text.move_left(shift),
+            key::RIGHT => text.move_right(shift),
+            key::HOME | key::UP => text.move_home(shift),
+            key::END | key::DOWN => text.move_end(shift),
+            key::BACKSPACE => {
+                if !text.cancel_composition() {
+                    text.backspace();
+                }
+            }
+            key::DELETE => {
+                text.delete();
+            }
+            key::ESC => {
+                if !text.cancel_composition() {
+                    return false;
+                }
+            }
+            key::ENTER => {
+                if !text.commit_composition() {
+                    return false;
+                }
+            }
+            code if self.control => match code {
+                key::A => text.select_all(),
+                key::C => {
+                    text.copy();
+                }
+                key::X => {
+                    text.cut();
+                }
+                key::V => {
+                    text.paste();
+                }
+                key::Z if shift => {
+                    text.redo();
+                }
+                key::Z => {
+                    text.undo();
+                }
+                key::Y => {
+                    text.redo();
+                }
+                _ => return false,
+            },
+            code => match key::to_char(code, shift) {
+                Some(c) => {
+                    text.commit_composition();
+                    text.insert(&c.to_string());
+                }
+                None => return false,
+            },
+        }
+        self.changed();
+        true
+    }
+
+    fn handle_touch(&mut self, t: Touch) -> bool {
+        // Touches are measured down from the top of the screen.
+        let (_, height) = draw::canvas_size();
+        let x = t.x as VGfloat;
+        let y = height as VGfloat - t.y as VGfloat;
+        match t.kind {
+            1 => {
+                let f = self.frame;
+                if x < f[0] || x > f[0] + f[2] || y < f[1] || y > f[1] + f[3] {
+                    if self.focused {
+                        self.set_focused(false);
+                    }
+                    return false;
+                }
+                if !self.focused {
+                    self.set_focused(true);
+                }
+                self.text.commit_composition();
+                let pos = self.position_at(x);
+                self.text.set_caret(pos, self.shift);
+                self.dragging = Some(t.slot);
+            }
+            2 if self.dragging == Some(t.slot) => {
+                let pos = self.position_at(x);
+                self.text.set_caret(pos, true);
+            }
+            3 if self.dragging == Some(t.slot) => self.dragging = None,
+            _ => return false,
+        }
+        self.changed();
+        true
+    }
+
+    /// Restarts the caret blink and keeps the caret in view after a change.
+    fn changed(&mut self) {
+        self.blink_start = self.now;
+        self.scroll_to_caret();
+    }
+
+    fn width(&self, s: &str) -> VGfloat {
+        draw::text_width(s, self.font, self.pointsize)
+    }
+
+    /// Returns the distance from the start of the text to a position.
+    fn offset(&self, pos: usize) -> VGfloat {
+        self.width(&self.text.display(&self.text.text()[..pos]))
+    }
+
+    /// Returns the distance from the start of the text to the caret, which follows any
+    /// text being composed.
+    fn caret_offset(&self) -> VGfloat {
+        let composition = self.text.composition().unwrap_or("");
+        self.width(&format!(
+            "{}{}",
+            self.text.display(&self.text.text()[..self.text.caret()]),
+            composition
+        ))
+    }
+
+    /// Returns the text position nearest to x.
+    fn position_at(&self, x: VGfloat) -> usize {
+        let target = x - (self.frame[0] + self.padding - self.scroll);
+        let text = self.text.text();
+        let mut best = 0;
+        let mut best_distance = target.abs();
+        for (i, c) in text.char_indices() {
+            let pos = i + c.len_utf8();
+            let distance = (self.offset(pos) - target).abs();
+            if distance < best_distance {
+                best = pos;
+                best_distance = distance;
+            }
+        }
+        best
+    }
+
+    fn scroll_to_caret(&mut self) {
+        let visible = (self.frame[2] - 2.0 * self.padding).max(0.0);
+        let caret = self.caret_offset();
+        if caret - self.scroll > visible {
+            self.scroll = caret - visible;
+        } else if caret < self.scroll {
+            self.scroll = caret;
+        }
+        let total = self.width(&self.text.display(self.text.text()));
+        self.scroll = self.scroll.min((total - visible).max(0.0)).max(0.0);
+    }
+
+    /// Returns true while a blinking caret is shown.
+    fn caret_shown(&self) -> bool {
+        self.focused && (self.now.saturating_sub(self.blink_start) / BLINK_INTERVAL) % 2 == 0
+    }
+
+    /// Draws the field's text, selection and caret, clipped to its rectangle.
+    pub fn draw(&self) {
+        let (x, y, w, h) = (self.frame[0], self.frame[1], self.frame[2], self.frame[3]);
+        let _state = draw::SavedState::new();
+        draw::clip_rect(
+            x.floor() as VGint,
+            y.floor() as VGint,
+            w.ceil() as VGint,
+            h.ceil() as VGint,
+        );
+
+        let height = draw::text_height(self.font, self.pointsize);
+        let depth = draw::text_depth(self.font, self.pointsize);
+        let bottom = y + (h - height) / 2.0;
+        let baseline = bottom + depth;
+        let left = x + self.padding - self.scroll;
+
+        if let Some((start, end)) = self.text.selection() {
+            let _state = draw::SavedState::new();
+            let (r, g, b, a) = self.selection_color;
+            draw::fill(r, g, b, a);
+            draw::stroke_width(0.0);
+            let x0 = left + self.offset(start);
+            draw::rect(x0, bottom, left + self.offset(end) - x0, height);
+        }
+
+        let text = self.text.text();
+        let caret = self.text.caret();
+        let style = TextStyle::new(self.font, self.pointsize);
+        let mut s = AttributedString::new();
+        s.push(&self.text.display(&text[..caret]), &style);
+        if let Some(composition) = self.text.composition() {
+            s.push(composition, &style.clone().underline());
+        }
+        s.push(&self.text.display(&text[caret..]), &style);
+        draw::rich_text(left, baseline, &s);
+
+        if self.caret_shown() {
+            let thickness = (height / 20.0).max(1.0);
+            let cx = left + self.caret_offset();
+            draw::rect(cx - thickness / 2.0, bottom, thickness, height);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(text: &str) -> EditableText {
+        let mut t = EditableText::new(InputMode::Numeric);
+        t.set_text(text);
+        t
+    }
+
+    #[test]
+    fn typing_is_undone_in_one_step() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.insert("a");
+        t.insert("b");
+        t.insert("c");
+        assert!(t.undo());
+        assert_eq!(t.text(), "");
+        assert!(!t.undo());
+        assert!(t.redo());
+        assert_eq!(t.text(), "abc");
+        assert_eq!(t.caret(), 3);
+    }
+
+    #[test]
+    fn moving_the_caret_ends_a_run_of_typing() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.insert("ab");
+        t.set_caret(1, false);
+        t.insert("x");
+        assert_eq!(t.text(), "axb");
+        t.undo();
+        assert_eq!(t.text(), "ab");
+        t.undo();
+        assert_eq!(t.text(), "");
+    }
+
+    #[test]
+    fn changes_clear_redo() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.insert("a");
+        t.undo();
+        t.insert("b");
+        assert!(!t.redo());
+        assert_eq!(t.text(), "b");
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.set_text("hello");
+        t.set_caret(1, false);
+        t.set_caret(4, true);
+        assert_eq!(t.selected_text(), "ell");
+        assert!(t.insert("i"));
+        assert_eq!(t.text(), "hio");
+        assert_eq!(t.caret(), 2);
+        assert_eq!(t.selection(), None);
+    }
+
+    #[test]
+    fn backspace_deletes_the_selection_or_one_character() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.set_text("h\u{e9}llo");
+        t.set_caret(3, false);
+        assert!(t.backspace());
+        assert_eq!(t.text(), "hllo");
+        t.select_all();
+        assert!(t.backspace());
+        assert_eq!(t.text(), "");
+        assert!(!t.backspace());
+    }
+
+    #[test]
+    fn rejected_text_keeps_the_selection() {
+        let mut t = numeric("12");
+        t.select_all();
+        assert!(!t.insert("x"));
+        assert_eq!(t.text(), "12");
+        assert_eq!(t.selection(), Some((0, 2)));
+        assert!(!t.undo());
+    }
+
+    #[test]
+    fn numeric_text_drops_other_characters() {
+        let mut t = numeric("");
+        t.insert("1a2b");
+        assert_eq!(t.text(), "12");
+    }
+
+    #[test]
+    fn numeric_text_allows_one_leading_minus_sign() {
+        let mut t = numeric("5");
+        assert!(!t.insert("-"));
+        t.set_caret(0, false);
+        t.insert("-");
+        assert_eq!(t.text(), "-5");
+        t.set_caret(0, false);
+        t.insert("-");
+        assert_eq!(t.text(), "-5");
+    }
+
+    #[test]
+    fn numeric_text_allows_nothing_before_a_minus_sign() {
+        let mut t = numeric("-5");
+        t.set_caret(0, false);
+        t.insert("1");
+        t.insert(".");
+        assert_eq!(t.text(), "-5");
+    }
+
+    #[test]
+    fn numeric_text_allows_one_decimal_point() {
+        let mut t = numeric("");
+        t.insert("1.2.3");
+        assert_eq!(t.text(), "1.23");
+        t.set_caret(0, false);
+        t.insert(".");
+        assert_eq!(t.text(), "1.23");
+    }
+
+    #[test]
+    fn copy_and_paste() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.set_text("abc");
+        t.set_caret(1, false);
+        t.set_caret(3, true);
+        assert!(t.copy());
+        t.move_end(false);
+        assert!(t.paste());
+        assert_eq!(t.text(), "abcbc");
+    }
+
+    #[test]
+    fn masked_text_cant_be_copied_or_cut() {
+        let mut t = EditableText::new(InputMode::Password);
+        t.set_text("secret");
+        t.select_all();
+        assert!(!t.copy());
+        assert!(!t.cut());
+        assert_eq!(t.text(), "secret");
+        assert_eq!(t.display(t.text()), "\u{2022}".repeat(6));
+    }
+
+    #[test]
+    fn committed_composition_is_typed() {
+        let mut t = EditableText::new(InputMode::Text);
+        t.insert("a");
+        t.set_composition("e");
+        assert_eq!(t.text(), "a");
+        assert_eq!(t.composition(), Some("e"));
+        assert!(t.commit_composition());
+        assert_eq!(t.text(), "ae");
+        assert_eq!(t.composition(), None);
+        t.undo();
+        assert_eq!(t.text(), "");
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Representation of affine transformations.
+
+use openvg::VGfloat;
+
+use std::ops::Mul;
+
+/// Represents a 2D affine transformation.
+///
+/// A point (x, y) is mapped to (sx * x + shx * y + tx, shy * x + sy * y + ty).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub sx: VGfloat,
+    pub shy: VGfloat,
+    pub shx: VGfloat,
+    pub sy: VGfloat,
+    pub tx: VGfloat,
+    pub ty: VGfloat,
+}
+
+/// The components of an affine transformation.
+///
+/// Applying them in the order scale, shear, rotate, translate rebuilds the transformation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Decomposition {
+    /// Translation along the x axis.
+    pub translate_x: VGfloat,
+    /// Translation along the y axis.
+    pub translate_y: VGfloat,
+    /// Rotation in degrees.
+    pub rotation: VGfloat,
+    /// Scale factor along the x axis.
+    pub scale_x: VGfloat,
+    /// Scale factor along the y axis.
+    pub scale_y: VGfloat,
+    /// Shear of the x coordinate by the y coordinate.
+    pub shear: VGfloat,
+}
+
+impl Affine {
+    /// Creates a new Affine.
+    pub fn new(
+        sx: VGfloat,
+        shy: VGfloat,
+        shx: VGfloat,
+        sy: VGfloat,
+        tx: VGfloat,
+        ty: VGfloat,
+    ) -> Affine {
+        Affine {
+            sx: sx,
+            shy: shy,
+            shx: shx,
+            sy: sy,
+            tx: tx,
+            ty: ty,
+        }
+    }
+
+    /// Creates the identity transformation.
+    pub fn identity() -> Affine {
+        Affine::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    /// Creates a translation by x, y.
+    pub fn translation(x: VGfloat, y: VGfloat) -> Affine {
+        Affine::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Creates a scaling by x, y.
+    pub fn scaling(x: VGfloat, y: VGfloat) -> Affine {
+        Affine::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    /// Creates a counterclockwise rotation by r degrees.
+    pub fn rotation(r: VGfloat) -> Affine {
+        let (s, c) = r.to_radians().sin_cos();
+        Affine::new(c, s, -s, c, 0.0, 0.0)
+    }
+
+    /// Creates a shear of the x coordinate by x and the y coordinate by y.
+    pub fn shearing(x: VGfloat, y: VGfloat) -> Affine {
+        Affine::new(1.0, y, x, 1.0, 0.0, 0.0)
+    }
+
+    /// Creates an Affine from a column-major OpenVG matrix.
+    pub fn from_matrix(m: &[VGfloat; 9]) -> Affine {
+        Affine::new(m[0], m[1], m[3], m[4], m[6], m[7])
+    }
+
+    /// Returns the column-major OpenVG matrix for this transformation.
+    pub fn to_matrix(&self) -> [VGfloat; 9] {
+        [
+            self.sx, self.shy, 0.0, self.shx, self.sy, 0.0, self.tx, self.ty, 1.0,
+        ]
+    }
+
+    /// Returns the transformation that applies other and then self.
+    pub fn multiply(&self, other: &Affine) -> Affine {
+        Affine::new(
+            self.sx * other.sx + self.shx * other.shy,
+            self.shy * other.sx + self.sy * other.shy,
+            self.sx * other.shx + self.shx * other.sy,
+            self.shy * other.shx + self.sy * other.sy,
+            self.sx * other.tx + self.shx * other.ty + self.tx,
+            self.shy * other.tx + self.sy * other.ty + self.ty,
+        )
+    }
+
+    /// Returns the determinant of the linear part of the transformation.
+    pub fn determinant(&self) -> VGfloat {
+        self.sx * self.sy - self.shx * self.shy
+    }
+
+    /// Returns the inverse transformation, or None if the transformation is singular.
+    pub fn invert(&self) -> Option<Affine> {
+        let det = self.determinant();
+        if det.abs() < ::std::f32::EPSILON {
+            return None;
+        }
+        let sx = self.sy / det;
+        let shy = -self.shy / det;
+        let shx = -self.shx / det;
+        let sy = self.sx / det;
+        Some(Affine::new(
+            sx,
+            shy,
+            shx,
+            sy,
+            -(sx * self.tx + shx * self.ty),
+            -(shy * self.tx + sy * self.ty),
+        ))
+    }
+
+    /// Maps a point through the transformation.
+    pub fn apply(&self, x: VGfloat, y: VGfloat) -> (VGfloat, VGfloat) {
+        (
+            self.sx * x + self.shx * y + self.tx,
+            self.shy * x + self.sy * y + self.ty,
+        )
+    }
+
+    /// Maps a vector through the transformation, ignoring translation.
+    pub fn apply_vector(&self, x: VGfloat, y: VGfloat) -> (VGfloat, VGfloat) {
+        (self.sx * x + self.shx * y, self.shy * x + self.sy * y)
+    }
+
+    /// Splits the transformation into translation, rotation, scale and shear.
+    pub fn decompose(&self) -> Decomposition {
+        let scale_x = (self.sx * self.sx + self.shy * self.shy).sqrt();
+        let (c, s) = if scale_x != 0.0 {
+            (self.sx / scale_x, self.shy / scale_x)
+        } else {
+            (1.0, 0.0)
+        };
+        // Remove the rotation from the second column to find shear and y scale.
+        let shx = c * self.shx + s * self.sy;
+        let sy = c * self.sy - s * self.shx;
+        let shear = if sy != 0.0 { shx / sy } else { 0.0 };
+        Decomposition {
+            translate_x: self.tx,
+            translate_y: self.ty,
+            rotation: s.atan2(c).to_degrees(),
+            scale_x: scale_x,
+            scale_y: sy,
+            shear: shear,
+        }
+    }
+}
+
+impl Default for Affine {
+    fn default() -> Affine {
+        Affine::identity()
+    }
+}
+
+impl Mul for Affine {
+    type Output = Affine;
+
+    fn mul(self, other: Affine) -> Affine {
+        self.multiply(&other)
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates WeeKit font modules from TrueType and OpenType fonts.
+//!
+//! The generated module has the same tables as the built-in DejaVu fonts in src/font
+//! and can be loaded with `Font::new`.
+
+#[path = "../../../src/font/sfnt.rs"]
+mod sfnt;
+
+use std::collections::{BTreeSet, HashMap};
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::fs::File;
+use std::io::Write;
+use std::process;
+
+const USAGE: &str = "usage: fontgen [--subset TEXTFILE] [--output FILE] FONT RANGES MODULE
+
+Converts the glyphs of FONT for the characters in RANGES into a Rust module named
+MODULE, written to src/font/MODULE.rs unless --output is given.
+
+RANGES is a comma-separated list of hexadecimal code points and ranges, such as
+20-7E,A0-FF,2026. With --subset, only characters that appear in TEXTFILE are kept.";
+
+/// Tables in the generated module, in the units used by `Font::new`.
+struct Tables {
+    instructions: Vec<u8>,
+    instruction_indices: Vec<usize>,
+    instruction_counts: Vec<usize>,
+    points: Vec<i32>,
+    point_indices: Vec<usize>,
+    advances: Vec<i32>,
+    character_map: Vec<i16>,
+    kerning_pairs: Vec<i32>,
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("fontgen: {}", message);
+    process::exit(1);
+}
+
+/// Parses a list like "20-7E,A0-FF,2026" into a set of characters.
+fn parse_ranges(s: &str) -> Result<BTreeSet<u32>, String> {
+    let parse = |v: &str| {
+        let v = v.trim();
+        let v = if v.starts_with("U+") || v.starts_with("u+") {
+            &v[2..]
+        } else {
+            v
+        };
+        u32::from_str_radix(v, 16).map_err(|_| format!("invalid code point '{}'", v))
+    };
+    let mut characters = BTreeSet::new();
+    for range in s.split(',').filter(|r| !r.trim().is_empty()) {
+        let mut ends = range.splitn(2, '-');
+        let first = parse(ends.next().unwrap_or(""))?;
+        let last = match ends.next() {
+            Some(last) => parse(last)?,
+            None => first,
+        };
+        if last < first || last > 0x10FFFF {
+            return Err(format!("invalid range '{}'", range));
+        }
+        // Surrogates aren't characters, so ranges that span them skip them.
+        for c in (first..last + 1).filter(|&c| std::char::from_u32(c).is_some()) {
+            characters.insert(c);
+        }
+    }
+    Ok(characters)
+}
+
+/// Converts a value in font units to the units of the built-in fonts.
+///
+/// font2openvg loaded the built-in fonts with FreeType at 64 points and 96 dpi, which
+/// FreeType rounds to 85 pixels per em, and stored 26.6 fixed-point pixels times 16.
+fn scale(value: f32, units_per_em: u16) -> i32 {
+    (value * 85.0 * 64.0 / units_per_em as f32).round() as i32 * 16
+}
+
+fn build_tables(font: &sfnt::FontFile, characters: &BTreeSet<u32>) -> Result<Tables, String> {
+    let units_per_em = font.units_per_em;
+    let mut tables = Tables {
+        instructions: Vec::new(),
+        instruction_indices: Vec::new(),
+        instruction_counts: Vec::new(),
+        points: Vec::new(),
+        point_indices: Vec::new(),
+        advances: Vec::new(),
+        character_map: Vec::new(),
+        kerning_pairs: Vec::new(),
+    };
+    let mut glyph_ids = Vec::new();
+    let mut indices: HashMap<u16, i16> = HashMap::new();
+    for &c in characters.iter() {
+        let glyph = match font.glyph_index(c) {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let index = match indices.get(&glyph) {
+            Some(&index) => index,
+            None => {
+                let outline = font.outline(glyph)?;
+                tables.instruction_indices.push(tables.instructions.len());
+                tables.instruction_counts.push(outline.commands.len());
+                tables.point_indices.push(tables.points.len() / 2);
+                tables.instructions.extend_from_slice(&outline.commands);
+                for p in outline.points.iter() {
+                    tables.points.push(scale(*p, units_per_em));
+                }
+                tables
+                    .advances
+                    .push(scale(font.advance(glyph) as f32, units_per_em));
+                glyph_ids.push(glyph);
+                let index = (tables.advances.len() - 1) as i16;
+                indices.insert(glyph, index);
+                index
+            }
+        };
+        tables.character_map.resize(c as usize + 1, -1);
+        tables.character_map[c as usize] = index;
+    }
+
+    let kerning = font.kerning();
+    if !kerning.is_empty() {
+        for (left, &l) in glyph_ids.iter().enumerate() {
+            for (right, &r) in glyph_ids.iter().enumerate() {
+                let value = scale(kerning.lookup(l, r) as f32, units_per_em);
+                if value != 0 {
+                    tables
+                        .kerning_pairs
+                        .extend_from_slice(&[left as i32, right as i32, value]);
+                }
+            }
+        }
+    }
+    Ok(tables)
+}
+
+/// Writes a static array wrapped at 100 columns the way rustfmt lays it out.
+fn write_array<T: Display>(
+    out: &mut String,
+    name: &str,
+    element: &str,
+    length: &str,
+    values: &[T],
+    suffix: &str,
+) {
+    out.push_str(&format!(
+        "pub static {}: [{}; {}] = [\n",
+        name, element, length
+    ));
+    let mut line = String::new();
+    for value in values.iter() {
+        let item = format!("{}{},", value, suffix);
+        if !line.is_empty() && 4 + line.len() + 1 + item.len() >= 100 {
+            out.push_str(&format!("    {}\n", line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&item);
+    }
+    if !line.is_empty() {
+        out.push_str(&format!("    {}\n", line));
+    }
+    out.push_str("];\n\n");
+}
+
+fn generate(font: &sfnt::FontFile, path: &str, tables: &Tables) -> String {
+    // Like font2openvg, which made the built-in fonts, measure the lowest and highest
+    // points of the converted glyphs rather than using the font's own metrics.
+    let ys: Vec<i32> = tables.points.chunks(2).map(|p| p[1]).collect();
+    let descender = ys.iter().cloned().min().unwrap_or(0);
+    let height = ys.iter().cloned().max().unwrap_or(0);
+    let glyph_count = tables.advances.len();
+
+    let mut out = String::new();
+    out.push_str(
+        "// Copyright 2018 The WeeKit Authors. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the \"License\");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//    http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an \"AS IS\" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(dead_code)]
+
+/* Generated by tools/fontgen. */
+
+",
+    );
+    out.push_str(&format!("/* converted from font file {} */\n", path));
+    if let Some(ref name) = font.family_name {
+        out.push_str(&format!("/* font family name: {} */\n", name));
+    }
+    if let Some(ref name) = font.style_name {
+        out.push_str(&format!("/* font style name: {} */\n", name));
+    }
+    out.push_str("\n");
+
+    let count = glyph_count.to_string();
+    write_array(
+        &mut out,
+        "GLYPH_INSTRUCTIONS",
+        "i8",
+        &tables.instructions.len().to_string(),
+        &tables.instructions,
+        "",
+    );
+    write_array(
+        &mut out,
+        "GLYPH_INSTRUCTION_INDICES",
+        "i32",
+        &count,
+        &tables.instruction_indices,
+        "",
+    );
+    write_array(
+        &mut out,
+        "GLYPH_INSTRUCTION_COUNTS",
+        "i32",
+        &count,
+        &tables.instruction_counts,
+        "",
+    );
+    write_array(
+        &mut out,
+        "GLYPH_POINT_INDICES",
+        "i32",
+        &count,
+        &tables.point_indices,
+        "",
+    );
+    write_array(
+        &mut out,
+        "GLYPH_POINTS",
+        "f32",
+        &format!("{} * 2", tables.points.len() / 2),
+        &tables.points,
+        ".",
+    );
+    write_array(
+        &mut out,
+        "GLYPH_ADVANCES",
+        "i32",
+        &count,
+        &tables.advances,
+        "",
+    );
+    out.push_str(&format!(
+        "pub static DESCENDER_HEIGHT: i32 = {};\n\n",
+        descender
+    ));
+    out.push_str(&format!("pub static FONT_HEIGHT: i32 = {};\n\n", height));
+    out.push_str(&format!(
+        "pub static GLYPH_COUNT: i32 = {};\n\n",
+        glyph_count
+    ));
+    write_array(
+        &mut out,
+        "CHARACTER_MAP",
+        "i16",
+        &tables.character_map.len().to_string(),
+        &tables.character_map,
+        "",
+    );
+    write_array(
+        &mut out,
+        "KERNING_PAIRS",
+        "i32",
+        &format!("{} * 3", tables.kerning_pairs.len() / 3),
+        &tables.kerning_pairs,
+        "",
+    );
+    out.pop();
+    out
+}
+
+fn main() {
+    let mut subset = None;
+    let mut output = None;
+    let mut positional = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--subset" => subset = Some(args.next().unwrap_or_else(|| fail(USAGE))),
+            "--output" => output = Some(args.next().unwrap_or_else(|| fail(USAGE))),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => positional.push(arg),
+        }
+    }
+    if positional.len() != 3 {
+        fail(USAGE);
+    }
+    let (font_path, ranges, module) = (&positional[0], &positional[1], &positional[2]);
+    if module.is_empty()
+        || !module
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        fail("module names must be lowercase identifiers like deja_vu_sans");
+    }
+
+    let mut characters = parse_ranges(ranges).unwrap_or_else(|e| fail(&e));
+    if let Some(path) = subset {
+        let text = fs::read_to_string(&path)
+            .unwrap_or_else(|e| fail(&format!("unable to read {}: {}", path, e)));
+        let used: BTreeSet<u32> = text.chars().map(|c| c as u32).collect();
+        characters = characters.intersection(&used).cloned().collect();
+    }
+
+    let data = fs::read(font_path)
+        .unwrap_or_else(|e| fail(&format!("unable to read {}: {}", font_path, e)));
+    let font = sfnt::FontFile::parse(&data).unwrap_or_else(|e| fail(&e));
+    let tables = build_tables(&font, &characters).unwrap_or_else(|e| fail(&e));
+    let source = generate(&font, font_path, &tables);
+
+    let output = output.unwrap_or_else(|| format!("src/font/{}.rs", module));
+    File::create(&output)
+        .and_then(|mut f| f.write_all(source.as_bytes()))
+        .unwrap_or_else(|e| fail(&format!("unable to write {}: {}", output, e)));
+    eprintln!(
+        "wrote {} glyphs for {} characters to {}",
+        tables.advances.len(),
+        characters.len(),
+        output
+    );
+    eprintln!(
+        "add `mod {};` to src/font/mod.rs and load it with Font::new and \
+         Font::with_kerning like Font::sans()",
+        module
+    );
+}